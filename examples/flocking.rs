@@ -0,0 +1,102 @@
+use baba::math::steering;
+use baba::prelude::*;
+
+const WIDTH: f32 = 800.;
+const HEIGHT: f32 = 600.;
+const MAX_SPEED: f32 = 3.;
+const MAX_FORCE: f32 = 0.08;
+const VIEW_RADIUS: f32 = 60.;
+
+fn main() -> baba::Result {
+    baba::run("Flocking", Flock::update)
+}
+
+struct Boid {
+    position: Vec2,
+    velocity: Vec2,
+    wander: f32,
+}
+
+struct Flock {
+    boids: Vec<Boid>,
+    seed: u32,
+}
+
+impl Default for Flock {
+    fn default() -> Self {
+        let mut flock = Self {
+            boids: Vec::new(),
+            seed: 0x1234_5678,
+        };
+
+        for _ in 0..120 {
+            let position = vec2(flock.random() * WIDTH, flock.random() * HEIGHT);
+            let velocity = Vec2::from_angle(flock.random() * TAU) * MAX_SPEED;
+            flock.boids.push(Boid {
+                position,
+                velocity,
+                wander: 0.,
+            });
+        }
+
+        flock
+    }
+}
+
+impl Flock {
+    fn update(&mut self) {
+        gfx::clear(Color::from_rgb(0x2f, 0x28, 0x43));
+
+        let mouse = input::mouse_position();
+        let scared = is_mouse_down(MouseButton::Left);
+
+        for i in 0..self.boids.len() {
+            let boid = &self.boids[i];
+            let neighbours = || {
+                self.boids.iter().filter(|other| {
+                    other.position.distance_squared(boid.position) < VIEW_RADIUS * VIEW_RADIUS
+                })
+            };
+
+            let mut force = steering::separation(
+                boid.position,
+                neighbours().map(|b| b.position),
+                VIEW_RADIUS / 2.,
+            ) * 4.;
+            force += steering::alignment(boid.velocity, neighbours().map(|b| b.velocity));
+            force += steering::cohesion(
+                boid.position,
+                boid.velocity,
+                neighbours().map(|b| b.position),
+                MAX_SPEED,
+            );
+            force += steering::wander(boid.velocity, 8., 4., boid.wander) * 0.02;
+            if scared && boid.position.distance(mouse) < VIEW_RADIUS * 2. {
+                force += steering::flee(boid.position, boid.velocity, mouse, MAX_SPEED) * 4.;
+            }
+
+            let jitter = (self.random() - 0.5) * 0.4;
+            let boid = &mut self.boids[i];
+            boid.wander += jitter;
+            boid.velocity =
+                (boid.velocity + force.clamp_length_max(MAX_FORCE)).clamp_length_max(MAX_SPEED);
+        }
+
+        for boid in &mut self.boids {
+            boid.position += boid.velocity;
+            boid.position = boid.position.rem_euclid(vec2(WIDTH, HEIGHT));
+
+            let heading = boid.velocity.normalize_or_zero() * 8.;
+            gfx::draw_circle(4., Color::from_rgb(0xf0, 0xc0, 0x60), boid.position);
+            gfx::draw_line(Vec2::ZERO, heading, 2., Color::WHITE, boid.position);
+        }
+    }
+
+    /// A tiny xorshift generator, so the example doesn't need extra dependencies.
+    fn random(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        self.seed as f32 / u32::MAX as f32
+    }
+}
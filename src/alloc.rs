@@ -0,0 +1,241 @@
+//! Scratch memory for data that only lives for a single frame.
+//!
+//! The engine owns a [`FrameArena`] which is reset after every frame. It's used internally for
+//! temporary geometry, like the vertices of [shapes][crate::gfx::Shape], and you can use it too
+//! through [`with_frame_arena`], to avoid allocating short-lived buffers every frame.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! baba::alloc::with_frame_arena(|arena| {
+//!     let points = arena.alloc_slice_fill(16, Vec2::ZERO);
+//!     for (i, point) in points.iter_mut().enumerate() {
+//!         *point = vec2(i as f32, 0.);
+//!     }
+//! });
+//! ```
+
+use std::alloc::Layout;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+/// Size of the first chunk allocated by an arena, in bytes.
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+thread_local! {
+    static FRAME_ARENA: RefCell<FrameArena> = const { RefCell::new(FrameArena::new()) };
+}
+
+/// Provides access to the engine's per-frame arena.
+///
+/// Anything allocated in it will be freed at the end of the frame.
+pub fn with_frame_arena<T>(f: impl FnOnce(&FrameArena) -> T) -> T {
+    FRAME_ARENA.with_borrow(f)
+}
+
+/// Resets the engine's arena. This is done by the main loop after each frame.
+pub(crate) fn reset_frame_arena() {
+    FRAME_ARENA.with_borrow_mut(FrameArena::reset);
+}
+
+/// A bump allocator.
+///
+/// Allocating is just moving a pointer forward, and everything is freed at once with
+/// [`reset`][FrameArena::reset]. Only [`Copy`] types can be stored, as nothing is ever dropped.
+pub struct FrameArena {
+    // Chunks are kept as raw pointers, as allocations hand out references into them
+    chunks: RefCell<Vec<NonNull<[MaybeUninit<u8>]>>>,
+    offset: Cell<usize>,
+}
+
+impl FrameArena {
+    /// Creates an empty arena. Nothing is allocated until it's used.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            chunks: RefCell::new(Vec::new()),
+            offset: Cell::new(0),
+        }
+    }
+
+    /// Stores a value in the arena.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<T: Copy>(&self, value: T) -> &mut T {
+        &mut self.alloc_slice_fill(1, value)[0]
+    }
+
+    /// Allocates a slice of `len` copies of `value`.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_fill<T: Copy>(&self, len: usize, value: T) -> &mut [T] {
+        let slice = self.alloc_uninit::<T>(len);
+        for item in slice.iter_mut() {
+            item.write(value);
+        }
+        // SAFETY: every item was just initialised
+        unsafe { &mut *(std::ptr::from_mut(slice) as *mut [T]) }
+    }
+
+    /// Copies a slice into the arena.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_copy<T: Copy>(&self, src: &[T]) -> &mut [T] {
+        let slice = self.alloc_uninit::<T>(src.len());
+        for (item, value) in slice.iter_mut().zip(src) {
+            item.write(*value);
+        }
+        // SAFETY: every item was just initialised
+        unsafe { &mut *(std::ptr::from_mut(slice) as *mut [T]) }
+    }
+
+    /// Copies a string into the arena.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_str(&self, src: &str) -> &mut str {
+        let bytes = self.alloc_slice_copy(src.as_bytes());
+        // SAFETY: the bytes were copied from a valid str
+        unsafe { std::str::from_utf8_unchecked_mut(bytes) }
+    }
+
+    /// Formats a string into the arena, without allocating on the heap.
+    ///
+    /// ```
+    /// # let arena = baba::alloc::FrameArena::new();
+    /// let score = 42;
+    /// let text = arena.alloc_fmt(format_args!("Score: {score}"));
+    /// assert_eq!(text, "Score: 42");
+    /// ```
+    pub fn alloc_fmt(&self, args: fmt::Arguments) -> &str {
+        if let Some(s) = args.as_str() {
+            return self.alloc_str(s);
+        }
+        let mut writer = ArenaWriter {
+            arena: self,
+            buf: &mut [],
+            len: 0,
+        };
+        fmt::write(&mut writer, args).expect("a Display implementation returned an error");
+        let bytes = &writer.buf[..writer.len];
+        // SAFETY: the bytes are initialised, and were copied from whole strs
+        unsafe { std::str::from_utf8_unchecked(&*(std::ptr::from_ref(bytes) as *const [u8])) }
+    }
+
+    /// Allocates room for `len` items of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `len` items overflows.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_uninit<T: Copy>(&self, len: usize) -> &mut [MaybeUninit<T>] {
+        let layout = Layout::array::<T>(len).expect("arena allocation is too large");
+        let (size, align) = (layout.size(), layout.align());
+        if size == 0 {
+            let ptr = NonNull::<MaybeUninit<T>>::dangling().as_ptr();
+            // SAFETY: zero-sized slices may use a dangling pointer
+            return unsafe { std::slice::from_raw_parts_mut(ptr, len) };
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+        let reserved = chunks
+            .last()
+            .and_then(|&c| bump(c, self.offset.get(), size, align));
+        let (ptr, offset) = reserved.unwrap_or_else(|| {
+            let last = chunks.last().map_or(0, |c| c.len());
+            let needed = size
+                .checked_add(align)
+                .expect("arena allocation is too large");
+            let capacity = last.saturating_mul(2).max(DEFAULT_CHUNK_SIZE).max(needed);
+            let chunk = new_chunk(capacity);
+            chunks.push(chunk);
+
+            bump(chunk, 0, size, align).expect("chunk is large enough")
+        });
+        self.offset.set(offset);
+
+        // SAFETY: this range was reserved above, and chunks are never moved or freed while the
+        // arena is borrowed
+        unsafe { std::slice::from_raw_parts_mut(ptr.as_ptr().cast(), len) }
+    }
+
+    /// Total memory reserved by this arena, in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.chunks.borrow().iter().map(|c| c.len()).sum()
+    }
+
+    /// Frees everything in the arena.
+    ///
+    /// If more than one chunk was needed, they're merged into a single one, so the next frame
+    /// won't need to allocate at all.
+    pub fn reset(&mut self) {
+        let chunks = self.chunks.get_mut();
+        if chunks.len() > 1 {
+            let capacity = chunks.iter().map(|c| c.len()).sum();
+            for chunk in chunks.drain(..) {
+                // SAFETY: chunks come from `new_chunk`, and nothing borrows the arena
+                drop(unsafe { Box::from_raw(chunk.as_ptr()) });
+            }
+            chunks.push(new_chunk(capacity));
+        }
+        self.offset.set(0);
+    }
+}
+
+impl Drop for FrameArena {
+    fn drop(&mut self) {
+        for chunk in self.chunks.get_mut().drain(..) {
+            // SAFETY: chunks come from `new_chunk`, and nothing borrows the arena
+            drop(unsafe { Box::from_raw(chunk.as_ptr()) });
+        }
+    }
+}
+
+impl Default for FrameArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats into the arena for [`FrameArena::alloc_fmt`], moving to a larger allocation when the
+/// text doesn't fit. The smaller ones are only freed with the rest of the arena.
+struct ArenaWriter<'a> {
+    arena: &'a FrameArena,
+    buf: &'a mut [MaybeUninit<u8>],
+    len: usize,
+}
+
+impl fmt::Write for ArenaWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            let grown = self.arena.alloc_uninit(end.max(self.buf.len() * 2).max(64));
+            grown[..self.len].copy_from_slice(&self.buf[..self.len]);
+            self.buf = grown;
+        }
+        for (byte, value) in self.buf[self.len..end].iter_mut().zip(s.as_bytes()) {
+            byte.write(*value);
+        }
+        self.len = end;
+        Ok(())
+    }
+}
+
+fn new_chunk(capacity: usize) -> NonNull<[MaybeUninit<u8>]> {
+    NonNull::from(Box::leak(Box::new_uninit_slice(capacity)))
+}
+
+/// Reserves `size` bytes in a chunk, returning a pointer to them and the new offset.
+fn bump(
+    chunk: NonNull<[MaybeUninit<u8>]>,
+    offset: usize,
+    size: usize,
+    align: usize,
+) -> Option<(NonNull<u8>, usize)> {
+    let base = chunk.cast::<u8>();
+    let start = offset + base.as_ptr().wrapping_add(offset).align_offset(align);
+    let end = start.checked_add(size)?;
+    if end > chunk.len() {
+        return None;
+    }
+
+    // SAFETY: start is within the chunk
+    Some((unsafe { base.add(start) }, end))
+}
@@ -0,0 +1,208 @@
+//! Analytics events, for measuring how players get through a game.
+//!
+//! ```no_run
+//! # let (level, seconds) = (3, 94.5);
+//! use baba::analytics;
+//!
+//! analytics::event("level_complete", &[("level", &level), ("time", &seconds)]);
+//! ```
+//!
+//! Events are queued until a [`Sink`] accepts them. baba doesn't send them anywhere by itself, so
+//! set a sink which uploads them to your own server. Events the sink couldn't send are kept for
+//! the next [`flush`], and with a [queue file][set_queue_file] they're kept on disk, so events
+//! from players who are offline aren't lost when the game closes.
+//!
+//! ```no_run
+//! use baba::analytics::{self, Event, Sink};
+//!
+//! struct Server;
+//!
+//! impl Sink for Server {
+//!     fn send(&mut self, events: &[Event]) -> bool {
+//!         // Upload the events, returning whether it worked
+//!         false
+//!     }
+//! }
+//!
+//! analytics::set_sink(Server);
+//! analytics::set_queue_file("analytics.queue");
+//!
+//! // Every now and then, and before the game closes
+//! analytics::flush();
+//! ```
+
+use std::cell::RefCell;
+use std::fmt::{Display, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Something that happened in the game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// What happened, like `"level_complete"`.
+    pub name: String,
+    /// Extra information, as pairs of names and values.
+    pub properties: Vec<(String, String)>,
+    /// When it happened, in seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// Sends events somewhere, like to a server.
+pub trait Sink {
+    /// Sends a batch of events, returning whether it worked. If it didn't, the same events are
+    /// sent again on the next [`flush`].
+    fn send(&mut self, events: &[Event]) -> bool;
+}
+
+#[derive(Default)]
+struct Analytics {
+    sink: Option<Box<dyn Sink>>,
+    queue: Vec<Event>,
+    queue_file: Option<PathBuf>,
+}
+
+thread_local! {
+    static ANALYTICS: RefCell<Analytics> = RefCell::new(Analytics::default());
+}
+
+/// Records an event. It isn't sent until the next [`flush`].
+pub fn event(name: &str, properties: &[(&str, &dyn Display)]) {
+    let properties = properties
+        .iter()
+        .map(|(key, value)| ((*key).to_owned(), value.to_string()))
+        .collect();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    ANALYTICS.with_borrow_mut(|analytics| {
+        analytics.queue.push(Event {
+            name: name.to_owned(),
+            properties,
+            timestamp,
+        });
+    });
+}
+
+/// Sets where events are sent.
+pub fn set_sink(sink: impl Sink + 'static) {
+    ANALYTICS.with_borrow_mut(|analytics| analytics.sink = Some(Box::new(sink)));
+}
+
+/// Keeps events which haven't been sent in a file, so they can be sent the next time the game
+/// runs. Any events already in the file are queued again.
+pub fn set_queue_file(path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let saved = match std::fs::read_to_string(path) {
+        Ok(text) => text.lines().filter_map(parse_event).collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => {
+            log::error!("Failed to read {}: {e}", path.display());
+            Vec::new()
+        }
+    };
+    ANALYTICS.with_borrow_mut(|analytics| {
+        analytics.queue.splice(0..0, saved);
+        analytics.queue_file = Some(path.to_owned());
+    });
+}
+
+/// Number of events which haven't been sent yet.
+#[must_use]
+pub fn pending() -> usize {
+    ANALYTICS.with_borrow(|analytics| analytics.queue.len())
+}
+
+/// Sends every queued event to the sink. If there's no sink, or it fails, they stay queued, and
+/// are saved to the queue file if there is one.
+pub fn flush() {
+    let (batch, mut sink) = ANALYTICS
+        .with_borrow_mut(|analytics| (std::mem::take(&mut analytics.queue), analytics.sink.take()));
+
+    // The sink runs outside of the borrow, so it can record events, like to report a failure
+    let sent = batch.is_empty() || sink.as_mut().is_some_and(|sink| sink.send(&batch));
+
+    ANALYTICS.with_borrow_mut(|analytics| {
+        // Unless it was replaced while sending
+        if analytics.sink.is_none() {
+            analytics.sink = sink;
+        }
+        if !sent {
+            // Events recorded while sending happened after these
+            analytics.queue.splice(0..0, batch);
+        } else if batch.is_empty() {
+            return;
+        }
+
+        let Some(path) = &analytics.queue_file else {
+            return;
+        };
+        let mut text = String::new();
+        for event in &analytics.queue {
+            write_event(&mut text, event);
+        }
+        if let Err(e) = std::fs::write(path, text) {
+            log::error!("Failed to write {}: {e}", path.display());
+        }
+    });
+}
+
+// The queue file has one event per line, with tab-separated fields: the timestamp, the name, and
+// then alternating property names and values.
+
+fn write_event(out: &mut String, event: &Event) {
+    let _ = write!(out, "{}\t", event.timestamp);
+    escape(out, &event.name);
+    for (key, value) in &event.properties {
+        out.push('\t');
+        escape(out, key);
+        out.push('\t');
+        escape(out, value);
+    }
+    out.push('\n');
+}
+
+fn parse_event(line: &str) -> Option<Event> {
+    let mut fields = line.split('\t').map(unescape);
+    let timestamp = fields.next()?.parse().ok()?;
+    let name = fields.next()?;
+    let mut properties = Vec::new();
+    while let (Some(key), Some(value)) = (fields.next(), fields.next()) {
+        properties.push((key, value));
+    }
+    Some(Event {
+        name,
+        properties,
+        timestamp,
+    })
+}
+
+fn escape(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(c) => out.push(c),
+            None => {}
+        }
+    }
+    out
+}
@@ -0,0 +1,308 @@
+//! Animation state machines, which pick a character's animation from parameters like their
+//! speed, instead of a pile of `if`s.
+//!
+//! ```
+//! use baba::animator::{Animator, Condition};
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! enum Anim {
+//!     Idle,
+//!     Run,
+//!     Jump,
+//!     Attack,
+//! }
+//!
+//! let mut animator = Animator::new(Anim::Idle)
+//!     .with_transition(Anim::Idle, Anim::Run, [Condition::above("speed", 0.1)])
+//!     .with_transition(Anim::Run, Anim::Idle, [Condition::below("speed", 0.1)])
+//!     .with_any_transition(Anim::Jump, [Condition::not("grounded")])
+//!     .with_transition(Anim::Jump, Anim::Idle, [Condition::is("grounded")])
+//!     .with_any_transition(Anim::Attack, [Condition::trigger("attack")])
+//!     .with_transition(Anim::Attack, Anim::Idle, [])
+//!     // Attacks play for at least 0.4 seconds before anything else
+//!     .with_min_time(Anim::Attack, 0.4);
+//!
+//! // Every frame
+//! animator.set_float("speed", 3.);
+//! animator.set_bool("grounded", true);
+//! animator.update();
+//! assert_eq!(animator.current(), &Anim::Run);
+//! ```
+//!
+//! Animations can be anything, like an enum or the name of a clip, and the animator only says
+//! which one should play. [`blend`][Animator::blend] gives a weight for crossfading from the
+//! previous one.
+
+use std::collections::BTreeMap;
+
+use crate::time;
+
+/// Something a [`Transition`] checks about the animator's parameters.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Condition {
+    /// A bool parameter is true.
+    Is(String),
+    /// A bool parameter is false, or was never set.
+    Not(String),
+    /// A float parameter is greater than a value.
+    Above(String, f32),
+    /// A float parameter is less than a value.
+    Below(String, f32),
+    /// A trigger was set since the last update.
+    Trigger(String),
+}
+
+impl Condition {
+    /// A bool parameter is true.
+    pub fn is(name: impl Into<String>) -> Self {
+        Self::Is(name.into())
+    }
+
+    /// A bool parameter is false, or was never set.
+    pub fn not(name: impl Into<String>) -> Self {
+        Self::Not(name.into())
+    }
+
+    /// A float parameter is greater than a value.
+    pub fn above(name: impl Into<String>, value: f32) -> Self {
+        Self::Above(name.into(), value)
+    }
+
+    /// A float parameter is less than a value.
+    pub fn below(name: impl Into<String>, value: f32) -> Self {
+        Self::Below(name.into(), value)
+    }
+
+    /// A trigger was set since the last update.
+    pub fn trigger(name: impl Into<String>) -> Self {
+        Self::Trigger(name.into())
+    }
+}
+
+/// A rule for moving from one animation to another.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transition<A> {
+    /// The animation this moves from, or `None` to move from any other animation.
+    pub from: Option<A>,
+    /// The animation this moves to.
+    pub to: A,
+    /// Conditions which must all be met. With none, the transition happens as soon as the
+    /// current animation has played for its minimum time.
+    pub conditions: Vec<Condition>,
+}
+
+/// Picks which animation plays, moving between them when their [`Transition`]s' conditions are
+/// met.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Animator<A> {
+    transitions: Vec<Transition<A>>,
+    min_times: Vec<(A, f32)>,
+    blend_time: f32,
+    floats: BTreeMap<String, f32>,
+    triggers: Vec<String>,
+    current: A,
+    previous: Option<A>,
+    time: f32,
+}
+
+impl<A: Clone + PartialEq> Animator<A> {
+    /// Creates an animator which starts on an animation.
+    pub const fn new(initial: A) -> Self {
+        Self {
+            transitions: Vec::new(),
+            min_times: Vec::new(),
+            blend_time: 0.,
+            floats: BTreeMap::new(),
+            triggers: Vec::new(),
+            current: initial,
+            previous: None,
+            time: 0.,
+        }
+    }
+
+    /// Adds a transition between two animations. Transitions are checked in the order they're
+    /// added, and the first one whose conditions are met is taken.
+    pub fn with_transition(
+        mut self,
+        from: A,
+        to: A,
+        conditions: impl IntoIterator<Item = Condition>,
+    ) -> Self {
+        self.transitions.push(Transition {
+            from: Some(from),
+            to,
+            conditions: conditions.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Adds a transition to an animation from any other one, like for getting hit.
+    pub fn with_any_transition(
+        mut self,
+        to: A,
+        conditions: impl IntoIterator<Item = Condition>,
+    ) -> Self {
+        self.transitions.push(Transition {
+            from: None,
+            to,
+            conditions: conditions.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Makes an animation play for at least some seconds before any transition away from it,
+    /// so that things like attacks aren't cut short.
+    pub fn with_min_time(mut self, animation: A, seconds: f32) -> Self {
+        self.min_times.retain(|(a, _)| *a != animation);
+        self.min_times.push((animation, seconds));
+        self
+    }
+
+    /// Sets how long animations crossfade for, in seconds, as given by
+    /// [`blend`][Self::blend]. Defaults to 0.
+    pub const fn with_blend_time(mut self, seconds: f32) -> Self {
+        self.blend_time = seconds;
+        self
+    }
+
+    /// Sets a float parameter, like the character's speed.
+    pub fn set_float(&mut self, name: &str, value: f32) {
+        match self.floats.get_mut(name) {
+            Some(v) => *v = value,
+            None => {
+                self.floats.insert(name.to_owned(), value);
+            }
+        }
+    }
+
+    /// Sets a bool parameter, like whether the character is on the ground.
+    pub fn set_bool(&mut self, name: &str, value: bool) {
+        self.set_float(name, f32::from(value));
+    }
+
+    /// Sets a trigger, like for an attack. Triggers last until the next
+    /// [`update`][Self::update] which checks transitions, whether one used them or not. While
+    /// the current animation hasn't played for its minimum time, they're kept.
+    pub fn trigger(&mut self, name: &str) {
+        if !self.triggers.iter().any(|t| t == name) {
+            self.triggers.push(name.to_owned());
+        }
+    }
+
+    /// A float parameter, or 0 if it was never set.
+    #[must_use]
+    pub fn float(&self, name: &str) -> f32 {
+        self.floats.get(name).copied().unwrap_or(0.)
+    }
+
+    /// A bool parameter, or `false` if it was never set.
+    #[must_use]
+    pub fn bool(&self, name: &str) -> bool {
+        self.float(name) != 0.
+    }
+
+    fn is_met(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Is(name) => self.bool(name),
+            Condition::Not(name) => !self.bool(name),
+            Condition::Above(name, value) => self.float(name) > *value,
+            Condition::Below(name, value) => self.float(name) < *value,
+            Condition::Trigger(name) => self.triggers.contains(name),
+        }
+    }
+
+    fn min_time(&self) -> f32 {
+        self.min_times
+            .iter()
+            .find(|(a, _)| *a == self.current)
+            .map_or(0., |&(_, seconds)| seconds)
+    }
+
+    /// Moves time forward, and takes a transition if one's conditions are met. Returns whether
+    /// the animation changed. This should be called every frame, after setting parameters.
+    pub fn update(&mut self) -> bool {
+        self.time += time::delta();
+        if self.time < self.min_time() {
+            return false;
+        }
+        let next = self.transitions.iter().find(|t| {
+            t.from.as_ref().is_none_or(|from| *from == self.current)
+                && t.to != self.current
+                && t.conditions.iter().all(|c| self.is_met(c))
+        });
+        let next = next.cloned();
+
+        self.triggers.clear();
+        let Some(transition) = next else {
+            return false;
+        };
+        self.play(transition.to);
+        true
+    }
+
+    /// Switches to an animation right away, ignoring transitions. Does nothing if it's already
+    /// playing.
+    pub fn play(&mut self, animation: A) {
+        if animation == self.current {
+            return;
+        }
+        self.previous = Some(std::mem::replace(&mut self.current, animation));
+        self.time = 0.;
+    }
+
+    /// The animation which should be playing.
+    #[must_use]
+    pub const fn current(&self) -> &A {
+        &self.current
+    }
+
+    /// The animation which played before the current one, if any.
+    #[must_use]
+    pub const fn previous(&self) -> Option<&A> {
+        self.previous.as_ref()
+    }
+
+    /// How long the current animation has been playing, in seconds.
+    #[must_use]
+    pub const fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// How much of the current animation to show while crossfading from the previous one, from
+    /// 0 to 1.
+    #[must_use]
+    pub fn blend(&self) -> f32 {
+        if self.previous.is_none() || self.blend_time <= 0. {
+            return 1.;
+        }
+        (self.time / self.blend_time).min(1.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn trigger_waits_for_min_time() {
+        let mut animator = Animator::new("attack")
+            .with_min_time("attack", 0.5)
+            .with_transition("attack", "combo", [Condition::trigger("attack")]);
+
+        time::advance(Duration::from_millis(100));
+        animator.trigger("attack");
+        assert!(!animator.update());
+        assert_eq!(animator.current(), &"attack");
+
+        for _ in 0..5 {
+            time::advance(Duration::from_millis(100));
+            animator.update();
+        }
+        assert_eq!(animator.current(), &"combo");
+    }
+}
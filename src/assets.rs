@@ -0,0 +1,319 @@
+//! Cached asset loading, for any type.
+//!
+//! Implement [`Asset`] for your own types, then [`load`] them. Each path is loaded once, and
+//! every [`Handle`] to it shares the same value.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! use std::path::Path;
+//! use baba::assets::{self, Asset};
+//!
+//! struct Level {
+//!     tiles: Vec<u8>,
+//! }
+//!
+//! impl Asset for Level {
+//!     type Error = std::io::Error;
+//!
+//!     fn load_asset(path: &Path) -> Result<Self, Self::Error> {
+//!         Ok(Self { tiles: std::fs::read(path)? })
+//!     }
+//!
+//!     fn placeholder() -> Self {
+//!         Self { tiles: Vec::new() }
+//!     }
+//! }
+//!
+//! let level = assets::load::<Level>("resources/level1.bin");
+//! let player = assets::load::<Texture>("resources/player.png");
+//! gfx::draw(&player, vec2(10., 10.));
+//! ```
+//!
+//! In debug builds, assets are reloaded when their file changes, and every handle sees the new
+//! value. Loading is synchronous, as textures can only be created on the main thread.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use crate::audio::Sound;
+use crate::gfx::{Canvas, Drawable, Texture, TextureOptions, Transform};
+
+/// A type which can be loaded from a file, through [`load`].
+pub trait Asset: Sized + 'static {
+    /// Error returned when loading fails.
+    type Error: std::fmt::Display;
+
+    /// Loads this asset from a file.
+    fn load_asset(path: &Path) -> Result<Self, Self::Error>;
+
+    /// A value to use when loading fails.
+    fn placeholder() -> Self;
+}
+
+impl Asset for Texture {
+    type Error = crate::gfx::TextureLoadError;
+
+    fn load_asset(path: &Path) -> Result<Self, Self::Error> {
+        Self::try_load(path, TextureOptions::default())
+    }
+
+    fn placeholder() -> Self {
+        Self::empty()
+    }
+}
+
+impl Asset for Sound {
+    type Error = crate::audio::LoadError;
+
+    fn load_asset(path: &Path) -> Result<Self, Self::Error> {
+        Self::try_load(path)
+    }
+
+    fn placeholder() -> Self {
+        Self::empty()
+    }
+}
+
+/// A shared reference to a loaded asset.
+///
+/// This is cheap to clone. The value may change when the asset is [reloaded][reload].
+pub struct Handle<T> {
+    slot: Rc<Slot<T>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        let slot = self.slot.clone();
+        Self { slot }
+    }
+}
+
+impl<T: Asset> Handle<T> {
+    /// The current value of this asset.
+    #[must_use]
+    pub fn get(&self) -> Rc<T> {
+        self.slot.value.borrow().clone()
+    }
+
+    /// The path this asset was loaded from.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.slot.path
+    }
+}
+
+impl<T: Asset + Drawable> Drawable for Handle<T> {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        self.get().draw(canvas, transform);
+    }
+}
+
+struct Slot<T> {
+    path: PathBuf,
+    value: RefCell<Rc<T>>,
+    modified: RefCell<Option<SystemTime>>,
+}
+
+impl<T: Asset> Slot<T> {
+    fn load(&self) {
+        *self.modified.borrow_mut() = modified(&self.path);
+        let value = T::load_asset(&self.path)
+            .inspect_err(|e| log::error!("Failed to load {}: {e}", self.path.display()))
+            .unwrap_or_else(|_| T::placeholder());
+        *self.value.borrow_mut() = Rc::new(value);
+    }
+}
+
+trait Entry {
+    fn as_any(&self) -> &dyn Any;
+    fn reload(&self);
+    fn is_outdated(&self) -> bool;
+}
+
+impl<T: Asset> Entry for Rc<Slot<T>> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn reload(&self) {
+        self.load();
+    }
+
+    fn is_outdated(&self) -> bool {
+        *self.modified.borrow() != modified(&self.path)
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+thread_local! {
+    static ASSETS: RefCell<HashMap<(TypeId, PathBuf), Box<dyn Entry>>> =
+        RefCell::new(HashMap::new());
+    static REQUESTED: RefCell<BTreeSet<PathBuf>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+/// Loads an asset, or returns it from the cache if it was already loaded.
+///
+/// If it fails to load, an error is logged and the handle holds a
+/// [placeholder][Asset::placeholder].
+pub fn load<T: Asset>(path: impl AsRef<Path>) -> Handle<T> {
+    let path = path.as_ref();
+    let key = (TypeId::of::<T>(), path.to_owned());
+    record(path);
+
+    let cached = ASSETS.with_borrow(|assets| {
+        let entry = assets.get(&key)?;
+        entry.as_any().downcast_ref::<Rc<Slot<T>>>().cloned()
+    });
+    if let Some(slot) = cached {
+        return Handle { slot };
+    }
+
+    let slot = Rc::new(Slot {
+        path: path.to_owned(),
+        value: RefCell::new(Rc::new(T::placeholder())),
+        modified: RefCell::new(None),
+    });
+    slot.load();
+
+    ASSETS.with_borrow_mut(|assets| assets.insert(key, Box::new(slot.clone())));
+    Handle { slot }
+}
+
+/// Remembers that a path was loaded, for [`check`]. Every loader in the engine calls this.
+pub(crate) fn record(path: &Path) {
+    REQUESTED.with_borrow_mut(|requested| requested.insert(normalize(path)));
+}
+
+/// Reloads an asset from its file, updating every handle to it.
+pub fn reload<T: Asset>(handle: &Handle<T>) {
+    handle.slot.load();
+}
+
+/// Reloads every asset whose file changed since it was loaded.
+///
+/// This is called for you about once a second in debug builds.
+pub fn reload_changed() {
+    let outdated: Vec<_> = ASSETS.with_borrow(|assets| {
+        let outdated = assets.iter().filter(|(_, entry)| entry.is_outdated());
+        outdated.map(|(key, _)| key.clone()).collect()
+    });
+
+    for key in outdated {
+        log::info!("Reloading {}", key.1.display());
+        // Loading may itself load other assets, so the cache can't stay borrowed
+        let entry = ASSETS.with_borrow_mut(|assets| assets.remove(&key));
+        if let Some(entry) = entry {
+            entry.reload();
+            ASSETS.with_borrow_mut(|assets| assets.insert(key, entry));
+        }
+    }
+}
+
+/// Forgets all cached assets. Existing handles keep their values, but won't be reloaded.
+pub fn clear_cache() {
+    ASSETS.with_borrow_mut(HashMap::clear);
+}
+
+/// Missing and unused assets, found by [`check`].
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// Paths which were loaded, but don't exist.
+    pub missing: Vec<PathBuf>,
+    /// Files in the asset directory which were never loaded.
+    pub unused: Vec<PathBuf>,
+}
+
+impl Report {
+    /// Were there no missing or unused assets?
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.unused.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for path in &self.missing {
+            writeln!(f, "missing: {}", path.display())?;
+        }
+        for path in &self.unused {
+            writeln!(f, "unused: {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares every path loaded so far with the files in an asset directory. This includes paths
+/// passed to [`load`], and to the engine's own loaders, like
+/// [`Texture::load`][crate::gfx::Texture::load] and `data::table`.
+///
+/// This is best run after loading everything up front, or at the end of a playtest, to catch
+/// typos in paths and leftover files:
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// let report = baba::assets::check("resources");
+/// if !report.is_ok() {
+///     warn!("Asset problems:\n{report}");
+/// }
+/// ```
+pub fn check(dir: impl AsRef<Path>) -> Report {
+    let dir = normalize(dir.as_ref());
+    let requested = REQUESTED.with_borrow(Clone::clone);
+
+    let missing = requested.iter().filter(|path| !path.is_file());
+    let mut files = Vec::new();
+    if let Err(e) = list_files(&dir, &mut files) {
+        log::warn!("Failed to read {}: {e}", dir.display());
+    }
+    files.sort();
+
+    Report {
+        missing: missing.cloned().collect(),
+        unused: files
+            .into_iter()
+            .filter(|f| !requested.contains(f))
+            .collect(),
+    }
+}
+
+/// Checks that paths exist, before they're loaded. Returns the ones which don't.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// const SPRITES: &[&str] = &["resources/player.png", "resources/slime.png"];
+///
+/// for path in baba::assets::find_missing(SPRITES) {
+///     warn!("Missing sprite: {}", path.display());
+/// }
+/// ```
+pub fn find_missing<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> Vec<PathBuf> {
+    let paths = paths.into_iter().map(|p| normalize(p.as_ref()));
+    paths.filter(|path| !path.is_file()).collect()
+}
+
+fn list_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            list_files(&path, files)?;
+        } else {
+            files.push(normalize(&path));
+        }
+    }
+    Ok(())
+}
+
+/// Removes redundant parts of a path, like `./`, so that the same file compares equal.
+fn normalize(path: &Path) -> PathBuf {
+    let components = path.components();
+    components.filter(|c| c != &Component::CurDir).collect()
+}
@@ -0,0 +1,439 @@
+//! Audio playback.
+//!
+//! Sound effects are loaded as [`Sound`]s, and played with [`Sound::play`]. One sound can be
+//! played as looping music with [`play_music`].
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! use baba::audio::{self, Sound};
+//!
+//! let jump = Sound::load("resources/jump.wav");
+//! let song = Sound::load("resources/song.wav");
+//!
+//! audio::play_music(&song);
+//! if is_key_pressed(KeyCode::Space) {
+//!     jump.play();
+//! }
+//! ```
+//!
+//! The mixer runs on its own thread. Calls here only queue commands for it, so they never wait
+//! on the mixer, and the mixer never waits on the game, even when a frame takes a long time.
+//! The queues between them have a fixed size, so the mixer doesn't allocate or free memory
+//! while it's using them either.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::panic::Location;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
+
+use sdl2::audio::{
+    AudioCVT, AudioCallback, AudioDevice, AudioFormat, AudioSpecDesired, AudioSpecWAV,
+};
+use sdl2::rwops::RWops;
+use thiserror::Error;
+
+use crate::SdlError;
+
+/// Sample rate used by the mixer. Sounds are converted to it when they're loaded.
+const SAMPLE_RATE: i32 = 48000;
+/// Number of channels used by the mixer.
+const CHANNELS: u8 = 2;
+
+/// Number of sounds which can play at once before the mixer has to allocate.
+const VOICES: usize = 64;
+/// Number of commands which can wait for the mixer. More are held back until it catches up.
+const COMMANDS: usize = 256;
+
+thread_local! {
+    static AUDIO: RefCell<Option<Audio>> = const { RefCell::new(None) };
+}
+
+/// The game's end of the mixer.
+struct Audio {
+    _device: AudioDevice<Mixer>,
+    commands: SyncSender<Command>,
+    // Commands which didn't fit in the queue, in order
+    held: VecDeque<Command>,
+    finished: Receiver<Voice>,
+}
+
+/// Something for the mixer to do, sent from the game.
+enum Command {
+    Play(Voice),
+    PlayMusic(Voice),
+    StopMusic,
+    SetMusicPaused(bool),
+    SetMusicVolume(f32),
+    SetVolume(f32),
+    StopSounds,
+}
+
+/// Sound load error.
+pub type LoadError = crate::LoadError<LoadErrorKind>;
+
+/// What went wrong while loading a sound.
+#[derive(Debug, Error)]
+pub enum LoadErrorKind {
+    /// This sound couldn't be opened.
+    #[error("couldn't read file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The sound couldn't be decoded. Only WAV files are supported.
+    #[error("couldn't decode sound: {0}")]
+    Decode(#[from] SdlError),
+}
+
+/// A sound effect, or a song.
+///
+/// The audio data is shared, so cloning a sound is cheap.
+#[must_use]
+#[derive(Clone)]
+pub struct Sound {
+    samples: Arc<[f32]>,
+    volume: f32,
+}
+
+impl Sound {
+    /// Creates an empty sound, which plays nothing. This is a placeholder value.
+    pub fn empty() -> Self {
+        Self {
+            samples: Arc::new([]),
+            volume: 1.,
+        }
+    }
+
+    /// Loads a WAV file at a given path.
+    #[track_caller]
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        Self::try_load(path)
+            .inspect_err(|e| log::error!("Failed to load {e}"))
+            .unwrap_or_else(|_| Self::empty())
+    }
+
+    /// Like [`load`][Sound::load], but returns an error instead of outputting a warning.
+    #[track_caller]
+    pub fn try_load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        let caller = Location::caller();
+        let path = path.as_ref();
+        crate::assets::record(path);
+
+        std::fs::read(path)
+            .map_err(LoadErrorKind::from)
+            .and_then(|bytes| Self::decode_wav(&bytes))
+            .map_err(|kind| LoadError::new(kind, Some(path), caller))
+    }
+
+    /// Loads a sound from WAV data in memory.
+    #[track_caller]
+    pub fn from_wav(bytes: &[u8]) -> Result<Self, LoadError> {
+        let caller = Location::caller();
+        Self::decode_wav(bytes).map_err(|kind| LoadError::new(kind, None, caller))
+    }
+
+    fn decode_wav(bytes: &[u8]) -> Result<Self, LoadErrorKind> {
+        let error = |_| SdlError::from_sdl();
+        let mut rw = RWops::from_bytes(bytes).map_err(error)?;
+        let wav = AudioSpecWAV::load_wav_rw(&mut rw).map_err(error)?;
+
+        let cvt = AudioCVT::new(
+            wav.format,
+            wav.channels,
+            wav.freq,
+            AudioFormat::f32_sys(),
+            CHANNELS,
+            SAMPLE_RATE,
+        )
+        .map_err(error)?;
+        let data = cvt.convert(wav.buffer().to_vec());
+
+        Ok(Self {
+            samples: bytemuck::pod_collect_to_vec(&data).into(),
+            volume: 1.,
+        })
+    }
+
+    /// Creates a sound from interleaved stereo samples, at 48000Hz.
+    pub fn from_samples(samples: impl Into<Arc<[f32]>>) -> Self {
+        Self {
+            samples: samples.into(),
+            volume: 1.,
+        }
+    }
+
+    /// Sets the volume for this sound, from 0 to 1. Defaults to 1.
+    pub const fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// The volume for this sound.
+    #[must_use]
+    pub const fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Sets the volume for this sound, from 0 to 1.
+    pub const fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+    }
+
+    /// How long this sound is, in seconds.
+    #[must_use]
+    pub fn duration(&self) -> f32 {
+        self.samples.len() as f32 / f32::from(CHANNELS) / SAMPLE_RATE as f32
+    }
+
+    /// Plays this sound once.
+    pub fn play(&self) {
+        send(Command::Play(Voice::new(self, false)));
+    }
+}
+
+/// Plays a sound as music, looping it until it's stopped. Any playing music is replaced.
+pub fn play_music(sound: &Sound) {
+    send(Command::PlayMusic(Voice::new(sound, true)));
+}
+
+/// Stops the current music.
+pub fn stop_music() {
+    send(Command::StopMusic);
+}
+
+/// Pauses or resumes the current music.
+pub fn set_music_paused(paused: bool) {
+    send(Command::SetMusicPaused(paused));
+}
+
+/// Sets the volume for music, from 0 to 1. Defaults to 1.
+pub fn set_music_volume(volume: f32) {
+    send(Command::SetMusicVolume(volume));
+}
+
+/// Sets the volume for all audio, from 0 to 1. Defaults to 1.
+pub fn set_volume(volume: f32) {
+    send(Command::SetVolume(volume));
+}
+
+/// Stops all sounds, except for music.
+pub fn stop_sounds() {
+    send(Command::StopSounds);
+}
+
+fn send(command: Command) {
+    AUDIO.with_borrow_mut(|audio| {
+        if let Some(audio) = audio {
+            audio.held.push_back(command);
+            audio.flush();
+        }
+    });
+}
+
+impl Audio {
+    /// Sends held commands to the mixer, until its queue is full.
+    fn flush(&mut self) {
+        while let Some(command) = self.held.pop_front() {
+            match self.commands.try_send(command) {
+                Ok(()) => {}
+                Err(TrySendError::Full(command)) => {
+                    self.held.push_front(command);
+                    break;
+                }
+                // The mixer is gone, so there's nothing to play anyway
+                Err(TrySendError::Disconnected(_)) => self.held.clear(),
+            }
+        }
+    }
+}
+
+/// Frees the sounds the mixer is done with, and sends it any commands which didn't fit in its
+/// queue. Called by the game loop every frame, even while the game is paused, so that the mixer
+/// never has to free memory, which can take long enough to make audio crackle.
+pub(crate) fn update() {
+    AUDIO.with_borrow_mut(|audio| {
+        if let Some(audio) = audio {
+            for voice in audio.finished.try_iter() {
+                drop(voice);
+            }
+            audio.flush();
+        }
+    });
+}
+
+pub(crate) fn init(sdl: &sdl2::Sdl) {
+    // Bounded channels are allocated up front, so using them doesn't allocate
+    let (commands, command_receiver) = mpsc::sync_channel(COMMANDS);
+    let (finished_sender, finished) = mpsc::sync_channel(VOICES);
+    let spec = AudioSpecDesired {
+        freq: Some(SAMPLE_RATE),
+        channels: Some(CHANNELS),
+        samples: None,
+    };
+    let device = sdl.audio().and_then(|audio| {
+        audio.open_playback(None, &spec, |_| {
+            Mixer::new(command_receiver, finished_sender)
+        })
+    });
+
+    match device {
+        Ok(device) => {
+            device.resume();
+            AUDIO.set(Some(Audio {
+                _device: device,
+                commands,
+                held: VecDeque::new(),
+                finished,
+            }));
+        }
+        Err(e) => log::warn!("Failed to initialise audio: {e}"),
+    }
+}
+
+struct Voice {
+    samples: Arc<[f32]>,
+    position: usize,
+    volume: f32,
+    looping: bool,
+}
+
+impl Voice {
+    fn new(sound: &Sound, looping: bool) -> Self {
+        Self {
+            samples: sound.samples.clone(),
+            position: 0,
+            volume: sound.volume,
+            looping,
+        }
+    }
+
+    /// Adds this voice to the output. Returns whether it's still playing.
+    fn mix(&mut self, out: &mut [f32], volume: f32) -> bool {
+        let volume = volume * self.volume;
+        let mut out = out.iter_mut();
+
+        loop {
+            let remaining = &self.samples[self.position..];
+            let mut mixed = 0;
+            for (sample, out) in remaining.iter().zip(&mut out) {
+                *out += sample * volume;
+                mixed += 1;
+            }
+            self.position += mixed;
+
+            if self.position < self.samples.len() {
+                return true;
+            }
+            if !self.looping || self.samples.is_empty() {
+                return false;
+            }
+            self.position = 0;
+            if out.len() == 0 {
+                return true;
+            }
+        }
+    }
+}
+
+/// Runs on the audio thread, taking commands from the game and sending back voices which are
+/// done, so they're freed on the game's thread.
+///
+/// If the game is slow to take finished voices back, they're kept until there's room in the
+/// queue, so they're never freed here.
+struct Mixer {
+    sounds: Vec<Voice>,
+    music: Option<Voice>,
+    music_paused: bool,
+    music_volume: f32,
+    volume: f32,
+    commands: Receiver<Command>,
+    finished: SyncSender<Voice>,
+    unsent: Vec<Voice>,
+}
+
+impl Mixer {
+    fn new(commands: Receiver<Command>, finished: SyncSender<Voice>) -> Self {
+        Self {
+            sounds: Vec::with_capacity(VOICES),
+            unsent: Vec::with_capacity(VOICES),
+            music: None,
+            music_paused: false,
+            music_volume: 1.,
+            volume: 1.,
+            commands,
+            finished,
+        }
+    }
+
+    fn finish(&mut self, voices: impl IntoIterator<Item = Voice>) {
+        for voice in voices {
+            if let Err(TrySendError::Full(voice)) = self.finished.try_send(voice) {
+                self.unsent.push(voice);
+            }
+        }
+    }
+
+    /// Tries to send back the voices which didn't fit in the queue before.
+    fn retry_unsent(&mut self) {
+        while let Some(voice) = self.unsent.pop() {
+            if let Err(TrySendError::Full(voice)) = self.finished.try_send(voice) {
+                self.unsent.push(voice);
+                break;
+            }
+        }
+    }
+
+    fn run(&mut self, command: Command) {
+        match command {
+            Command::Play(voice) => self.sounds.push(voice),
+            Command::PlayMusic(voice) => {
+                let previous = self.music.replace(voice);
+                self.finish(previous);
+            }
+            Command::StopMusic => {
+                let previous = self.music.take();
+                self.finish(previous);
+            }
+            Command::SetMusicPaused(paused) => self.music_paused = paused,
+            Command::SetMusicVolume(volume) => self.music_volume = volume,
+            Command::SetVolume(volume) => self.volume = volume,
+            Command::StopSounds => {
+                // Popping keeps the capacity, so playing more sounds doesn't allocate
+                while let Some(voice) = self.sounds.pop() {
+                    self.finish(Some(voice));
+                }
+            }
+        }
+    }
+}
+
+impl AudioCallback for Mixer {
+    type Channel = f32;
+
+    fn callback(&mut self, out: &mut [f32]) {
+        self.retry_unsent();
+        while let Ok(command) = self.commands.try_recv() {
+            self.run(command);
+        }
+        out.fill(0.);
+
+        let mut i = 0;
+        while i < self.sounds.len() {
+            if self.sounds[i].mix(out, self.volume) {
+                i += 1;
+            } else {
+                let voice = self.sounds.swap_remove(i);
+                self.finish(Some(voice));
+            }
+        }
+        if let Some(music) = self.music.as_mut().filter(|_| !self.music_paused) {
+            if !music.mix(out, self.volume * self.music_volume) {
+                let music = self.music.take();
+                self.finish(music);
+            }
+        }
+
+        for sample in out {
+            *sample = sample.clamp(-1., 1.);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Copying and pasting text, through the system clipboard.
+//!
+//! ```no_run
+//! # let mut text = String::new();
+//! # let ctrl_v = true;
+//! if ctrl_v {
+//!     text.push_str(&baba::clipboard::get());
+//! }
+//! ```
+//!
+//! The clipboard is only available while the game is running.
+
+use std::ffi::{c_void, CStr, CString};
+
+use sdl2_sys::{SDL_GetClipboardText, SDL_HasClipboardText, SDL_SetClipboardText, SDL_free};
+
+use crate::SdlError;
+
+/// The text in the clipboard. This is empty if there's no text, or it couldn't be read.
+#[must_use]
+pub fn get() -> String {
+    let text = unsafe { SDL_GetClipboardText() };
+    if text.is_null() {
+        log::error!("Failed to get clipboard text: {}", SdlError::from_sdl());
+        return String::new();
+    }
+    let owned = unsafe { CStr::from_ptr(text) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { SDL_free(text.cast::<c_void>()) };
+    owned
+}
+
+/// Puts text in the clipboard, replacing what was there.
+///
+/// # Errors
+///
+/// If the text has a nul character in it, or the system refused to set the clipboard.
+pub fn set(text: &str) -> Result<(), SdlError> {
+    let text = CString::new(text).map_err(|e| SdlError(e.to_string()))?;
+    if unsafe { SDL_SetClipboardText(text.as_ptr()) } != 0 {
+        return Err(SdlError::from_sdl());
+    }
+    Ok(())
+}
+
+/// Is there text in the clipboard?
+#[must_use]
+pub fn has_text() -> bool {
+    unsafe { SDL_HasClipboardText() == sdl2_sys::SDL_bool::SDL_TRUE }
+}
@@ -0,0 +1,220 @@
+//! Combos for arcade games, where hits in quick succession build up a score multiplier.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let (font, enemy) = (Font::empty(), Vec2::ZERO);
+//! use baba::combo::{Combo, ComboEvent};
+//! use baba::floating_text::FloatingText;
+//!
+//! let mut combo = Combo::new(2.).with_tier(5, 2.).with_tier(20, 4.);
+//! let mut numbers = FloatingText::new(&font);
+//!
+//! // When an enemy is defeated
+//! combo.hit();
+//! combo.award_with_text(100, &mut numbers, enemy, Color::YELLOW);
+//!
+//! // Every frame
+//! combo.update();
+//! numbers.update();
+//! for event in baba::events::drain::<ComboEvent>() {
+//!     if let ComboEvent::Dropped { count } = event {
+//!         info!("Lost a {count} hit combo");
+//!     }
+//! }
+//! ```
+//!
+//! Changes are sent as [events][crate::events], so sounds and effects can react to them without
+//! the code that scores points knowing about it.
+
+use glam::Vec2;
+
+use crate::events;
+use crate::floating_text::FloatingText;
+use crate::gfx::Color;
+use crate::time;
+
+/// A change to a [`Combo`], sent as an [event][crate::events].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComboEvent {
+    /// The combo got another hit.
+    Increased {
+        /// Number of hits so far.
+        count: u32,
+    },
+    /// The combo reached a new multiplier tier.
+    TierChanged {
+        /// Index of the tier, in order of hit counts, or `None` when below every tier.
+        tier: Option<usize>,
+        /// The new multiplier.
+        multiplier: f32,
+    },
+    /// The combo ran out of time, or was broken.
+    Dropped {
+        /// Number of hits it had.
+        count: u32,
+    },
+}
+
+/// A count of hits which drops back to 0 if there's no hit for a while, with score multipliers
+/// for reaching enough hits.
+#[must_use]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combo {
+    window: f32,
+    tiers: Vec<(u32, f32)>,
+    count: u32,
+    best: u32,
+    time_left: f32,
+    score: u64,
+}
+
+impl Combo {
+    /// Creates a combo which drops when there's no hit for `window` seconds.
+    pub const fn new(window: f32) -> Self {
+        Self {
+            window,
+            tiers: Vec::new(),
+            count: 0,
+            best: 0,
+            time_left: 0.,
+            score: 0,
+        }
+    }
+
+    /// Adds a tier, so points are multiplied by `multiplier` once the combo has `count` hits.
+    /// Below the lowest tier, points aren't multiplied.
+    pub fn with_tier(mut self, count: u32, multiplier: f32) -> Self {
+        let index = self.tiers.partition_point(|&(c, _)| c <= count);
+        self.tiers.insert(index, (count, multiplier));
+        self
+    }
+
+    /// Adds a hit, and gives the player another `window` seconds to keep the combo going.
+    /// Returns the new number of hits.
+    pub fn hit(&mut self) -> u32 {
+        let tier = self.tier();
+        self.count = self.count.saturating_add(1);
+        self.best = self.best.max(self.count);
+        self.time_left = self.window;
+        events::emit(ComboEvent::Increased { count: self.count });
+        if self.tier() != tier {
+            events::emit(ComboEvent::TierChanged {
+                tier: self.tier(),
+                multiplier: self.multiplier(),
+            });
+        }
+        self.count
+    }
+
+    /// Adds points to the score, multiplied by the current multiplier. Returns the points that
+    /// were added.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn award(&mut self, points: u32) -> u64 {
+        let points = (points as f32 * self.multiplier()).round() as u64;
+        self.score = self.score.saturating_add(points);
+        points
+    }
+
+    /// Like [`award`][Self::award], also showing the added points as floating text.
+    pub fn award_with_text(
+        &mut self,
+        points: u32,
+        text: &mut FloatingText,
+        position: Vec2,
+        color: Color,
+    ) -> u64 {
+        let points = self.award(points);
+        text.spawn(position, format_args!("+{points}"), color);
+        points
+    }
+
+    /// Drops the combo right away, like when the player is hit.
+    pub fn break_combo(&mut self) {
+        if self.count == 0 {
+            return;
+        }
+        let tier = self.tier();
+        events::emit(ComboEvent::Dropped { count: self.count });
+        self.count = 0;
+        self.time_left = 0.;
+        if tier.is_some() {
+            events::emit(ComboEvent::TierChanged {
+                tier: None,
+                multiplier: 1.,
+            });
+        }
+    }
+
+    /// Resets everything, including the score and best combo, like for a new game.
+    pub const fn reset(&mut self) {
+        self.count = 0;
+        self.best = 0;
+        self.time_left = 0.;
+        self.score = 0;
+    }
+
+    /// Counts down the time left, dropping the combo once it runs out. This should be called
+    /// every frame.
+    pub fn update(&mut self) {
+        if self.count == 0 {
+            return;
+        }
+        self.time_left -= time::delta();
+        if self.time_left <= 0. {
+            self.break_combo();
+        }
+    }
+
+    /// Number of hits in the current combo.
+    #[must_use]
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Most hits reached since the last [`reset`][Self::reset].
+    #[must_use]
+    pub const fn best(&self) -> u32 {
+        self.best
+    }
+
+    /// Total points awarded since the last [`reset`][Self::reset].
+    #[must_use]
+    pub const fn score(&self) -> u64 {
+        self.score
+    }
+
+    /// Seconds left before the combo drops.
+    #[must_use]
+    pub const fn time_left(&self) -> f32 {
+        self.time_left.max(0.)
+    }
+
+    /// How much time is left before the combo drops, from 1 right after a hit to 0, like for a
+    /// timer bar.
+    #[must_use]
+    pub fn time_left_fraction(&self) -> f32 {
+        if self.window <= 0. {
+            return 0.;
+        }
+        (self.time_left / self.window).clamp(0., 1.)
+    }
+
+    /// Index of the highest tier reached, in the order of their hit counts, or `None` if the
+    /// combo hasn't reached any.
+    #[must_use]
+    pub fn tier(&self) -> Option<usize> {
+        self.tiers
+            .partition_point(|&(count, _)| count <= self.count)
+            .checked_sub(1)
+    }
+
+    /// The current multiplier for points.
+    #[must_use]
+    pub fn multiplier(&self) -> f32 {
+        self.tier().map_or(1., |tier| self.tiers[tier].1)
+    }
+}
@@ -0,0 +1,136 @@
+//! Data tables, loaded from CSV or JSON files.
+//!
+//! Balance data like enemy stats is easier to tweak in a spreadsheet than in code. Derive
+//! [`Deserialize`][serde::Deserialize] for a row type and load a table of them:
+//!
+//! ```no_run
+//! #[derive(serde::Deserialize)]
+//! struct EnemyDef {
+//!     name: String,
+//!     health: u32,
+//!     speed: f32,
+//! }
+//!
+//! for enemy in baba::data::table::<EnemyDef>("resources/enemies.csv").iter() {
+//!     println!("{} has {} health", enemy.name, enemy.health);
+//! }
+//! ```
+//!
+//! CSV files need a header row, naming the fields. JSON files must hold an array of rows.
+//!
+//! Tables are cached, so calling [`table`] every frame is cheap. In debug builds, tables are
+//! reloaded when their file changes, so you can tweak values while the game is running.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::panic::Location;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+/// Table load error.
+pub type LoadError = crate::LoadError<LoadErrorKind>;
+
+/// What went wrong while loading a table.
+#[derive(Debug, Error)]
+pub enum LoadErrorKind {
+    /// This table couldn't be opened.
+    #[error("couldn't read file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A CSV row couldn't be read.
+    #[error("couldn't read CSV: {0}")]
+    Csv(#[from] csv::Error),
+    /// The JSON data couldn't be read.
+    #[error("couldn't read JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The file extension isn't `.csv` or `.json`.
+    #[error("unknown table format, expected .csv or .json")]
+    UnknownFormat,
+}
+
+struct Cached {
+    rows: Rc<dyn Any>,
+    modified: Option<SystemTime>,
+}
+
+thread_local! {
+    static TABLES: RefCell<HashMap<(TypeId, PathBuf), Cached>> = RefCell::new(HashMap::new());
+}
+
+/// Loads a table of rows, or returns it from the cache.
+///
+/// If it fails to load, an error is logged and the table is empty.
+#[track_caller]
+pub fn table<T: DeserializeOwned + 'static>(path: impl AsRef<Path>) -> Rc<[T]> {
+    try_table(path)
+        .inspect_err(|e| log::error!("Failed to load {e}"))
+        .unwrap_or_else(|_| Rc::new([]))
+}
+
+/// Like [`table`], but returns an error instead of outputting a warning.
+#[track_caller]
+pub fn try_table<T: DeserializeOwned + 'static>(
+    path: impl AsRef<Path>,
+) -> Result<Rc<[T]>, LoadError> {
+    let path = path.as_ref();
+    let key = (TypeId::of::<T>(), path.to_owned());
+    let modified = cfg!(debug_assertions)
+        .then(|| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .flatten();
+
+    let cached = TABLES.with_borrow(|tables| {
+        let cached = tables.get(&key)?;
+        if cached.modified != modified {
+            log::info!("Reloading {}", path.display());
+            return None;
+        }
+        cached.rows.clone().downcast::<Rc<[T]>>().ok()
+    });
+    if let Some(rows) = cached {
+        return Ok((*rows).clone());
+    }
+
+    let rows = load(path)?;
+    TABLES.with_borrow_mut(|tables| {
+        let cached = Cached {
+            rows: Rc::new(rows.clone()),
+            modified,
+        };
+        tables.insert(key, cached);
+    });
+    Ok(rows)
+}
+
+/// Loads a table, without caching it.
+#[track_caller]
+pub fn load<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<Rc<[T]>, LoadError> {
+    let caller = Location::caller();
+    let path = path.as_ref();
+    crate::assets::record(path);
+    read(path).map_err(|kind| LoadError::new(kind, Some(path), caller))
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> Result<Rc<[T]>, LoadErrorKind> {
+    let ext = path.extension().and_then(|ext| ext.to_str());
+
+    if ext.is_some_and(|ext| ext.eq_ignore_ascii_case("csv")) {
+        let mut reader = csv::Reader::from_path(path)?;
+        let rows = reader.deserialize().collect::<Result<Vec<T>, _>>()?;
+        Ok(rows.into())
+    } else if ext.is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        let rows: Vec<T> = serde_json::from_reader(file)?;
+        Ok(rows.into())
+    } else {
+        Err(LoadErrorKind::UnknownFormat)
+    }
+}
+
+/// Drops all cached tables.
+pub fn clear_cache() {
+    TABLES.with_borrow_mut(HashMap::clear);
+}
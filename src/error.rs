@@ -1,13 +1,16 @@
 use std::ffi::CStr;
+use std::fmt;
+use std::panic::Location;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
-use crate::gfx;
+use crate::{audio, gfx};
 
 /// Internal SDL error. This usually means something in backend went wrong.
 #[derive(Debug, Error)]
 #[error("SDL error: {0}")]
-pub struct SdlError(String);
+pub struct SdlError(pub(crate) String);
 
 impl SdlError {
     pub(crate) fn from_sdl() -> Self {
@@ -28,4 +31,107 @@ pub enum Error {
     /// Failed to load a texture. It could be missing, corrupted, or have an unsupported format.
     #[error("Failed to load texture: {0}")]
     TextureLoad(#[from] gfx::TextureLoadError),
+    /// Failed to load a font. It could be missing, corrupted, or have an unsupported format.
+    #[error("Failed to load font: {0}")]
+    FontLoad(#[from] gfx::FontLoadError),
+    /// Failed to load a sound. It could be missing, corrupted, or have an unsupported format.
+    #[error("Failed to load sound: {0}")]
+    SoundLoad(#[from] audio::LoadError),
+    /// Failed to load a data table. It could be missing, or have rows which don't match.
+    #[cfg(feature = "data")]
+    #[error("Failed to load data table: {0}")]
+    DataLoad(#[from] crate::data::LoadError),
+}
+
+impl Error {
+    /// Formats this error along with all of its causes, separated by colons.
+    ///
+    /// ```no_run
+    /// # use baba::prelude::*;
+    /// if let Err(e) = Texture::try_load("resources/player.png", Origin::CENTER) {
+    ///     let e = baba::Error::from(e);
+    ///     eprintln!("{}", e.source_chain());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn source_chain(&self) -> impl fmt::Display + '_ {
+        SourceChain(self)
+    }
+}
+
+struct SourceChain<'a>(&'a dyn std::error::Error);
+
+impl fmt::Display for SourceChain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut message = self.0.to_string();
+        f.write_str(&message)?;
+
+        let mut source = self.0.source();
+        while let Some(e) = source {
+            // Most errors here already include their source in their message
+            let next = e.to_string();
+            if !message.contains(&next) {
+                write!(f, ": {next}")?;
+            }
+            message = next;
+            source = e.source();
+        }
+        Ok(())
+    }
+}
+
+/// An error from loading a resource, with the file it came from and the code which loaded it.
+///
+/// What went wrong is described by its [kind][Self::kind], such as a
+/// [`TextureLoadErrorKind`][crate::gfx::TextureLoadErrorKind].
+#[derive(Debug)]
+pub struct LoadError<K> {
+    kind: K,
+    path: Option<PathBuf>,
+    caller: &'static Location<'static>,
+}
+
+impl<K> LoadError<K> {
+    pub(crate) fn new(kind: K, path: Option<&Path>, caller: &'static Location<'static>) -> Self {
+        let path = path.map(Path::to_owned);
+        Self { kind, path, caller }
+    }
+
+    /// What went wrong.
+    pub const fn kind(&self) -> &K {
+        &self.kind
+    }
+
+    /// The file being loaded, if it was loaded from a file.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Where the load function was called.
+    #[must_use]
+    pub const fn caller(&self) -> &'static Location<'static> {
+        self.caller
+    }
+
+    /// Discards the context, returning what went wrong.
+    pub fn into_kind(self) -> K {
+        self.kind
+    }
+}
+
+impl<K: fmt::Display> fmt::Display for LoadError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}: ", path.display())?;
+        }
+        write!(f, "{} (loaded at {})", self.kind, self.caller)
+    }
+}
+
+impl<K: std::error::Error> std::error::Error for LoadError<K> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        // The kind is already part of the message
+        self.kind.source()
+    }
 }
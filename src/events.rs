@@ -0,0 +1,105 @@
+//! Typed message queues for game systems.
+//!
+//! Any `'static` type can be an event. Systems [`emit`] events, and other systems [`drain`] them
+//! later, without needing a reference to each other.
+//!
+//! ```no_run
+//! struct PlayerDied {
+//!     score: u32,
+//! }
+//!
+//! // somewhere in your game logic
+//! baba::events::emit(PlayerDied { score: 100 });
+//!
+//! // somewhere else, like in the UI
+//! for event in baba::events::drain::<PlayerDied>() {
+//!     println!("Game over! Score: {}", event.score);
+//! }
+//! ```
+//!
+//! Events which are never drained are dropped after two frames, so they can be read on the frame
+//! they were sent and on the frame after, regardless of the order systems run in.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+    static EVENTS: RefCell<HashMap<TypeId, Box<dyn Queue>>> = RefCell::new(HashMap::new());
+}
+
+trait Queue {
+    fn as_any(&mut self) -> &mut dyn Any;
+    fn swap(&mut self);
+    fn clear(&mut self);
+}
+
+struct Events<T> {
+    previous: Vec<T>,
+    current: Vec<T>,
+}
+
+impl<T: 'static> Queue for Events<T> {
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn swap(&mut self) {
+        self.previous.clear();
+        std::mem::swap(&mut self.previous, &mut self.current);
+    }
+
+    fn clear(&mut self) {
+        self.previous.clear();
+        self.current.clear();
+    }
+}
+
+fn with_queue<T: 'static, U>(f: impl FnOnce(&mut Events<T>) -> U) -> U {
+    EVENTS.with_borrow_mut(|events| {
+        let queue = events.entry(TypeId::of::<T>()).or_insert_with(|| {
+            Box::new(Events::<T> {
+                previous: Vec::new(),
+                current: Vec::new(),
+            })
+        });
+
+        f(queue.as_any().downcast_mut().unwrap())
+    })
+}
+
+/// Sends an event.
+pub fn emit<T: 'static>(event: T) {
+    with_queue(|queue| queue.current.push(event));
+}
+
+/// Takes all pending events of a type, oldest first.
+#[must_use]
+pub fn drain<T: 'static>() -> impl ExactSizeIterator<Item = T> {
+    with_queue(|queue: &mut Events<T>| {
+        let mut events = std::mem::take(&mut queue.previous);
+        events.append(&mut queue.current);
+        events.into_iter()
+    })
+}
+
+/// Counts how many events of a type are pending.
+#[must_use]
+pub fn count<T: 'static>() -> usize {
+    with_queue(|queue: &mut Events<T>| queue.previous.len() + queue.current.len())
+}
+
+/// Drops all pending events of a type.
+pub fn clear<T: 'static>() {
+    with_queue(Events::<T>::clear);
+}
+
+/// Drops all pending events.
+pub fn clear_all() {
+    EVENTS.with_borrow_mut(|events| events.values_mut().for_each(|queue| queue.clear()));
+}
+
+/// Ages every queue by a frame. This is done by the main loop after each frame.
+pub(crate) fn end_frame() {
+    EVENTS.with_borrow_mut(|events| events.values_mut().for_each(|queue| queue.swap()));
+}
@@ -0,0 +1,211 @@
+//! Game-state flags and counters, for quests and progression.
+//!
+//! Declare typed [`Key`]s as constants, and keep a [`Flags`] store in your game state:
+//!
+//! ```
+//! use baba::flags::{Flags, Key};
+//!
+//! const MET_WIZARD: Key<bool> = Key::new("met_wizard");
+//! const SLIMES_KILLED: Key<i32> = Key::new("slimes_killed");
+//!
+//! let mut flags = Flags::new();
+//! flags.set(MET_WIZARD, true);
+//! flags.increment(SLIMES_KILLED, 1);
+//!
+//! if flags.get(MET_WIZARD) && flags.get(SLIMES_KILLED) >= 10 {
+//!     // quest complete!
+//! }
+//! ```
+//!
+//! Every change emits a [`Changed`] [event][crate::events], so other systems can react to
+//! progress without polling. With the `serde` feature, [`Flags`] can be serialized along with the
+//! rest of a save file.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::events;
+
+/// A value stored in [`Flags`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum Value {
+    /// A flag.
+    Bool(bool),
+    /// A counter.
+    Int(i64),
+}
+
+/// Types which can be stored in [`Flags`].
+pub trait FlagValue: Copy + Default {
+    /// Converts this into a stored value.
+    fn into_value(self) -> Value;
+
+    /// Converts a stored value back, if it has the right type.
+    fn from_value(value: Value) -> Option<Self>;
+}
+
+impl FlagValue for bool {
+    fn into_value(self) -> Value {
+        Value::Bool(self)
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Bool(b) => Some(b),
+            Value::Int(_) => None,
+        }
+    }
+}
+
+macro_rules! int_flag_value {
+    ($($ty:ty),*) => {$(
+        impl FlagValue for $ty {
+            fn into_value(self) -> Value {
+                Value::Int(self.into())
+            }
+
+            fn from_value(value: Value) -> Option<Self> {
+                match value {
+                    Value::Int(n) => n.try_into().ok(),
+                    Value::Bool(_) => None,
+                }
+            }
+        }
+    )*};
+}
+
+int_flag_value!(i32, i64, u32);
+
+/// A typed name for a value in [`Flags`].
+pub struct Key<T> {
+    name: &'static str,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Key<T> {
+    /// Creates a key with a name. The name is what gets saved, so it shouldn't change.
+    #[must_use]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _type: PhantomData,
+        }
+    }
+
+    /// The name of this key.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for Key<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Key<T> {}
+
+impl<T> fmt::Debug for Key<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Key").field(&self.name).finish()
+    }
+}
+
+/// Event emitted whenever a value in [`Flags`] changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Changed {
+    /// Name of the key which changed.
+    pub name: Cow<'static, str>,
+    /// The previous value, if it was set.
+    pub old: Option<Value>,
+    /// The new value, or `None` if it was removed.
+    pub new: Option<Value>,
+}
+
+/// A store of named flags and counters.
+///
+/// Unset keys read as their type's default, `false` or `0`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Flags {
+    values: HashMap<Cow<'static, str>, Value>,
+}
+
+impl Flags {
+    /// Creates an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a value.
+    ///
+    /// If it was stored with a different type, this logs a warning and returns the default.
+    #[must_use]
+    pub fn get<T: FlagValue>(&self, key: Key<T>) -> T {
+        let Some(&value) = self.values.get(key.name) else {
+            return T::default();
+        };
+        T::from_value(value).unwrap_or_else(|| {
+            log::warn!("Flag {:?} has the wrong type: {value:?}", key.name);
+            T::default()
+        })
+    }
+
+    /// Has this key been set?
+    #[must_use]
+    pub fn contains<T>(&self, key: Key<T>) -> bool {
+        self.values.contains_key(key.name)
+    }
+
+    /// Sets a value, emitting [`Changed`] if it's different.
+    pub fn set<T: FlagValue>(&mut self, key: Key<T>, value: T) {
+        let new = value.into_value();
+        let old = self.values.insert(Cow::Borrowed(key.name), new);
+        if old != Some(new) {
+            events::emit(Changed {
+                name: Cow::Borrowed(key.name),
+                old,
+                new: Some(new),
+            });
+        }
+    }
+
+    /// Adds to a counter, returning the new count.
+    pub fn increment<T>(&mut self, key: Key<T>, by: T) -> T
+    where
+        T: FlagValue + std::ops::Add<Output = T>,
+    {
+        let count = self.get(key) + by;
+        self.set(key, count);
+        count
+    }
+
+    /// Unsets a value, emitting [`Changed`] if it was set.
+    pub fn remove<T>(&mut self, key: Key<T>) {
+        if let Some(old) = self.values.remove(key.name) {
+            events::emit(Changed {
+                name: Cow::Borrowed(key.name),
+                old: Some(old),
+                new: None,
+            });
+        }
+    }
+
+    /// Unsets everything, without emitting events. Use this when starting a new game.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// All set values, by name, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Value)> {
+        self.values.iter().map(|(name, &value)| (&**name, value))
+    }
+}
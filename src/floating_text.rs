@@ -0,0 +1,145 @@
+//! Text which pops up in the world, floats away and fades out, like damage numbers.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let (font, camera, enemy, damage) = (Font::empty(), Camera::default(), Vec2::ZERO, 12);
+//! use baba::floating_text::FloatingText;
+//!
+//! let mut numbers = FloatingText::new(&font);
+//!
+//! // When an enemy is hit
+//! numbers.spawn(enemy, damage, Color::RED);
+//!
+//! // Every frame
+//! numbers.update();
+//! gfx::with_camera(&camera, || gfx::draw(&numbers, Vec2::ZERO));
+//! ```
+//!
+//! Text is drawn at world positions, but always upright and at its normal size, even when the
+//! camera is zoomed or rotated. Finished text is reused, so spawning doesn't allocate once
+//! enough has been shown.
+
+use std::fmt::{Display, Write};
+
+use glam::{vec2, Vec2};
+
+use crate::gfx::{Canvas, Color, Drawable, Font, Text, Transform};
+use crate::time;
+
+#[derive(Debug, Clone)]
+struct Entry {
+    text: String,
+    position: Vec2,
+    color: Color,
+    age: f32,
+}
+
+/// A group of floating text, drawn with the same font.
+#[must_use]
+#[derive(Clone)]
+pub struct FloatingText {
+    font: Font,
+    entries: Vec<Entry>,
+    active: usize,
+    lifetime: f32,
+    rise: f32,
+}
+
+impl FloatingText {
+    /// Creates an empty group of text, drawn with a font.
+    pub fn new(font: &Font) -> Self {
+        Self {
+            font: font.clone(),
+            entries: Vec::new(),
+            active: 0,
+            lifetime: 1.,
+            rise: 24.,
+        }
+    }
+
+    /// Sets how long text is shown, in seconds. Defaults to 1.
+    pub const fn with_lifetime(mut self, lifetime: f32) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Sets how far text floats up before it's gone, in pixels. Defaults to 24.
+    pub const fn with_rise(mut self, rise: f32) -> Self {
+        self.rise = rise;
+        self
+    }
+
+    /// Shows some text, centered on a position.
+    pub fn spawn(&mut self, position: Vec2, text: impl Display, color: Color) {
+        if self.active == self.entries.len() {
+            self.entries.push(Entry {
+                text: String::new(),
+                position,
+                color,
+                age: 0.,
+            });
+        }
+        let entry = &mut self.entries[self.active];
+        entry.text.clear();
+        let _ = write!(entry.text, "{text}");
+        entry.position = position;
+        entry.color = color;
+        entry.age = 0.;
+        self.active += 1;
+    }
+
+    /// Number of texts being shown.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.active
+    }
+
+    /// Is no text being shown?
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.active == 0
+    }
+
+    /// Removes all text.
+    pub const fn clear(&mut self) {
+        self.active = 0;
+    }
+
+    /// Ages the text, removing it once it's done. This should be called every frame.
+    pub fn update(&mut self) {
+        let delta = time::delta();
+        let mut i = 0;
+        while i < self.active {
+            self.entries[i].age += delta;
+            if self.entries[i].age >= self.lifetime {
+                // Keep finished entries at the end, to reuse their strings
+                self.active -= 1;
+                self.entries.swap(i, self.active);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+impl Drawable for FloatingText {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        // Undo the camera, so text isn't zoomed or rotated along with the world
+        let view = canvas.set_view(Transform::IDENTITY);
+        let transform = view * transform;
+
+        for entry in &self.entries[..self.active] {
+            let t = (entry.age / self.lifetime).clamp(0., 1.);
+            // Rise quickly, then slow down, and only fade out near the end
+            let rise = (1. - (1. - t).powi(3)) * self.rise;
+            let alpha = 1. - t * t;
+
+            let size = self.font.measure(&entry.text);
+            let position = transform.transform_point(entry.position) - vec2(size.x / 2., rise);
+            let text = Text::new(&self.font, &entry.text).color(entry.color.gamma_multiply(alpha));
+            text.draw(canvas, Transform::from_translation(position.round()));
+        }
+
+        let _ = canvas.set_view(view);
+    }
+}
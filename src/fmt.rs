@@ -0,0 +1,178 @@
+//! String formatting helpers.
+//!
+//! HUD text is usually formatted every frame, and allocating a [`String`] each time adds up. The
+//! [`hud!`] macro formats into a [`SmallString`] instead, which keeps short text on the stack, and
+//! can be passed straight to [`gfx::draw_text`][crate::gfx::draw_text].
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let score = 0;
+//! let text = baba::fmt::hud!("Score: {score}");
+//! assert_eq!(&*text, "Score: 0");
+//! ```
+//!
+//! This doesn't use the [frame arena][crate::alloc], as text in it can't outlive the
+//! [`with_frame_arena`][crate::alloc::with_frame_arena] call, and text longer than
+//! [`INLINE_CAPACITY`] still allocates. To format long text without allocating, use
+//! [`FrameArena::alloc_fmt`][crate::alloc::FrameArena::alloc_fmt] instead:
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let (font, log) = (Font::empty(), "");
+//! baba::alloc::with_frame_arena(|arena| {
+//!     let text = arena.alloc_fmt(format_args!("Log:\n{log}"));
+//!     gfx::draw_text(&font, text, vec2(10., 10.));
+//! });
+//! ```
+
+use std::fmt::{self, Write};
+use std::ops::Deref;
+
+/// Formats text into a [`SmallString`], avoiding allocations for short strings.
+///
+/// This takes the same arguments as [`format!`]. Text longer than [`INLINE_CAPACITY`] is moved
+/// to the heap.
+#[doc(inline)]
+pub use crate::__hud as hud;
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hud {
+    ($($arg:tt)*) => {
+        $crate::fmt::SmallString::from_fmt(::std::format_args!($($arg)*))
+    };
+}
+
+/// How many bytes a [`SmallString`] can hold before allocating.
+pub const INLINE_CAPACITY: usize = 64;
+
+/// A string which is stored inline if it's short enough.
+///
+/// Strings up to [`INLINE_CAPACITY`] bytes don't allocate at all. Longer strings are moved to the
+/// heap.
+#[derive(Clone)]
+pub struct SmallString(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Inline { len: u8, buf: [u8; INLINE_CAPACITY] },
+    Heap(String),
+}
+
+impl SmallString {
+    /// Creates an empty string.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Repr::Inline {
+            len: 0,
+            buf: [0; INLINE_CAPACITY],
+        })
+    }
+
+    /// Formats arguments into a new string. This is what [`hud!`] uses.
+    #[must_use]
+    pub fn from_fmt(args: fmt::Arguments) -> Self {
+        let mut s = Self::new();
+        let _ = s.write_fmt(args);
+        s
+    }
+
+    /// Appends text to the end of this string.
+    pub fn push_str(&mut self, text: &str) {
+        match &mut self.0 {
+            Repr::Inline { len, buf } => {
+                let start = *len as usize;
+                let end = start + text.len();
+                if end <= INLINE_CAPACITY {
+                    buf[start..end].copy_from_slice(text.as_bytes());
+                    *len = end as u8;
+                } else {
+                    let mut heap = String::with_capacity(end);
+                    heap.push_str(self.as_str());
+                    heap.push_str(text);
+                    self.0 = Repr::Heap(heap);
+                }
+            }
+            Repr::Heap(heap) => heap.push_str(text),
+        }
+    }
+
+    /// Clears the string. The memory is kept, if it was allocated.
+    pub fn clear(&mut self) {
+        match &mut self.0 {
+            Repr::Inline { len, .. } => *len = 0,
+            Repr::Heap(heap) => heap.clear(),
+        }
+    }
+
+    /// Is this string stored inline?
+    #[must_use]
+    pub const fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    /// Extracts a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            // SAFETY: only valid strings are ever copied into the buffer
+            Repr::Inline { len, buf } => unsafe {
+                std::str::from_utf8_unchecked(&buf[..*len as usize])
+            },
+            Repr::Heap(heap) => heap,
+        }
+    }
+}
+
+impl Default for SmallString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for SmallString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SmallString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Write for SmallString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl fmt::Display for SmallString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for SmallString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq<str> for SmallString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl From<&str> for SmallString {
+    fn from(text: &str) -> Self {
+        let mut s = Self::new();
+        s.push_str(text);
+        s
+    }
+}
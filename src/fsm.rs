@@ -0,0 +1,165 @@
+//! Finite state machines.
+//!
+//! Implement [`State`] for your states, usually an enum, and keep a [`StateMachine`] in your
+//! entity. Call [`StateMachine::update`] every frame and it will take care of calling the hooks
+//! and moving between states.
+//!
+//! ```
+//! use baba::fsm::{State, StateMachine, Transition};
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Enemy {
+//!     Idle,
+//!     Chasing,
+//!     Stunned(u32),
+//! }
+//!
+//! struct World {
+//!     player_near: bool,
+//! }
+//!
+//! impl State for Enemy {
+//!     type Context = World;
+//!
+//!     fn update(&mut self, world: &mut World) -> Transition<Self> {
+//!         match self {
+//!             Self::Idle if world.player_near => Transition::To(Self::Chasing),
+//!             Self::Chasing if !world.player_near => Transition::To(Self::Idle),
+//!             Self::Stunned(0) => Transition::To(Self::Idle),
+//!             Self::Stunned(frames) => {
+//!                 *frames -= 1;
+//!                 Transition::None
+//!             }
+//!             _ => Transition::None,
+//!         }
+//!     }
+//!
+//!     fn can_transition(&self, _next: &Self, _world: &World) -> bool {
+//!         // can't escape being stunned!
+//!         !matches!(self, Self::Stunned(1..))
+//!     }
+//! }
+//!
+//! let mut world = World { player_near: true };
+//! let mut enemy = StateMachine::new(Enemy::Idle);
+//! enemy.update(&mut world);
+//! assert_eq!(enemy.state(), &Enemy::Chasing);
+//! ```
+
+/// A state which can be used in a [`StateMachine`].
+///
+/// All hooks have empty defaults, so you only need to implement the ones you use.
+pub trait State: Sized {
+    /// Data passed to every hook. This is usually your game state, or the entity which owns the
+    /// state machine.
+    type Context;
+
+    /// Called when the machine moves into this state.
+    fn enter(&mut self, ctx: &mut Self::Context) {
+        let _ = ctx;
+    }
+
+    /// Called when the machine moves out of this state.
+    fn exit(&mut self, ctx: &mut Self::Context) {
+        let _ = ctx;
+    }
+
+    /// Called on every [`StateMachine::update`]. May request a transition to another state.
+    fn update(&mut self, ctx: &mut Self::Context) -> Transition<Self> {
+        let _ = ctx;
+        Transition::None
+    }
+
+    /// Transition guard. Returning `false` prevents the machine from moving to `next`.
+    fn can_transition(&self, next: &Self, ctx: &Self::Context) -> bool {
+        let _ = (next, ctx);
+        true
+    }
+}
+
+/// A transition requested by [`State::update`].
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transition<S> {
+    /// Stay in the current state.
+    None,
+    /// Move to another state, if the guard allows it.
+    To(S),
+}
+
+/// Holds the current [`State`] and handles transitions between states.
+#[derive(Debug, Clone)]
+pub struct StateMachine<S> {
+    state: S,
+    entered: bool,
+    frames: u32,
+}
+
+impl<S: State> StateMachine<S> {
+    /// Creates a state machine in an initial state.
+    ///
+    /// The [`enter`][State::enter] hook is called on the first update.
+    pub const fn new(initial: S) -> Self {
+        Self {
+            state: initial,
+            entered: false,
+            frames: 0,
+        }
+    }
+
+    /// The current state.
+    pub const fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// How many updates happened since the last transition.
+    #[must_use]
+    pub const fn frames_in_state(&self) -> u32 {
+        self.frames
+    }
+
+    /// Updates the current state, and transitions if it asks to.
+    ///
+    /// Returns whether a transition happened.
+    pub fn update(&mut self, ctx: &mut S::Context) -> bool {
+        if !self.entered {
+            self.entered = true;
+            self.state.enter(ctx);
+        }
+
+        self.frames = self.frames.saturating_add(1);
+        match self.state.update(ctx) {
+            Transition::None => false,
+            Transition::To(next) => self.transition(next, ctx),
+        }
+    }
+
+    /// Moves to another state, if the current state's [guard][State::can_transition] allows it.
+    ///
+    /// Returns whether the transition happened.
+    pub fn transition(&mut self, next: S, ctx: &mut S::Context) -> bool {
+        if !self.state.can_transition(&next, ctx) {
+            return false;
+        }
+
+        self.force_transition(next, ctx);
+        true
+    }
+
+    /// Moves to another state, ignoring guards.
+    pub fn force_transition(&mut self, next: S, ctx: &mut S::Context) {
+        if self.entered {
+            self.state.exit(ctx);
+        }
+
+        self.state = next;
+        self.entered = true;
+        self.frames = 0;
+        self.state.enter(ctx);
+    }
+
+    /// Takes the current state out of the machine, without calling any hooks.
+    pub fn into_state(self) -> S {
+        self.state
+    }
+}
@@ -1,8 +1,67 @@
+use std::cell::Cell;
 use std::marker::PhantomData;
 use std::time::{Duration, Instant};
 
-use crate::gfx::{ScaleMode, Viewport};
-use crate::{gfx, input, Result};
+use glam::vec2;
+
+use crate::gfx::{Color, Fullscreen, ScaleMode, Viewport, WindowEvent, WindowPosition};
+use crate::input::gamepad;
+use crate::{
+    alloc, assets, audio, debug, events, gfx, input, jobs, scene, time, timer, tween, Result,
+};
+
+thread_local! {
+    static QUIT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Closes the game once the current frame is done, like from a "Quit" button in a menu.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let (quit_button, mouse) = (Vec2::ZERO, mouse_position());
+/// if is_mouse_pressed(MouseButton::Left) && mouse.distance(quit_button) < 16. {
+///     baba::quit();
+/// }
+/// ```
+///
+/// This doesn't call the [`on_quit_requested`][Game::on_quit_requested] hook, which is only for
+/// the window being closed.
+pub fn quit() {
+    QUIT.set(true);
+}
+
+/// How early to wake up before the next frame, to wait for the rest of the time in a loop. Sleeps
+/// can overshoot by a millisecond or more, which is enough to make the framerate wobble.
+const SPIN_MARGIN: Duration = Duration::from_millis(2);
+
+/// How often window events are checked while paused in the background.
+const BACKGROUND_POLL: Duration = Duration::from_millis(50);
+
+/// Waits until `deadline`, more precisely than [`std::thread::sleep`] alone.
+fn sleep_until(deadline: Instant) {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if let Some(sleep) = remaining.checked_sub(SPIN_MARGIN) {
+        std::thread::sleep(sleep);
+    }
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
+
+/// Draws the message shown while paused by [`Game::pause_on_disconnect`].
+fn draw_disconnected() {
+    let font = debug::font();
+    let center = gfx::screen_size() / 2.;
+    gfx::clear(Color::from_gray(16));
+    for (i, line) in ["Controller disconnected", "Press any button to continue"]
+        .into_iter()
+        .enumerate()
+    {
+        let size = font.measure(line);
+        let y = (i as f32 - 1.) * font.line_height() * 2.;
+        debug::text((center + vec2(-size.x / 2., y)).round(), line);
+    }
+}
 
 /// Tells the engine how to run a game.
 ///
@@ -13,9 +72,31 @@ pub struct Game<State, Update> {
     update: Update,
     settings: Settings,
     window: WindowSettings,
+    hooks: Hooks<State>,
     _state: PhantomData<State>,
 }
 
+type ResizeHook<State> = Box<dyn Fn(&mut State, u32, u32)>;
+type FocusHook<State> = Box<dyn Fn(&mut State, bool)>;
+type QuitHook<State> = Box<dyn Fn(&mut State) -> QuitResponse>;
+
+/// Callbacks for window events, set with the `on_*` methods on [`Game`].
+struct Hooks<State> {
+    resize: Option<ResizeHook<State>>,
+    focus_changed: Option<FocusHook<State>>,
+    quit_requested: Option<QuitHook<State>>,
+}
+
+impl<State> Default for Hooks<State> {
+    fn default() -> Self {
+        Self {
+            resize: None,
+            focus_changed: None,
+            quit_requested: None,
+        }
+    }
+}
+
 #[allow(clippy::missing_const_for_fn)] // Game can't be const initialised
 impl<State, Update: Fn(&mut State)> Game<State, Update> {
     pub(crate) fn new(name: String, update: Update) -> Self {
@@ -24,6 +105,7 @@ impl<State, Update: Fn(&mut State)> Game<State, Update> {
             update,
             settings: Settings::default(),
             window: WindowSettings::default(),
+            hooks: Hooks::default(),
             _state: PhantomData,
         }
     }
@@ -42,6 +124,27 @@ impl<State, Update: Fn(&mut State)> Game<State, Update> {
         self
     }
 
+    /// Sets what happens while the window is minimized or in the background. Defaults to
+    /// [`BackgroundBehavior::Run`].
+    ///
+    /// ```no_run
+    /// # #[derive(Default)]
+    /// # struct MyGame;
+    /// # impl MyGame { fn update(&mut self) {} }
+    /// use baba::BackgroundBehavior;
+    ///
+    /// # fn main() -> baba::Result {
+    /// baba::game("My game", MyGame::update)
+    ///     .background_behavior(BackgroundBehavior::Throttle(10))
+    ///     .run()
+    /// # }
+    /// ```
+    #[must_use]
+    pub const fn background_behavior(mut self, behavior: BackgroundBehavior) -> Self {
+        self.settings.background_behavior = behavior;
+        self
+    }
+
     /// Enable vsync, reducing tearing at the cost of some latency.
     #[must_use]
     pub fn vsync(mut self) -> Self {
@@ -49,6 +152,33 @@ impl<State, Update: Fn(&mut State)> Game<State, Update> {
         self
     }
 
+    /// Sets the brightness, like from the player's saved settings. Defaults to 0. See
+    /// [`gfx::set_brightness`].
+    #[must_use]
+    pub fn brightness(mut self, brightness: f32) -> Self {
+        self.settings.brightness = brightness;
+        self
+    }
+
+    /// Sets the display's gamma, like from the player's saved settings. Defaults to 1. See
+    /// [`gfx::set_gamma`].
+    #[must_use]
+    pub fn gamma(mut self, gamma: f32) -> Self {
+        self.settings.gamma = gamma;
+        self
+    }
+
+    /// Pauses the game when the [active controller][gamepad::active] is disconnected, showing a
+    /// message until a button or key is pressed. `update` isn't called while it's paused.
+    ///
+    /// An [`ActiveDisconnected`][gamepad::ActiveDisconnected] event is sent either way, for games
+    /// which would rather show their own pause menu.
+    #[must_use]
+    pub const fn pause_on_disconnect(mut self, pause: bool) -> Self {
+        self.settings.pause_on_disconnect = pause;
+        self
+    }
+
     /// Sets a viewport for the screen.
     #[must_use]
     pub fn viewport(mut self, viewport: Viewport) -> Self {
@@ -56,6 +186,14 @@ impl<State, Update: Fn(&mut State)> Game<State, Update> {
         self
     }
 
+    /// Sets the number of threads running [jobs][crate::jobs]. Defaults to one per CPU core,
+    /// minus one for the main thread.
+    #[must_use]
+    pub fn worker_threads(mut self, count: usize) -> Self {
+        self.settings.worker_threads = count;
+        self
+    }
+
     /// Sets the window title. This defaults to the name given to [`game`][crate::game()] or [`run`][crate::run].
     #[must_use]
     pub fn window_title(mut self, title: impl Into<String>) -> Self {
@@ -70,6 +208,80 @@ impl<State, Update: Fn(&mut State)> Game<State, Update> {
         self
     }
 
+    /// Sets where the window is placed. Defaults to [`WindowPosition::Default`].
+    #[must_use]
+    pub fn window_position(mut self, position: WindowPosition) -> Self {
+        self.window.position = position;
+        self
+    }
+
+    /// Starts the game in fullscreen. Defaults to [`Fullscreen::Off`].
+    #[must_use]
+    pub fn window_fullscreen(mut self, fullscreen: Fullscreen) -> Self {
+        self.window.fullscreen = fullscreen;
+        self
+    }
+
+    /// Calls a function when the window is resized, with its new size in window units. These are
+    /// the same as pixels, except on high DPI screens.
+    #[must_use]
+    pub fn on_resize(mut self, f: impl Fn(&mut State, u32, u32) + 'static) -> Self {
+        self.hooks.resize = Some(Box::new(f));
+        self
+    }
+
+    /// Calls a function when the window gains or loses focus, with `true` if it gained focus.
+    ///
+    /// ```no_run
+    /// # use baba::prelude::*;
+    /// # #[derive(Default)]
+    /// # struct MyGame { paused: bool }
+    /// # impl MyGame { fn update(&mut self) {} }
+    /// baba::game("My game", MyGame::update)
+    ///     .on_focus_changed(|game, focused| {
+    ///         if !focused {
+    ///             game.paused = true;
+    ///         }
+    ///     })
+    ///     .run()
+    /// # .unwrap();
+    /// ```
+    #[must_use]
+    pub fn on_focus_changed(mut self, f: impl Fn(&mut State, bool) + 'static) -> Self {
+        self.hooks.focus_changed = Some(Box::new(f));
+        self
+    }
+
+    /// Calls a function when the user asks to close the game, like with the window's close
+    /// button. It decides whether the game actually closes, so it's a good place to save
+    /// progress, or to ask about unsaved changes first.
+    ///
+    /// ```no_run
+    /// # use baba::prelude::*;
+    /// # #[derive(Default)]
+    /// # struct Editor { unsaved: bool, confirming: bool }
+    /// # impl Editor { fn update(&mut self) {} }
+    /// use baba::QuitResponse;
+    ///
+    /// baba::game("Level editor", Editor::update)
+    ///     .on_quit_requested(|editor| {
+    ///         if editor.unsaved {
+    ///             // Show a prompt, which calls `baba::quit()` if the user really wants to quit
+    ///             editor.confirming = true;
+    ///             QuitResponse::Cancel
+    ///         } else {
+    ///             QuitResponse::Quit
+    ///         }
+    ///     })
+    ///     .run()
+    /// # .unwrap();
+    /// ```
+    #[must_use]
+    pub fn on_quit_requested(mut self, f: impl Fn(&mut State) -> QuitResponse + 'static) -> Self {
+        self.hooks.quit_requested = Some(Box::new(f));
+        self
+    }
+
     /// Set the engine settings.
     ///
     /// This will override any other methods.
@@ -98,11 +310,29 @@ impl<State, Update: Fn(&mut State)> Game<State, Update> {
 
     /// Runs the game, using a initializer function.
     pub fn run_with(self, init: impl FnOnce() -> State) -> Result {
-        env_logger::Builder::from_env(env_logger::Env::new().default_filter_or("info"))
+        self.build_with(init)?.run();
+        Ok(())
+    }
+
+    /// Opens the window without starting the game loop, using [`Default`] for the state. See
+    /// [`Runner`].
+    pub fn build(self) -> Result<Runner<State, Update>>
+    where
+        State: Default,
+    {
+        self.build_with(State::default)
+    }
+
+    /// Opens the window without starting the game loop, using a initializer function. See
+    /// [`Runner`].
+    pub fn build_with(self, init: impl FnOnce() -> State) -> Result<Runner<State, Update>> {
+        // Tests may build several games, but the logger can only be set once
+        let _ = env_logger::Builder::from_env(env_logger::Env::new().default_filter_or("info"))
             .format_timestamp_millis()
-            .init();
+            .try_init();
 
         let (canvas, mode) = self.init_canvas()?;
+        jobs::init(self.settings.worker_threads);
 
         let frame_limit = match self.settings.framerate {
             Framerate::Multiplier(mul) => {
@@ -110,32 +340,79 @@ impl<State, Update: Fn(&mut State)> Game<State, Update> {
                 let base = if base > 0. { base } else { 60. };
                 Duration::from_secs_f32(1. / (mul * base))
             }
-            Framerate::Exact(fps) => Duration::from_secs_f32(1. / fps as f32),
+            Framerate::Exact(fps) | Framerate::Fixed(fps) => {
+                Duration::from_secs_f32(1. / fps as f32)
+            }
             Framerate::Unlimited => Duration::ZERO,
         };
-        let mut frame_start = Instant::now();
-        let mut state = init();
+        let state = init();
 
         canvas.show_window();
 
-        while canvas.process_events() {
-            (self.update)(&mut state);
-
-            input::clear();
-            gfx::display();
+        let now = Instant::now();
+        Ok(Runner {
+            game: self,
+            state,
+            canvas,
+            frame_limit,
+            next_frame: now,
+            last_update: now,
+            last_reload: now,
+            last_shown: now,
+            waiting_for_pad: false,
+            behind: Duration::ZERO,
+            window_events: Vec::new(),
+            oldest_input: None,
+        })
+    }
 
-            let now = Instant::now();
-            let dt = now - std::mem::replace(&mut frame_start, now);
-            if dt <= frame_limit {
-                std::thread::sleep(frame_limit - dt);
+    /// Calls hooks for window events. Returns `false` if the game should close.
+    fn handle_window_events(&self, state: &mut State, events: &mut Vec<WindowEvent>) -> bool {
+        let mut running = true;
+        for event in events.drain(..) {
+            match event {
+                WindowEvent::Resized(width, height) => {
+                    if let Some(f) = &self.hooks.resize {
+                        f(state, width, height);
+                    }
+                }
+                WindowEvent::FocusChanged(focused) => {
+                    if let Some(f) = &self.hooks.focus_changed {
+                        f(state, focused);
+                    }
+                }
+                WindowEvent::QuitRequested => {
+                    let response = self
+                        .hooks
+                        .quit_requested
+                        .as_ref()
+                        .map_or(QuitResponse::Quit, |f| f(state));
+                    if response == QuitResponse::Quit {
+                        running = false;
+                    }
+                }
             }
         }
+        running
+    }
 
-        Ok(())
+    /// Runs one update, and finishes the frame for per-update state.
+    fn tick(&self, state: &mut State) {
+        span!(INFO, "update");
+        timer::update();
+        tween::update();
+        (self.update)(state);
+        scene::update();
+
+        input::clear();
+        events::end_frame();
+        time::end_frame();
     }
 
     fn init_canvas(&self) -> Result<(gfx::Canvas, gfx::DisplayMode)> {
         let sdl = sdl2::init().unwrap();
+        // This also raises the timer resolution on Windows, which the frame limiter relies on
+        unsafe { sdl2_sys::SDL_InitSubSystem(sdl2_sys::SDL_INIT_TIMER) };
         sdl2::hint::set("SDL_APP_NAME", &self.name);
         // sdl2::hint::set("SDL_IME_SUPPORT_EXTENDED_TEXT", "1");
         sdl2::hint::set("SDL_VIDEO_DOUBLE_BUFFER", "1");
@@ -149,18 +426,33 @@ impl<State, Update: Fn(&mut State)> Game<State, Update> {
 
         let mut flags = 0;
         flags |= sdl2_sys::SDL_WindowFlags::SDL_WINDOW_HIDDEN as u32;
+        flags |= sdl2_sys::SDL_WindowFlags::SDL_WINDOW_ALLOW_HIGHDPI as u32;
         if self.window.resizable {
             flags |= sdl2_sys::SDL_WindowFlags::SDL_WINDOW_RESIZABLE as u32;
         }
 
         let mut canvas = gfx::Canvas::new(&sdl.video().unwrap(), flags)?;
+        input::gamepad::init(&sdl);
+        audio::init(&sdl);
         canvas.set_window_title(self.window.title.as_ref().unwrap_or(&self.name));
         canvas.set_window_size(self.window.size.0, self.window.size.1);
+        if self.window.position != WindowPosition::Default {
+            canvas.set_window_position(self.window.position);
+        }
+        if self.window.fullscreen != Fullscreen::Off {
+            canvas.set_fullscreen(self.window.fullscreen);
+        }
 
         if let Some(viewport) = &self.settings.viewport {
             canvas.set_viewport(viewport);
         }
 
+        canvas.set_brightness(self.settings.brightness);
+        #[allow(clippy::float_cmp)]
+        if self.settings.gamma != 1. {
+            canvas.set_gamma(self.settings.gamma);
+        }
+
         if self.settings.vsync && !canvas.set_vsync(true) {
             log::warn!("Failed to set vsync!")
         }
@@ -180,6 +472,196 @@ impl<State, Update: Fn(&mut State)> Game<State, Update> {
     }
 }
 
+/// A game which is ready to run, returned by [`Game::build`].
+///
+/// [`run`][Self::run] starts the usual game loop. Tests can instead call [`step`][Self::step] to
+/// advance one frame at a time, and check the state in between:
+///
+/// ```no_run
+/// # fn main() -> baba::Result {
+/// #[derive(Default)]
+/// struct Counter(u32);
+///
+/// let mut game = baba::game("Test", |c: &mut Counter| c.0 += 1).build()?;
+/// for _ in 0..10 {
+///     game.step();
+/// }
+/// assert_eq!(game.state().0, 10);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Runner<State, Update> {
+    game: Game<State, Update>,
+    state: State,
+    canvas: gfx::Canvas,
+    frame_limit: Duration,
+    next_frame: Instant,
+    last_update: Instant,
+    last_reload: Instant,
+    last_shown: Instant,
+    waiting_for_pad: bool,
+    behind: Duration,
+    window_events: Vec<WindowEvent>,
+    /// When the oldest input which no update has seen yet was received, in SDL ticks.
+    oldest_input: Option<u32>,
+}
+
+impl<State, Update: Fn(&mut State)> Runner<State, Update> {
+    /// The game's state.
+    pub const fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// The game's state, for changing it between steps.
+    pub const fn state_mut(&mut self) -> &mut State {
+        &mut self.state
+    }
+
+    /// Runs the game loop until the game closes.
+    pub fn run(mut self) {
+        while self.frame() {}
+    }
+
+    /// Runs exactly one frame: handles events, calls `update` once, and presents. Returns `false`
+    /// if the game was asked to close.
+    ///
+    /// Unlike [`run`][Self::run], this doesn't wait between frames, and time always moves forward
+    /// by the framerate's frame time, or 1/60 of a second if it's unlimited, so steps are the same
+    /// on every run.
+    pub fn step(&mut self) -> bool {
+        if !self.handle_events() {
+            return false;
+        }
+        let frame_start = Instant::now();
+        let delta = match self.frame_limit {
+            Duration::ZERO => Duration::from_secs(1) / 60,
+            limit => limit,
+        };
+        time::advance(delta);
+        self.record_input_latency();
+        self.game.tick(&mut self.state);
+        self.present(frame_start);
+        true
+    }
+
+    /// Runs one iteration of the game loop, sleeping until the next one is due. Returns `false` if
+    /// the game should close.
+    fn frame(&mut self) -> bool {
+        // Sleeping happens at the end of the frame, so this is right before updating, and the
+        // update sees the newest input
+        if !self.handle_events() {
+            return false;
+        }
+
+        span!(INFO, "frame");
+        let now = Instant::now();
+        let delta = now - std::mem::replace(&mut self.last_update, now);
+        let frame_limit = self.frame_limit;
+
+        let background = !self.canvas.is_focused() || self.canvas.is_minimized();
+        let limit = match self.game.settings.background_behavior {
+            BackgroundBehavior::Pause if background => {
+                self.behind = Duration::ZERO;
+                sleep_until(now + BACKGROUND_POLL);
+                return true;
+            }
+            BackgroundBehavior::Throttle(fps) if background => {
+                frame_limit.max(Duration::from_secs_f32(1. / fps.max(1) as f32))
+            }
+            _ => frame_limit,
+        };
+
+        if self.game.settings.pause_on_disconnect && gamepad::active_lost() {
+            self.waiting_for_pad = true;
+        } else if self.waiting_for_pad
+            && (gamepad::active().is_some() || input::get_pressed_keys().len() > 0)
+        {
+            // Don't let the button that resumed the game do anything else
+            self.waiting_for_pad = false;
+            input::clear();
+        }
+
+        if self.waiting_for_pad {
+            self.behind = Duration::ZERO;
+            draw_disconnected();
+            input::clear();
+        } else if matches!(self.game.settings.framerate, Framerate::Fixed(_)) {
+            // Catch up on every tick due, but don't try to make up for long stalls
+            self.behind = (self.behind + delta).min(time::MAX_DELTA);
+            if self.behind < frame_limit {
+                sleep_until(now + frame_limit.saturating_sub(self.behind));
+                return true;
+            }
+            self.record_input_latency();
+            while self.behind >= frame_limit {
+                self.behind -= frame_limit;
+                time::advance(frame_limit);
+                self.game.tick(&mut self.state);
+            }
+        } else {
+            time::advance(delta);
+            self.record_input_latency();
+            self.game.tick(&mut self.state);
+        }
+
+        self.present(now);
+
+        if cfg!(debug_assertions) && now - self.last_reload >= Duration::from_secs(1) {
+            self.last_reload = now;
+            span!(INFO, "reload_assets");
+            assets::reload_changed();
+        }
+
+        // Drain events again, so the window stays responsive and closes promptly while waiting
+        // for the next frame
+        if !self.handle_events() {
+            return false;
+        }
+
+        // Keep frames evenly spaced, without trying to make up for slow ones
+        self.next_frame = (self.next_frame + limit).max(Instant::now());
+        sleep_until(self.next_frame);
+        true
+    }
+
+    /// Handles window events. Returns `false` if the game should close.
+    fn handle_events(&mut self) -> bool {
+        // This happens even while the game is paused, so finished sounds don't pile up
+        audio::update();
+        if let Some(received) = self.canvas.process_events(&mut self.window_events) {
+            self.oldest_input = Some(self.oldest_input.map_or(received, |t| t.min(received)));
+        }
+        self.game
+            .handle_window_events(&mut self.state, &mut self.window_events)
+            && !QUIT.take()
+    }
+
+    /// Measures how long the input about to be handled by an update has waited.
+    fn record_input_latency(&mut self) {
+        if let Some(received) = self.oldest_input.take() {
+            let now = unsafe { sdl2_sys::SDL_GetTicks() };
+            time::record_input_latency(Duration::from_millis(u64::from(
+                now.wrapping_sub(received),
+            )));
+        }
+    }
+
+    /// Presents the frame, and finishes per-frame bookkeeping.
+    fn present(&mut self, frame_start: Instant) {
+        let present_start = Instant::now();
+        gfx::display();
+        let shown = Instant::now();
+        time::record_frame(
+            shown - std::mem::replace(&mut self.last_shown, shown),
+            present_start - frame_start,
+            shown - present_start,
+        );
+        alloc::reset_frame_arena();
+        #[cfg(feature = "puffin")]
+        puffin::GlobalProfiler::lock().new_frame();
+    }
+}
+
 /// Global engine settings.
 pub struct Settings {
     /// Texture scaling mode, may be overriden with [`TextureOptions`][crate::gfx::TextureOptions].
@@ -190,9 +672,24 @@ pub struct Settings {
     /// Enable vertical sync (default off). Reduces tearing at the cost of some latency. You likely
     /// also want to set [framerate][Settings::framerate] if you use this.
     pub vsync: bool,
+    /// Brightness, from -1 to 1, for players whose displays are too dark or too bright. It's
+    /// drawn into every frame along with the [color grade][crate::gfx::Grade]. Defaults to 0.
+    pub brightness: f32,
+    /// Gamma of the display, for players whose displays are too dark or too bright. Defaults to
+    /// 1, which leaves colors as they are.
+    pub gamma: f32,
+    /// Pause when the active controller is disconnected. Defaults to `false`. See
+    /// [`Game::pause_on_disconnect`].
+    pub pause_on_disconnect: bool,
+    /// What happens while the window is minimized or in the background. Defaults to
+    /// [`BackgroundBehavior::Run`].
+    pub background_behavior: BackgroundBehavior,
     /// Viewport. If this is set, it will map coordinates to fit it's size, instead of following
     /// window coordinates.
     pub viewport: Option<Viewport>,
+    /// Number of threads running [jobs][crate::jobs]. Defaults to 0, which means one per CPU
+    /// core, minus one for the main thread.
+    pub worker_threads: usize,
 }
 
 impl Default for Settings {
@@ -201,22 +698,59 @@ impl Default for Settings {
             scale_mode: ScaleMode::Nearest,
             framerate: Framerate::Multiplier(1.),
             vsync: false,
+            brightness: 0.,
+            gamma: 1.,
+            pause_on_disconnect: false,
+            background_behavior: BackgroundBehavior::Run,
             // XXX: could have default?
             viewport: None,
+            worker_threads: 0,
         }
     }
 }
 
+/// What to do when the user asks to close the game, returned from the
+/// [`on_quit_requested`][Game::on_quit_requested] hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuitResponse {
+    /// Close the game.
+    Quit,
+    /// Keep running. To close the game later, like after a confirmation prompt, call
+    /// [`quit`][crate::quit()].
+    Cancel,
+}
+
 /// Framerate limit.
 pub enum Framerate {
     /// Sets framerate to a multiple of the current display's refresh rate.
     Multiplier(f32),
     /// Sets framerate to an exact value.
     Exact(u32),
+    /// Runs updates exactly this many times per second, regardless of how fast frames are
+    /// displayed. If the game falls behind, several updates run before the next frame is shown.
+    ///
+    /// [`time::delta`][crate::time::delta] is always `1 / rate`. Use this when logic needs a
+    /// stable tick, such as for physics.
+    Fixed(u32),
     /// No limits. Use this when you are going to implement your own limiting.
     Unlimited,
 }
 
+/// What the game does while its window is minimized or doesn't have focus, like when the player
+/// alt-tabs away.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundBehavior {
+    /// Keep running as usual.
+    #[default]
+    Run,
+    /// Keep running, but show at most this many frames per second, so the game doesn't keep a
+    /// CPU core busy.
+    Throttle(u32),
+    /// Stop calling `update` and drawing until the window is back. Window events are still
+    /// handled, so the game can be closed.
+    Pause,
+}
+
 /// Window settings.
 pub struct WindowSettings {
     /// Window title. Defaults to the name given to [`game`][crate::game()] or [`run`][crate::run].
@@ -225,6 +759,10 @@ pub struct WindowSettings {
     pub size: (u32, u32),
     /// Allow window to be resized. Defaults to true.
     pub resizable: bool,
+    /// Where the window is placed. Defaults to [`WindowPosition::Default`].
+    pub position: WindowPosition,
+    /// Fullscreen mode. Defaults to [`Fullscreen::Off`].
+    pub fullscreen: Fullscreen,
 }
 
 impl Default for WindowSettings {
@@ -233,6 +771,8 @@ impl Default for WindowSettings {
             title: None,
             size: (800, 600),
             resizable: true,
+            position: WindowPosition::Default,
+            fullscreen: Fullscreen::Off,
         }
     }
 }
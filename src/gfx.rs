@@ -1,17 +1,51 @@
 //! Rendering and resource loading
+//!
+//! Colors are in sRGB, like in image files. Blending, tints and the colors between vertices are
+//! all worked out on those values directly, rather than in linear space, which is what most 2D
+//! games expect. Overlapping translucent colors may look a little darker than they would in
+//! linear space.
 
 use std::cell::RefCell;
 
 pub use ecolor::Color32 as Color;
 use glam::Vec2;
 
+mod batch;
+mod camera;
 mod canvas;
+mod cursor;
+mod display;
+mod grade;
+mod nine_slice;
+mod post;
+mod shadow;
+mod shape;
+mod target;
+mod terrain;
+mod text;
 mod texture;
 mod transform;
-pub use canvas::{Canvas, CanvasError, DisplayMode, Viewport, ViewportScaling};
+pub use batch::SpriteBatch;
+pub use camera::{set_camera, with_camera, Camera, CameraController};
+pub(crate) use canvas::WindowEvent;
+pub use canvas::{
+    Canvas, CanvasError, DisplayMode, Fullscreen, Viewport, ViewportScaling, WindowPosition,
+};
+pub use cursor::{hide_cursor, is_cursor_visible, set_cursor, show_cursor, Cursor, SystemCursor};
+pub use display::{displays, Display, VideoMode};
+pub use grade::{color_grade, set_color_grade, Grade};
+pub use nine_slice::NineSlice;
+pub use post::{set_post_chain, with_post_chain, PostChain, PostEffect};
+pub use shadow::{draw_with_shadow, Shadow};
+pub use shape::Shape;
+pub use target::{with_target, RenderTarget};
+pub use terrain::PixelTerrain;
+pub use text::{
+    draw_text, Font, LoadError as FontLoadError, LoadErrorKind as FontLoadErrorKind, Text,
+};
 pub use texture::{
-    LoadError as TextureLoadError, Options as TextureOptions, Origin, ScaleMode, Texture,
-    TextureSlice,
+    BlendMode, LoadError as TextureLoadError, LoadErrorKind as TextureLoadErrorKind,
+    Options as TextureOptions, Origin, ScaleMode, Texture, TextureSlice,
 };
 pub use transform::Transform;
 
@@ -28,6 +62,7 @@ pub fn with_canvas<T>(f: impl FnOnce(&mut Canvas) -> T) -> T {
 ///
 /// This is a rendering primitive.
 #[repr(C)]
+#[derive(Debug, Clone, Copy)]
 pub struct Vertex {
     /// 2D position of the vertex on screen.
     pub coord: Vec2,
@@ -38,6 +73,12 @@ pub struct Vertex {
 }
 
 impl Vertex {
+    /// Create a vertex from 2d coordinates, a color and uv coordinates.
+    #[must_use]
+    pub const fn new(coord: Vec2, color: Color, uv: Vec2) -> Self {
+        Self { coord, color, uv }
+    }
+
     /// Create a vertex from 2d coordinates and uv coordinates.
     ///
     /// The color will be white.
@@ -46,6 +87,13 @@ impl Vertex {
         let color = Color::WHITE;
         Self { coord, color, uv }
     }
+
+    /// Create a vertex from 2d coordinates and a color, for drawing without a texture.
+    #[must_use]
+    pub const fn from_xy_color(coord: Vec2, color: Color) -> Self {
+        let uv = Vec2::ZERO;
+        Self { coord, color, uv }
+    }
 }
 
 /// Clears the screen.
@@ -53,6 +101,237 @@ pub fn clear(color: Color) {
     with_canvas(|canvas| canvas.clear(color))
 }
 
+/// The size of the screen, in the coordinates things are drawn in.
+///
+/// If a [`Viewport`] is set, this is its logical size. Otherwise, it's the size of the window,
+/// or of the [render target][RenderTarget] being drawn to.
+#[must_use]
+pub fn screen_size() -> Vec2 {
+    let (w, h) = with_canvas(|canvas| canvas.size());
+    Vec2::new(w as f32, h as f32)
+}
+
+/// How many pixels are drawn for each unit of the window's size. This is usually 2 on high DPI
+/// displays, like Apple's retina displays, and 1 everywhere else.
+///
+/// Window sizes, and coordinates without a [`Viewport`], are in window units, so everything is
+/// the same size no matter the display. Things are still drawn at the display's full resolution.
+#[must_use]
+pub fn dpi_scale() -> f32 {
+    with_canvas(|canvas| canvas.dpi_scale())
+}
+
+/// Converts a point in window pixels to the coordinates things are drawn in.
+///
+/// With a [`Viewport`], the screen is scaled up and may have black bars around it, so window
+/// pixels don't match what's drawn. [`mouse_position`][crate::input::mouse_position] is already
+/// converted, but positions from elsewhere, like the window size, may need this.
+#[must_use]
+pub fn window_to_logical(point: Vec2) -> Vec2 {
+    with_canvas(|canvas| canvas.window_to_logical(point))
+}
+
+/// Converts a point in the coordinates things are drawn in to window pixels.
+#[must_use]
+pub fn logical_to_window(point: Vec2) -> Vec2 {
+    with_canvas(|canvas| canvas.logical_to_window(point))
+}
+
+/// Sets the fullscreen mode for the window.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// // Alt+Enter toggles fullscreen
+/// if is_key_down(KeyCode::LAlt) && is_key_pressed(KeyCode::Return) {
+///     gfx::toggle_fullscreen();
+/// }
+/// ```
+pub fn set_fullscreen(mode: Fullscreen) {
+    with_canvas(|canvas| canvas.set_fullscreen(mode));
+}
+
+/// The current fullscreen mode of the window.
+#[must_use]
+pub fn fullscreen() -> Fullscreen {
+    with_canvas(|canvas| canvas.fullscreen())
+}
+
+/// Sets the player's brightness setting, from -1 to 1. See [`Canvas::set_brightness`].
+pub fn set_brightness(brightness: f32) {
+    with_canvas(|canvas| canvas.set_brightness(brightness));
+}
+
+/// The brightness set with [`set_brightness`].
+#[must_use]
+pub fn brightness() -> f32 {
+    with_canvas(|canvas| canvas.brightness())
+}
+
+/// Sets the display's gamma while the window is focused. 1 leaves colors as they are, and higher
+/// values are brighter. See [`Canvas::set_gamma`].
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let mut gamma = 1f32;
+/// // A brightness slider in the options menu
+/// if is_key_pressed(KeyCode::Right) {
+///     gamma = (gamma + 0.1).min(2.);
+///     gfx::set_gamma(gamma);
+/// }
+/// ```
+pub fn set_gamma(gamma: f32) {
+    with_canvas(|canvas| canvas.set_gamma(gamma));
+}
+
+/// The gamma set with [`set_gamma`].
+#[must_use]
+pub fn gamma() -> f32 {
+    with_canvas(|canvas| canvas.gamma())
+}
+
+/// Moves the window.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// use baba::gfx::WindowPosition;
+///
+/// // Move the window to the second display
+/// gfx::set_window_position(WindowPosition::CenteredOn(1));
+/// ```
+pub fn set_window_position(position: WindowPosition) {
+    with_canvas(|canvas| canvas.set_window_position(position));
+}
+
+/// Position of the window's top left corner, across all displays.
+#[must_use]
+pub fn window_position() -> (i32, i32) {
+    with_canvas(|canvas| canvas.get_window_position())
+}
+
+/// Index of the [display][displays] the window is on.
+#[must_use]
+pub fn window_display() -> u32 {
+    with_canvas(|canvas| canvas.display_index())
+}
+
+/// Minimizes the window.
+pub fn minimize() {
+    with_canvas(Canvas::minimize);
+}
+
+/// Maximizes the window, making it as large as the display allows without going fullscreen.
+pub fn maximize() {
+    with_canvas(Canvas::maximize);
+}
+
+/// Restores the window to its normal size, if it's minimized or maximized.
+pub fn restore() {
+    with_canvas(Canvas::restore);
+}
+
+/// Is the window minimized?
+#[must_use]
+pub fn is_minimized() -> bool {
+    with_canvas(|canvas| canvas.is_minimized())
+}
+
+/// Is the window maximized?
+#[must_use]
+pub fn is_maximized() -> bool {
+    with_canvas(|canvas| canvas.is_maximized())
+}
+
+/// Does the window have keyboard focus? This is `false` while the player is using another
+/// window, which is a good time to pause.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let mut paused = false;
+/// if !gfx::is_focused() || gfx::is_minimized() {
+///     paused = true;
+/// }
+/// ```
+///
+/// To react as soon as focus changes, use [`Game::on_focus_changed`][crate::Game::on_focus_changed].
+#[must_use]
+pub fn is_focused() -> bool {
+    with_canvas(|canvas| canvas.is_focused())
+}
+
+/// Switches between a window and [desktop fullscreen][Fullscreen::Desktop].
+pub fn toggle_fullscreen() {
+    with_canvas(|canvas| {
+        let mode = match canvas.fullscreen() {
+            Fullscreen::Off => Fullscreen::Desktop,
+            _ => Fullscreen::Off,
+        };
+        canvas.set_fullscreen(mode);
+    });
+}
+
+/// Overrides the blend mode for everything drawn after this. `None` goes back to the default,
+/// which uses each texture's own [blend mode][TextureOptions::blend].
+pub fn set_blend_mode(mode: Option<BlendMode>) {
+    with_canvas(|canvas| canvas.set_blend_mode(mode));
+}
+
+/// Draws with a blend mode for the duration of `f`.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let glow = Texture::empty();
+/// gfx::with_blend_mode(BlendMode::Additive, || {
+///     gfx::draw(&glow, vec2(40., 10.));
+///     gfx::draw_circle(16., Color::from_rgb(40, 20, 0), vec2(40., 10.));
+/// });
+/// ```
+pub fn with_blend_mode<T>(mode: BlendMode, f: impl FnOnce() -> T) -> T {
+    let previous = with_canvas(|canvas| canvas.blend_mode());
+    set_blend_mode(Some(mode));
+    let result = f();
+    set_blend_mode(previous);
+    result
+}
+
+/// Multiplies the color of everything drawn after this. [`Color::WHITE`] goes back to normal.
+pub fn set_tint(tint: Color) {
+    with_canvas(|canvas| canvas.set_tint(tint));
+}
+
+/// Draws with everything multiplied by a color for the duration of `f`, on top of any tint
+/// already set.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let (enemy, position) = (Texture::empty(), Vec2::ZERO);
+/// // Flash the whole enemy red when it's hit, even if it's made of many sprites
+/// gfx::with_tint(Color::RED, || {
+///     gfx::draw(&enemy, position);
+/// });
+/// ```
+pub fn with_tint<T>(tint: Color, f: impl FnOnce() -> T) -> T {
+    let previous = with_canvas(|canvas| canvas.tint());
+    set_tint(multiply_colors(previous, tint));
+    let result = f();
+    set_tint(previous);
+    result
+}
+
+/// Multiplies two colors together, channel by channel.
+pub(crate) fn multiply_colors(a: Color, b: Color) -> Color {
+    let (a, b) = (a.to_array(), b.to_array());
+    let [r, g, b, a] =
+        std::array::from_fn(|i| ((u16::from(a[i]) * u16::from(b[i]) + 127) / 255) as u8);
+    Color::from_rgba_premultiplied(r, g, b, a)
+}
+
+/// Stores a color's unmultiplied values, for blend modes which expect them, like
+/// [`BlendMode::Alpha`].
+pub(crate) fn unmultiply_color(color: Color) -> Color {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    Color::from_rgba_premultiplied(r, g, b, a)
+}
+
 /// Display the current frame.
 ///
 /// This is usually already called for you.
@@ -81,6 +360,60 @@ pub fn draw<T: Drawable>(object: &T, transform: impl Into<Transform>) {
     with_canvas(|canvas| object.draw(canvas, transform.into()))
 }
 
+/// Draws a filled rectangle, with its top-left corner at the origin.
+///
+/// This is a shorthand for drawing a [`Shape`].
+pub fn draw_rect(size: Vec2, color: Color, transform: impl Into<Transform>) {
+    draw(&Shape::rect(size).color(color), transform);
+}
+
+/// Draws the border of a rectangle, with its top-left corner at the origin.
+pub fn draw_rect_outline(
+    size: Vec2,
+    thickness: f32,
+    color: Color,
+    transform: impl Into<Transform>,
+) {
+    draw(
+        &Shape::rect(size).color(color).outline(thickness),
+        transform,
+    );
+}
+
+/// Draws a filled circle, centered on the origin.
+pub fn draw_circle(radius: f32, color: Color, transform: impl Into<Transform>) {
+    draw(&Shape::circle(radius).color(color), transform);
+}
+
+/// Draws the border of a circle, centered on the origin.
+pub fn draw_circle_outline(
+    radius: f32,
+    thickness: f32,
+    color: Color,
+    transform: impl Into<Transform>,
+) {
+    draw(
+        &Shape::circle(radius).color(color).outline(thickness),
+        transform,
+    );
+}
+
+/// Draws a line between two points.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// gfx::draw_line(vec2(0., 0.), vec2(100., 50.), 2., Color::WHITE, ());
+/// ```
+pub fn draw_line(
+    from: Vec2,
+    to: Vec2,
+    thickness: f32,
+    color: Color,
+    transform: impl Into<Transform>,
+) {
+    draw(&Shape::line(from, to, thickness).color(color), transform);
+}
+
 /// Objects which can be drawn by [`draw`].
 #[diagnostic::on_unimplemented(
     message = "`{Self}` is not a `Drawable` type",
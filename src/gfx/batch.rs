@@ -0,0 +1,126 @@
+use glam::{vec2, Affine2, Vec2};
+
+use crate::alloc::with_frame_arena;
+use crate::math::Rect;
+
+use super::{Canvas, Color, Drawable, Texture, Transform, Vertex};
+
+const QUAD_VERTS: [Vec2; 4] = [vec2(0., 0.), vec2(1., 0.), vec2(0., 1.), vec2(1., 1.)];
+const QUAD_IDX: [i32; 6] = [0, 1, 2, 2, 1, 3];
+
+/// Many sprites from the same texture, drawn all at once.
+///
+/// Every [`draw`][super::draw] call goes to the renderer separately, which adds up quickly for
+/// things like tile maps. A batch collects sprites and draws them together, which is much
+/// faster.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let tileset = Texture::empty();
+/// # let map = [[0u32; 200]; 200];
+/// let mut batch = SpriteBatch::new(&tileset);
+/// for (y, row) in map.iter().enumerate() {
+///     for (x, &tile) in row.iter().enumerate() {
+///         let rect = Rect::new(tile * 16, 0, 16, 16);
+///         batch.add_rect(&rect, vec2(x as f32, y as f32) * 16.);
+///     }
+/// }
+///
+/// // The whole map is drawn at once, and can be moved around as one
+/// gfx::draw(&batch, vec2(-100., -50.));
+/// ```
+///
+/// A batch can be kept around and drawn every frame, as long as its sprites don't change.
+#[must_use]
+#[derive(Clone)]
+pub struct SpriteBatch {
+    texture: Texture,
+    vertices: Vec<Vertex>,
+    indices: Vec<i32>,
+}
+
+impl SpriteBatch {
+    /// Creates an empty batch, for sprites from a texture.
+    pub fn new(texture: &Texture) -> Self {
+        Self::with_capacity(texture, 0)
+    }
+
+    /// Creates an empty batch, with room for some sprites.
+    pub fn with_capacity(texture: &Texture, sprites: usize) -> Self {
+        Self {
+            texture: texture.clone(),
+            vertices: Vec::with_capacity(sprites * 4),
+            indices: Vec::with_capacity(sprites * 6),
+        }
+    }
+
+    /// Adds the whole texture.
+    pub fn add(&mut self, transform: impl Into<Transform>) {
+        let rect = Rect::new(0, 0, self.texture.width(), self.texture.height());
+        self.add_rect_tinted(&rect, self.texture.tint(), transform);
+    }
+
+    /// Adds a part of the texture, like a tile or a frame from a spritesheet.
+    pub fn add_rect(&mut self, rect: &Rect, transform: impl Into<Transform>) {
+        self.add_rect_tinted(rect, self.texture.tint(), transform);
+    }
+
+    /// Adds a part of the texture, multiplied by a color.
+    pub fn add_rect_tinted(&mut self, rect: &Rect, tint: Color, transform: impl Into<Transform>) {
+        let texture_size = vec2(self.texture.width() as f32, self.texture.height() as f32);
+        let size = vec2(rect.w as f32, rect.h as f32);
+        let uv = vec2(rect.x as f32, rect.y as f32) / texture_size;
+        let uv_size = size / texture_size;
+        let transform = transform.into().scale(size);
+
+        let first = self.vertices.len() as i32;
+        self.vertices.extend(QUAD_VERTS.map(|p| {
+            let coord = transform.transform_point(p - self.texture.origin());
+            Vertex::new(coord, tint, p * uv_size + uv)
+        }));
+        self.indices.extend(QUAD_IDX.map(|i| first + i));
+    }
+
+    /// Removes all sprites, keeping the memory for reuse.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+    }
+
+    /// Number of sprites in this batch.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.vertices.len() / 4
+    }
+
+    /// Does this batch have no sprites?
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// The texture sprites are taken from.
+    pub const fn texture(&self) -> &Texture {
+        &self.texture
+    }
+}
+
+impl Drawable for SpriteBatch {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        if self.is_empty() {
+            return;
+        }
+        if transform.to_affine() == Affine2::IDENTITY {
+            canvas.draw_geometry(&self.texture, &self.vertices, Some(&self.indices));
+            return;
+        }
+
+        with_frame_arena(|arena| {
+            let vertices = arena.alloc_slice_copy(&self.vertices);
+            for vertex in vertices.iter_mut() {
+                vertex.coord = transform.transform_point(vertex.coord);
+            }
+            canvas.draw_geometry(&self.texture, vertices, Some(&self.indices));
+        });
+    }
+}
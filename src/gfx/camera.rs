@@ -0,0 +1,296 @@
+use glam::{vec2, Vec2};
+
+use super::{screen_size, with_canvas, Transform};
+use crate::input::{self, MouseButton};
+use crate::time;
+
+/// A view into the world, which can be moved, zoomed and rotated.
+///
+/// While a camera is active, everything drawn is in world coordinates, and the camera decides
+/// where it ends up on the screen.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let (player, map) = (Vec2::ZERO, Texture::empty());
+/// let camera = Camera::new(player)
+///     .with_offset(vec2(160., 90.))
+///     .with_zoom(2.);
+///
+/// gfx::with_camera(&camera, || {
+///     gfx::draw(&map, Vec2::ZERO);
+///     gfx::draw_circle(8., Color::RED, player);
+/// });
+///
+/// // The UI is drawn on top, in screen coordinates
+/// gfx::draw_rect(vec2(100., 8.), Color::GREEN, vec2(4., 4.));
+///
+/// // Which tile was clicked?
+/// let clicked = camera.screen_to_world(mouse_position());
+/// ```
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    /// The point in the world the camera looks at.
+    pub position: Vec2,
+    /// Where `position` ends up on the screen. For a camera that follows something around, this
+    /// is usually the center of the screen.
+    pub offset: Vec2,
+    /// How much the world is scaled up.
+    pub zoom: f32,
+    /// Rotation of the camera, in radians. The world turns the other way.
+    pub rotation: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new(Vec2::ZERO)
+    }
+}
+
+impl Camera {
+    /// Creates a camera looking at a point, which is shown at the top left of the screen.
+    pub const fn new(position: Vec2) -> Self {
+        Self {
+            position,
+            offset: Vec2::ZERO,
+            zoom: 1.,
+            rotation: 0.,
+        }
+    }
+
+    /// Sets where the camera's position is shown on the screen.
+    pub const fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the zoom of this camera.
+    pub const fn with_zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    /// Sets the rotation of this camera, in radians.
+    pub const fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// The transform from world coordinates to screen coordinates.
+    pub fn transform(&self) -> Transform {
+        Transform::from_translation(self.offset)
+            .scale(Vec2::splat(self.zoom))
+            .rotate(-self.rotation)
+            .translate(-self.position)
+    }
+
+    /// Converts a point on the screen, like the [mouse position][crate::input::mouse_position],
+    /// to world coordinates.
+    #[must_use]
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        self.transform().inverse().transform_point(point)
+    }
+
+    /// Converts a point in the world to screen coordinates.
+    #[must_use]
+    pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
+        self.transform().transform_point(point)
+    }
+}
+
+/// Makes everything drawn after this go through a camera. `None` goes back to screen
+/// coordinates.
+pub fn set_camera(camera: Option<&Camera>) {
+    let view = camera.map_or(Transform::IDENTITY, Camera::transform);
+    let _ = with_canvas(|canvas| canvas.set_view(view));
+}
+
+/// Draws through a camera for the duration of `f`.
+///
+/// Calls can be nested; the previous camera is restored afterwards.
+pub fn with_camera<T>(camera: &Camera, f: impl FnOnce() -> T) -> T {
+    let previous = with_canvas(|canvas| canvas.set_view(camera.transform()));
+    let result = f();
+    let _ = with_canvas(|canvas| canvas.set_view(previous));
+    result
+}
+
+/// Moves a [`Camera`] around with the mouse and touch screen, like in strategy games and map
+/// views.
+///
+/// The world can be dragged around with a mouse button, zoomed towards the cursor with the wheel,
+/// and pinched with two fingers. Edge scrolling and bounds are off until they're set.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let map = Texture::empty();
+/// use baba::gfx::CameraController;
+///
+/// let mut camera = Camera::default();
+/// let mut controller = CameraController::new()
+///     .with_edge_scrolling(8., 400.)
+///     .with_bounds(Vec2::ZERO, vec2(2048., 2048.));
+///
+/// // Every frame
+/// controller.update(&mut camera);
+/// gfx::with_camera(&camera, || gfx::draw(&map, Vec2::ZERO));
+/// ```
+///
+/// Bounds don't account for the camera's rotation.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct CameraController {
+    button: Option<MouseButton>,
+    zoom_step: f32,
+    min_zoom: f32,
+    max_zoom: f32,
+    edge_margin: f32,
+    edge_speed: f32,
+    bounds: Option<(Vec2, Vec2)>,
+    drag: Option<Vec2>,
+    pinch: Option<(Vec2, f32)>,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CameraController {
+    /// Creates a controller which drags with the left mouse button.
+    pub const fn new() -> Self {
+        Self {
+            button: Some(MouseButton::Left),
+            zoom_step: 1.1,
+            min_zoom: 0.25,
+            max_zoom: 4.,
+            edge_margin: 0.,
+            edge_speed: 0.,
+            bounds: None,
+            drag: None,
+            pinch: None,
+        }
+    }
+
+    /// Sets which mouse button drags the world around, or `None` to not drag with the mouse.
+    pub const fn with_drag_button(mut self, button: Option<MouseButton>) -> Self {
+        self.button = button;
+        self
+    }
+
+    /// Sets how much zoom changes with each step of the mouse wheel. Defaults to 1.1, and 1
+    /// turns wheel zooming off.
+    pub const fn with_zoom_step(mut self, step: f32) -> Self {
+        self.zoom_step = step;
+        self
+    }
+
+    /// Sets how far the camera can zoom in and out. Defaults to 0.25 to 4.
+    pub const fn with_zoom_range(mut self, min: f32, max: f32) -> Self {
+        self.min_zoom = min;
+        self.max_zoom = max;
+        self
+    }
+
+    /// Scrolls when the mouse is within `margin` pixels of the edge of the screen, at `speed`
+    /// screen pixels per second.
+    pub const fn with_edge_scrolling(mut self, margin: f32, speed: f32) -> Self {
+        self.edge_margin = margin;
+        self.edge_speed = speed;
+        self
+    }
+
+    /// Keeps the camera from showing anything outside of a rectangle in the world. If the
+    /// rectangle is smaller than the screen, it's kept in the middle.
+    pub const fn with_bounds(mut self, min: Vec2, max: Vec2) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// Is the world being dragged or pinched?
+    #[must_use]
+    pub const fn is_dragging(&self) -> bool {
+        self.drag.is_some() || self.pinch.is_some()
+    }
+
+    /// Moves the camera from this frame's input. This should be called every frame.
+    pub fn update(&mut self, camera: &mut Camera) {
+        let mouse = input::mouse_position();
+        let fingers: Vec<Vec2> = input::touches().take(2).collect();
+
+        if let [a, b] = fingers[..] {
+            // Touching also moves the mouse, so it shouldn't drag at the same time
+            self.drag = None;
+            let (center, distance) = ((a + b) / 2., a.distance(b));
+            if let Some((last_center, last_distance)) = self.pinch {
+                let before = camera.screen_to_world(last_center);
+                if last_distance > 0. {
+                    camera.zoom = self.clamp_zoom(camera.zoom * distance / last_distance);
+                }
+                camera.position += before - camera.screen_to_world(center);
+            }
+            self.pinch = Some((center, distance));
+        } else {
+            self.pinch = None;
+            self.drag = match self.button {
+                Some(button) if input::is_mouse_down(button) => {
+                    if let Some(last) = self.drag {
+                        camera.position +=
+                            camera.screen_to_world(last) - camera.screen_to_world(mouse);
+                    }
+                    Some(mouse)
+                }
+                _ => None,
+            };
+        }
+
+        let wheel = input::mouse_wheel().y;
+        if wheel != 0. {
+            let before = camera.screen_to_world(mouse);
+            camera.zoom = self.clamp_zoom(camera.zoom * self.zoom_step.powf(wheel));
+            camera.position += before - camera.screen_to_world(mouse);
+        }
+
+        let screen = screen_size();
+        if self.edge_margin > 0. && !self.is_dragging() {
+            let inside = mouse.cmpge(Vec2::ZERO).all() && mouse.cmplt(screen).all();
+            let towards = |near: f32, far: f32| {
+                f32::from(far < self.edge_margin) - f32::from(near < self.edge_margin)
+            };
+            let direction = vec2(
+                towards(mouse.x, screen.x - mouse.x),
+                towards(mouse.y, screen.y - mouse.y),
+            );
+            if inside && direction != Vec2::ZERO {
+                let step = direction.normalize() * self.edge_speed * time::delta();
+                camera.position +=
+                    camera.screen_to_world(step) - camera.screen_to_world(Vec2::ZERO);
+            }
+        }
+
+        if let Some((min, max)) = self.bounds {
+            // The part of the world on the screen, around the camera's position
+            let before = camera.offset / camera.zoom;
+            let after = (screen - camera.offset) / camera.zoom;
+            let low = min + before;
+            let high = max - after;
+            camera.position = vec2(
+                clamp_or_center(camera.position.x, low.x, high.x),
+                clamp_or_center(camera.position.y, low.y, high.y),
+            );
+        }
+    }
+
+    const fn clamp_zoom(&self, zoom: f32) -> f32 {
+        zoom.clamp(self.min_zoom, self.max_zoom.max(self.min_zoom))
+    }
+}
+
+fn clamp_or_center(value: f32, low: f32, high: f32) -> f32 {
+    if low > high {
+        low.midpoint(high)
+    } else {
+        value.clamp(low, high)
+    }
+}
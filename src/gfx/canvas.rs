@@ -4,16 +4,30 @@ use std::ptr::NonNull;
 
 use sdl2::VideoSubsystem;
 use sdl2_sys::{
-    SDL_CreateRenderer, SDL_CreateWindow, SDL_EventType, SDL_GetRendererInfo,
-    SDL_GetWindowDisplayMode, SDL_PollEvent, SDL_RenderClear, SDL_RenderGeometry,
-    SDL_RenderPresent, SDL_RenderSetIntegerScale, SDL_RenderSetLogicalSize, SDL_RenderSetVSync,
-    SDL_Renderer, SDL_SetRenderDrawColor, SDL_SetWindowMinimumSize, SDL_SetWindowSize,
-    SDL_SetWindowTitle, SDL_ShowWindow, SDL_Window, SDL_bool, SDL_WINDOWPOS_UNDEFINED_MASK,
+    SDL_BlendMode, SDL_CalculateGammaRamp, SDL_CreateRenderer, SDL_CreateWindow, SDL_EventType,
+    SDL_GetRenderTarget, SDL_GetRendererInfo, SDL_GetRendererOutputSize, SDL_GetTextureBlendMode,
+    SDL_GetWindowDisplayIndex, SDL_GetWindowDisplayMode, SDL_GetWindowFlags, SDL_GetWindowPosition,
+    SDL_GetWindowSize, SDL_GetWindowSizeInPixels, SDL_MaximizeWindow, SDL_MinimizeWindow,
+    SDL_MouseWheelDirection, SDL_PollEvent, SDL_RenderClear, SDL_RenderFillRect,
+    SDL_RenderGeometry, SDL_RenderGetLogicalSize, SDL_RenderGetScale, SDL_RenderLogicalToWindow,
+    SDL_RenderPresent, SDL_RenderSetIntegerScale, SDL_RenderSetLogicalSize, SDL_RenderSetScale,
+    SDL_RenderSetVSync, SDL_RenderWindowToLogical, SDL_Renderer, SDL_RestoreWindow,
+    SDL_SetRenderDrawBlendMode, SDL_SetRenderDrawColor, SDL_SetRenderTarget,
+    SDL_SetTextureBlendMode, SDL_SetWindowFullscreen, SDL_SetWindowGammaRamp,
+    SDL_SetWindowMinimumSize, SDL_SetWindowPosition, SDL_SetWindowSize, SDL_SetWindowTitle,
+    SDL_ShowWindow, SDL_Texture, SDL_Window, SDL_WindowEventID, SDL_WindowFlags, SDL_bool,
+    SDL_WINDOWPOS_CENTERED_MASK, SDL_WINDOWPOS_UNDEFINED_MASK,
 };
 use thiserror::Error;
 
-use crate::gfx::{Drawable, Texture, Transform, Vertex};
-use crate::{input, SdlError};
+use crate::alloc::with_frame_arena;
+use crate::gfx::{
+    multiply_colors, unmultiply_color, BlendMode, Color, Drawable, Grade, PostChain, Texture,
+    Transform, Vertex,
+};
+use crate::input::{self, Modifiers};
+use crate::math::{vec2, Affine2, Vec2};
+use crate::SdlError;
 
 /// Defines how coordinates are translated.
 #[must_use]
@@ -56,6 +70,60 @@ pub enum ViewportScaling {
     Fractional,
 }
 
+/// Fullscreen mode for the window.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Fullscreen {
+    /// A normal window.
+    #[default]
+    Off,
+    /// A borderless window covering the whole display. This is what players usually expect, as
+    /// it's quick to switch in and out of.
+    Desktop,
+    /// Takes over the display, changing its resolution to the window size.
+    Exclusive,
+}
+
+impl Fullscreen {
+    const fn flags(self) -> u32 {
+        match self {
+            Self::Off => 0,
+            Self::Desktop => SDL_WindowFlags::SDL_WINDOW_FULLSCREEN_DESKTOP as u32,
+            Self::Exclusive => SDL_WindowFlags::SDL_WINDOW_FULLSCREEN as u32,
+        }
+    }
+}
+
+/// Where the window is placed on the screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WindowPosition {
+    /// Wherever the system decides.
+    #[default]
+    Default,
+    /// In the middle of the main display.
+    Centered,
+    /// In the middle of a display, counting from 0. Displays which don't exist fall back to the
+    /// main one.
+    CenteredOn(u32),
+    /// At exact coordinates of the top left corner, across all displays.
+    At(i32, i32),
+}
+
+impl WindowPosition {
+    #[allow(clippy::cast_possible_wrap)]
+    const fn to_sdl(self) -> (i32, i32) {
+        let centered = SDL_WINDOWPOS_CENTERED_MASK as i32;
+        match self {
+            Self::Default => {
+                let undefined = SDL_WINDOWPOS_UNDEFINED_MASK as i32;
+                (undefined, undefined)
+            }
+            Self::Centered => (centered, centered),
+            Self::CenteredOn(display) => (centered | display as i32, centered | display as i32),
+            Self::At(x, y) => (x, y),
+        }
+    }
+}
+
 /// Canvas creation error.
 #[derive(Debug, Error)]
 pub enum CanvasError {
@@ -64,11 +132,29 @@ pub enum CanvasError {
     Sdl(#[from] SdlError),
 }
 
+/// Something that happened to the window, which the game may want to react to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WindowEvent {
+    /// The window was resized, to a size in window units.
+    Resized(u32, u32),
+    /// The window gained or lost focus.
+    FocusChanged(bool),
+    /// The user asked to close the game, like with the window's close button.
+    QuitRequested,
+}
+
 /// An object responsible for rendering stuff onto a window.
 #[derive(Clone)]
 pub struct Canvas {
     window: NonNull<SDL_Window>,
     renderer: NonNull<SDL_Renderer>,
+    blend_mode: Option<BlendMode>,
+    view: Transform,
+    tint: Color,
+    color_grade: Grade,
+    post_chain: PostChain,
+    brightness: f32,
+    gamma: f32,
     _video: VideoSubsystem,
 }
 
@@ -85,38 +171,137 @@ impl Canvas {
         Ok(Self {
             window,
             renderer,
+            blend_mode: None,
+            view: Transform::IDENTITY,
+            tint: Color::WHITE,
+            color_grade: Grade::NONE,
+            post_chain: PostChain::new(),
+            brightness: 0.,
+            gamma: 1.,
             _video: video.clone(),
         })
     }
 
+    pub(crate) const fn window(&self) -> *mut SDL_Window {
+        self.window.as_ptr()
+    }
+
     pub(crate) fn renderer(&mut self) -> *mut SDL_Renderer {
         self.renderer.as_ptr()
     }
 
+    /// Makes drawing go to a texture, or to the window if it's null. Returns the previous target.
+    pub(crate) fn set_target(&mut self, texture: *mut SDL_Texture) -> *mut SDL_Texture {
+        let renderer = self.renderer.as_ptr();
+        let previous = unsafe { SDL_GetRenderTarget(renderer) };
+        if unsafe { SDL_SetRenderTarget(renderer, texture) } < 0 {
+            log::warn!("Failed to set render target: {}", SdlError::from_sdl());
+        }
+        previous
+    }
+
+    /// Handles pending input events. Window events are added to `events`, for the game to
+    /// react to. Returns when the oldest input event was received, in SDL ticks, if there was
+    /// any input.
     #[allow(clippy::unused_self)]
-    pub(crate) fn process_events(&self) -> bool {
+    pub(crate) fn process_events(&self, events: &mut Vec<WindowEvent>) -> Option<u32> {
+        span!(INFO, "events");
         let mut event = MaybeUninit::uninit();
+        let mut oldest_input = None;
 
         while unsafe { SDL_PollEvent(event.as_mut_ptr()) } == 1 {
             let event = unsafe { event.assume_init() };
+            let kind = unsafe { std::mem::transmute::<u32, SDL_EventType>(event.type_) };
+
+            if is_input(kind) {
+                let timestamp = unsafe { event.common.timestamp };
+                oldest_input = Some(oldest_input.map_or(timestamp, |t: u32| t.min(timestamp)));
+            }
 
             unsafe {
-                match std::mem::transmute::<u32, SDL_EventType>(event.type_) {
-                    SDL_EventType::SDL_QUIT => return false,
+                match kind {
+                    SDL_EventType::SDL_QUIT => events.push(WindowEvent::QuitRequested),
+                    SDL_EventType::SDL_WINDOWEVENT => {
+                        let window = event.window;
+                        let id = u32::from(window.event);
+                        if id == SDL_WindowEventID::SDL_WINDOWEVENT_SIZE_CHANGED as u32 {
+                            #[allow(clippy::cast_sign_loss)]
+                            let (w, h) = (window.data1 as u32, window.data2 as u32);
+                            events.push(WindowEvent::Resized(w, h));
+                            self.update_dpi_scale();
+                        } else if id == SDL_WindowEventID::SDL_WINDOWEVENT_DISPLAY_CHANGED as u32 {
+                            self.update_dpi_scale();
+                        } else if id == SDL_WindowEventID::SDL_WINDOWEVENT_FOCUS_GAINED as u32 {
+                            events.push(WindowEvent::FocusChanged(true));
+                        } else if id == SDL_WindowEventID::SDL_WINDOWEVENT_FOCUS_LOST as u32 {
+                            events.push(WindowEvent::FocusChanged(false));
+                        }
+                    }
                     SDL_EventType::SDL_KEYDOWN if event.key.repeat == 0 => {
                         let key = bytemuck::checked::cast(event.key.keysym.scancode as u32);
                         input::press_key(key);
+                        input::set_modifiers(Modifiers::from_sdl(event.key.keysym.mod_));
+                    }
+                    SDL_EventType::SDL_KEYDOWN => {
+                        let key = bytemuck::checked::cast(event.key.keysym.scancode as u32);
+                        input::repeat_key(key);
+                    }
+                    SDL_EventType::SDL_TEXTINPUT => {
+                        let text = CStr::from_ptr(event.text.text.as_ptr());
+                        input::type_text(&text.to_string_lossy());
                     }
                     SDL_EventType::SDL_KEYUP => {
                         let key = bytemuck::checked::cast(event.key.keysym.scancode as u32);
                         input::release_key(key);
+                        input::set_modifiers(Modifiers::from_sdl(event.key.keysym.mod_));
+                    }
+                    SDL_EventType::SDL_MOUSEMOTION => {
+                        input::move_mouse(vec2(event.motion.x as f32, event.motion.y as f32));
+                    }
+                    SDL_EventType::SDL_MOUSEBUTTONDOWN => {
+                        if let Ok(button) = bytemuck::checked::try_cast(event.button.button) {
+                            input::press_mouse(button);
+                        }
+                    }
+                    SDL_EventType::SDL_MOUSEBUTTONUP => {
+                        if let Ok(button) = bytemuck::checked::try_cast(event.button.button) {
+                            input::release_mouse(button);
+                        }
+                    }
+                    SDL_EventType::SDL_MOUSEWHEEL => {
+                        let wheel = event.wheel;
+                        let mut delta = vec2(wheel.preciseX, wheel.preciseY);
+                        if wheel.direction == SDL_MouseWheelDirection::SDL_MOUSEWHEEL_FLIPPED as u32
+                        {
+                            delta = -delta;
+                        }
+                        input::scroll_mouse(delta);
+                    }
+                    SDL_EventType::SDL_FINGERDOWN | SDL_EventType::SDL_FINGERMOTION => {
+                        let finger = event.tfinger;
+                        // SDL already maps fingers to go from 0 to 1 across the logical screen
+                        let (w, h) = self.size();
+                        let position = vec2(finger.x * w as f32, finger.y * h as f32);
+                        input::touch(finger.fingerId, position);
+                    }
+                    SDL_EventType::SDL_FINGERUP => input::release_touch(event.tfinger.fingerId),
+                    SDL_EventType::SDL_CONTROLLERDEVICEADDED => {
+                        input::gamepad::connect(event.cdevice.which);
+                    }
+                    SDL_EventType::SDL_CONTROLLERDEVICEREMOVED => {
+                        input::gamepad::disconnect(event.cdevice.which);
+                    }
+                    SDL_EventType::SDL_CONTROLLERBUTTONDOWN => {
+                        input::gamepad::press_button(event.cbutton.which, event.cbutton.button);
+                    }
+                    SDL_EventType::SDL_CONTROLLERBUTTONUP => {
+                        input::gamepad::release_button(event.cbutton.which, event.cbutton.button);
                     }
                     _ => {}
                 }
             }
         }
-
-        true
+        oldest_input
     }
 
     /// Queries some information about the window.
@@ -161,6 +346,89 @@ impl Canvas {
         unsafe { SDL_SetWindowSize(self.window.as_ptr(), width as i32, height as i32) };
     }
 
+    /// Moves the window.
+    pub fn set_window_position(&mut self, position: WindowPosition) {
+        let (x, y) = position.to_sdl();
+        unsafe { SDL_SetWindowPosition(self.window.as_ptr(), x, y) };
+    }
+
+    /// Position of the window's top left corner, across all displays.
+    #[must_use]
+    pub fn get_window_position(&self) -> (i32, i32) {
+        let (mut x, mut y) = (0, 0);
+        unsafe { SDL_GetWindowPosition(self.window.as_ptr(), &raw mut x, &raw mut y) };
+        (x, y)
+    }
+
+    /// Minimizes the window.
+    pub fn minimize(&mut self) {
+        unsafe { SDL_MinimizeWindow(self.window.as_ptr()) };
+    }
+
+    /// Maximizes the window, making it as large as the display allows without going fullscreen.
+    pub fn maximize(&mut self) {
+        unsafe { SDL_MaximizeWindow(self.window.as_ptr()) };
+    }
+
+    /// Restores the window to its normal size, if it's minimized or maximized.
+    pub fn restore(&mut self) {
+        unsafe { SDL_RestoreWindow(self.window.as_ptr()) };
+    }
+
+    /// Is the window minimized?
+    #[must_use]
+    pub fn is_minimized(&self) -> bool {
+        self.has_flag(SDL_WindowFlags::SDL_WINDOW_MINIMIZED)
+    }
+
+    /// Is the window maximized?
+    #[must_use]
+    pub fn is_maximized(&self) -> bool {
+        self.has_flag(SDL_WindowFlags::SDL_WINDOW_MAXIMIZED)
+    }
+
+    /// Does the window have keyboard focus? This is `false` while the player is using another
+    /// window.
+    #[must_use]
+    pub fn is_focused(&self) -> bool {
+        self.has_flag(SDL_WindowFlags::SDL_WINDOW_INPUT_FOCUS)
+    }
+
+    fn has_flag(&self, flag: SDL_WindowFlags) -> bool {
+        let flags = unsafe { SDL_GetWindowFlags(self.window.as_ptr()) };
+        flags & flag as u32 != 0
+    }
+
+    /// Index of the display the window is on.
+    #[must_use]
+    pub fn display_index(&self) -> u32 {
+        let index = unsafe { SDL_GetWindowDisplayIndex(self.window.as_ptr()) };
+        u32::try_from(index).unwrap_or(0)
+    }
+
+    /// Sets the fullscreen mode. Returns whether it succeeded.
+    pub fn set_fullscreen(&mut self, mode: Fullscreen) -> bool {
+        if unsafe { SDL_SetWindowFullscreen(self.window.as_ptr(), mode.flags()) } < 0 {
+            log::warn!("Failed to set fullscreen: {}", SdlError::from_sdl());
+            return false;
+        }
+        true
+    }
+
+    /// The current fullscreen mode.
+    #[must_use]
+    pub fn fullscreen(&self) -> Fullscreen {
+        let flags = unsafe { SDL_GetWindowFlags(self.window.as_ptr()) };
+        let desktop = Fullscreen::Desktop.flags();
+        if flags & desktop == desktop {
+            Fullscreen::Desktop
+        } else if flags & Fullscreen::Exclusive.flags() != 0 {
+            Fullscreen::Exclusive
+        } else {
+            Fullscreen::Off
+        }
+    }
+
     /// Toggles vertical sync.
     pub fn set_vsync(&mut self, vsync: bool) -> bool {
         unsafe { SDL_RenderSetVSync(self.renderer.as_ptr(), i32::from(vsync)) == 0 }
@@ -178,13 +446,84 @@ impl Canvas {
         let _ = unsafe { SDL_RenderSetIntegerScale(self.renderer.as_ptr(), enable) == 0 };
     }
 
+    /// How many pixels are drawn for each unit of the window's size. This is usually 2 on high
+    /// DPI displays, like Apple's retina displays, and 1 everywhere else.
+    #[must_use]
+    pub fn dpi_scale(&self) -> f32 {
+        let window = self.window.as_ptr();
+        let (mut w, mut h) = (0, 0);
+        let (mut pixels_w, mut pixels_h) = (0, 0);
+        unsafe {
+            SDL_GetWindowSize(window, &raw mut w, &raw mut h);
+            SDL_GetWindowSizeInPixels(window, &raw mut pixels_w, &raw mut pixels_h);
+        }
+        if w > 0 {
+            pixels_w as f32 / w as f32
+        } else {
+            1.
+        }
+    }
+
+    /// Without a viewport, things are drawn in window coordinates like the mouse, instead of in
+    /// pixels. Viewports already take the scale into account.
+    fn update_dpi_scale(&self) {
+        let renderer = self.renderer.as_ptr();
+        let (mut w, mut h) = (0, 0);
+        unsafe { SDL_RenderGetLogicalSize(renderer, &raw mut w, &raw mut h) };
+        if w == 0 || h == 0 {
+            let scale = self.dpi_scale();
+            let _ = unsafe { SDL_RenderSetScale(renderer, scale, scale) };
+        }
+    }
+
     /// Sets the viewport for this canvas, changing how coordinates are used.
     pub fn set_viewport(&mut self, viewport: &Viewport) {
         self.set_logical_size(viewport.logical_size.0, viewport.logical_size.1);
         self.set_integer_scaling(matches!(viewport.scaling, ViewportScaling::Integer));
     }
 
+    /// Converts a point in window pixels to the coordinates things are drawn in, going through
+    /// the viewport's scaling and letterboxing.
+    #[must_use]
+    pub fn window_to_logical(&self, point: Vec2) -> Vec2 {
+        let (mut x, mut y) = (0., 0.);
+        let (wx, wy) = (point.x as i32, point.y as i32);
+        unsafe {
+            SDL_RenderWindowToLogical(self.renderer.as_ptr(), wx, wy, &raw mut x, &raw mut y)
+        };
+        vec2(x, y)
+    }
+
+    /// Converts a point in the coordinates things are drawn in to window pixels.
+    #[must_use]
+    pub fn logical_to_window(&self, point: Vec2) -> Vec2 {
+        let (mut x, mut y) = (0, 0);
+        let renderer = self.renderer.as_ptr();
+        unsafe { SDL_RenderLogicalToWindow(renderer, point.x, point.y, &raw mut x, &raw mut y) };
+        vec2(x as f32, y as f32)
+    }
+
+    /// The size of the screen, in the coordinates things are drawn in. This is the viewport's
+    /// logical size if one is set, or the size of the render target or window otherwise.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn size(&self) -> (u32, u32) {
+        let renderer = self.renderer.as_ptr();
+        let (mut w, mut h) = (0, 0);
+        unsafe { SDL_RenderGetLogicalSize(renderer, &raw mut w, &raw mut h) };
+        if w == 0 || h == 0 {
+            let (mut scale_x, mut scale_y) = (1., 1.);
+            unsafe {
+                SDL_GetRendererOutputSize(renderer, &raw mut w, &raw mut h);
+                SDL_RenderGetScale(renderer, &raw mut scale_x, &raw mut scale_y);
+            }
+            return ((w as f32 / scale_x) as u32, (h as f32 / scale_y) as u32);
+        }
+        (w as u32, h as u32)
+    }
+
     pub(crate) fn show_window(&self) {
+        self.update_dpi_scale();
         unsafe { SDL_ShowWindow(self.window.as_ptr()) };
     }
 
@@ -198,25 +537,214 @@ impl Canvas {
 
     /// Displays the current frame.
     pub fn display(&mut self) {
+        span!(INFO, "present");
+        if !self.post_chain.is_empty() {
+            let chain = std::mem::take(&mut self.post_chain);
+            chain.apply(self);
+            self.post_chain = chain;
+        }
+        if !self.color_grade.is_none() || self.brightness != 0. {
+            let brightness = (self.color_grade.brightness + self.brightness).clamp(-1., 1.);
+            self.apply_grade(self.color_grade.with_brightness(brightness));
+        }
         unsafe { SDL_RenderPresent(self.renderer.as_ptr()) };
     }
 
+    /// Sets the [color grade][Grade] applied to every frame before it's displayed.
+    pub const fn set_color_grade(&mut self, grade: Grade) {
+        self.color_grade = grade;
+    }
+
+    /// The color grade set with [`set_color_grade`][Self::set_color_grade].
+    pub const fn color_grade(&self) -> Grade {
+        self.color_grade
+    }
+
+    /// Sets the [effects][PostChain] applied to every frame before it's displayed, before the
+    /// color grade.
+    pub fn set_post_chain(&mut self, chain: PostChain) {
+        self.post_chain = chain;
+    }
+
+    /// The effects set with [`set_post_chain`][Self::set_post_chain], for changing them.
+    pub const fn post_chain_mut(&mut self) -> &mut PostChain {
+        &mut self.post_chain
+    }
+
+    /// Sets the player's brightness setting, from -1 to 1. It's added to the brightness of the
+    /// [color grade][Grade], so the game's own grades still work on top of it. Defaults to 0.
+    ///
+    /// This is drawn into every frame, so unlike [`set_gamma`][Self::set_gamma], it works on
+    /// every system, including HDR displays.
+    pub const fn set_brightness(&mut self, brightness: f32) {
+        self.brightness = brightness;
+    }
+
+    /// The brightness set with [`set_brightness`][Self::set_brightness].
+    #[must_use]
+    pub const fn brightness(&self) -> f32 {
+        self.brightness
+    }
+
+    /// Sets the display's gamma while the window is focused, for players with displays which are
+    /// too dark or too bright. 1 leaves colors as they are, and higher values are brighter.
+    /// Returns whether it succeeded.
+    ///
+    /// Unlike a [color grade][Grade], this changes how the display shows colors, so it costs
+    /// nothing, but not every system supports it. Some only support it in fullscreen.
+    pub fn set_gamma(&mut self, gamma: f32) -> bool {
+        let mut ramp = [0; 256];
+        unsafe { SDL_CalculateGammaRamp(gamma.max(0.), ramp.as_mut_ptr()) };
+        let ramp = ramp.as_ptr();
+        if unsafe { SDL_SetWindowGammaRamp(self.window.as_ptr(), ramp, ramp, ramp) } < 0 {
+            log::warn!("Failed to set gamma: {}", SdlError::from_sdl());
+            return false;
+        }
+        self.gamma = gamma;
+        true
+    }
+
+    /// The gamma set with [`set_gamma`][Self::set_gamma].
+    #[must_use]
+    pub const fn gamma(&self) -> f32 {
+        self.gamma
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    pub(crate) fn apply_grade(&mut self, grade: Grade) {
+        let renderer = self.renderer.as_ptr();
+        let Grade { tint, brightness } = grade;
+        let brightness = brightness.clamp(-1., 1.);
+
+        // Without shaders, multiplying and adding full screen rectangles is what's possible
+        let darken = 1. + brightness.min(0.);
+        let [r, g, b, _] = tint.to_array().map(|c| (f32::from(c) * darken) as u8);
+        let lighten = (brightness.max(0.) * 255.) as u8;
+        unsafe {
+            SDL_SetRenderDrawBlendMode(renderer, BlendMode::Multiply.to_sdl());
+            SDL_SetRenderDrawColor(renderer, r, g, b, 255);
+            SDL_RenderFillRect(renderer, std::ptr::null());
+            if lighten > 0 {
+                SDL_SetRenderDrawBlendMode(renderer, BlendMode::Additive.to_sdl());
+                SDL_SetRenderDrawColor(renderer, lighten, lighten, lighten, 255);
+                SDL_RenderFillRect(renderer, std::ptr::null());
+            }
+        }
+    }
+
     /// Draws an object
     pub fn draw<T: Drawable>(&mut self, object: &T, transform: impl Into<Transform>) {
         object.draw(self, transform.into());
     }
 
+    /// Overrides the blend mode for everything drawn after this. `None` goes back to using each
+    /// texture's own blend mode, and alpha blending for shapes.
+    pub const fn set_blend_mode(&mut self, mode: Option<BlendMode>) {
+        self.blend_mode = mode;
+    }
+
+    /// The blend mode set with [`set_blend_mode`][Self::set_blend_mode].
+    #[must_use]
+    pub const fn blend_mode(&self) -> Option<BlendMode> {
+        self.blend_mode
+    }
+
+    /// Multiplies the color of everything drawn after this. Returns the previous tint.
+    pub const fn set_tint(&mut self, tint: Color) -> Color {
+        std::mem::replace(&mut self.tint, tint)
+    }
+
+    /// The tint set with [`set_tint`][Self::set_tint].
+    #[must_use]
+    pub const fn tint(&self) -> Color {
+        self.tint
+    }
+
+    /// Transforms everything drawn after this, like a [camera][super::Camera]. Returns the
+    /// previous transform.
+    pub const fn set_view(&mut self, view: Transform) -> Transform {
+        std::mem::replace(&mut self.view, view)
+    }
+
+    /// The transform set with [`set_view`][Self::set_view].
+    pub const fn view(&self) -> Transform {
+        self.view
+    }
+
     /// Draws vertices on the screen.
     pub fn draw_geometry(
         &mut self,
         texture: &Texture,
         vertices: &[Vertex],
         indices: Option<&[i32]>,
+    ) {
+        let texture = texture.raw();
+        let mut previous = SDL_BlendMode::SDL_BLENDMODE_INVALID;
+        unsafe { SDL_GetTextureBlendMode(texture, &raw mut previous) };
+        if let Some(mode) = self.blend_mode {
+            unsafe { SDL_SetTextureBlendMode(texture, mode.to_sdl()) };
+        }
+
+        // Colors are premultiplied, but other blend modes expect them not to be
+        let premultiplied = BlendMode::PremultipliedAlpha.to_sdl();
+        let straight = self.blend_mode.map_or(previous, BlendMode::to_sdl) != premultiplied;
+        self.render_geometry(texture, vertices, indices, straight);
+
+        if self.blend_mode.is_some() {
+            unsafe { SDL_SetTextureBlendMode(texture, previous) };
+        }
+    }
+
+    /// Draws vertices on the screen without a texture, using only their colors.
+    pub fn draw_colored_geometry(&mut self, vertices: &[Vertex], indices: Option<&[i32]>) {
+        let mode = self.blend_mode.unwrap_or_default();
+        unsafe { SDL_SetRenderDrawBlendMode(self.renderer.as_ptr(), mode.to_sdl()) };
+
+        // Like with textures, colors are premultiplied, but other blend modes expect them not to be
+        let straight = mode != BlendMode::PremultipliedAlpha;
+        self.render_geometry(std::ptr::null_mut(), vertices, indices, straight);
+    }
+
+    fn render_geometry(
+        &mut self,
+        texture: *mut SDL_Texture,
+        vertices: &[Vertex],
+        indices: Option<&[i32]>,
+        straight_alpha: bool,
+    ) {
+        span!(TRACE, "draw");
+        let tinted = self.tint != Color::WHITE;
+        let translucent = |color: Color| color.a() < u8::MAX;
+        let unmultiply = straight_alpha
+            && (translucent(self.tint) || vertices.iter().any(|v| translucent(v.color)));
+        if tinted || unmultiply || self.view.to_affine() != Affine2::IDENTITY {
+            return with_frame_arena(|arena| {
+                let vertices = arena.alloc_slice_copy(vertices);
+                for vertex in vertices.iter_mut() {
+                    vertex.coord = self.view.transform_point(vertex.coord);
+                    if tinted {
+                        vertex.color = multiply_colors(vertex.color, self.tint);
+                    }
+                    if unmultiply {
+                        vertex.color = unmultiply_color(vertex.color);
+                    }
+                }
+                self.submit_geometry(texture, vertices, indices);
+            });
+        }
+        self.submit_geometry(texture, vertices, indices);
+    }
+
+    fn submit_geometry(
+        &mut self,
+        texture: *mut SDL_Texture,
+        vertices: &[Vertex],
+        indices: Option<&[i32]>,
     ) {
         unsafe {
             SDL_RenderGeometry(
                 self.renderer.as_ptr(),
-                texture.raw(),
+                texture,
                 // Vertex and SDL_Vertex have the same layout, as Vec2 is also repr(C)
                 vertices.as_ptr().cast::<sdl2_sys::SDL_Vertex>(),
                 vertices.len() as i32,
@@ -227,6 +755,25 @@ impl Canvas {
     }
 }
 
+/// Is this an event from the player, like a key press, for measuring input latency?
+const fn is_input(kind: SDL_EventType) -> bool {
+    matches!(
+        kind,
+        SDL_EventType::SDL_KEYDOWN
+            | SDL_EventType::SDL_KEYUP
+            | SDL_EventType::SDL_TEXTINPUT
+            | SDL_EventType::SDL_MOUSEMOTION
+            | SDL_EventType::SDL_MOUSEBUTTONDOWN
+            | SDL_EventType::SDL_MOUSEBUTTONUP
+            | SDL_EventType::SDL_MOUSEWHEEL
+            | SDL_EventType::SDL_FINGERDOWN
+            | SDL_EventType::SDL_FINGERMOTION
+            | SDL_EventType::SDL_FINGERUP
+            | SDL_EventType::SDL_CONTROLLERBUTTONDOWN
+            | SDL_EventType::SDL_CONTROLLERBUTTONUP
+    )
+}
+
 /// Some information about the canvas' output
 #[derive(Default)]
 pub struct DisplayMode {
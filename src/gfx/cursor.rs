@@ -0,0 +1,217 @@
+use std::cell::RefCell;
+use std::panic::Location;
+use std::path::Path;
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+use image::io::Reader;
+use sdl2_sys::{
+    SDL_CreateColorCursor, SDL_CreateRGBSurfaceWithFormatFrom, SDL_CreateSystemCursor, SDL_Cursor,
+    SDL_FreeCursor, SDL_FreeSurface, SDL_GetDefaultCursor, SDL_SetCursor, SDL_ShowCursor,
+    SDL_SystemCursor,
+};
+
+use crate::SdlError;
+
+use super::{Origin, TextureLoadError, TextureLoadErrorKind};
+
+/// One of the operating system's own cursors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemCursor {
+    /// The usual arrow.
+    Arrow,
+    /// A text cursor.
+    IBeam,
+    /// Something is loading.
+    Wait,
+    /// Something is loading in the background, but the mouse can still be used.
+    WaitArrow,
+    /// A crosshair, for precise aiming.
+    Crosshair,
+    /// A pointing hand, for things which can be clicked.
+    Hand,
+    /// Something can't be done, like a slashed circle.
+    No,
+    /// Arrows pointing in every direction, for moving things.
+    Move,
+    /// Arrows pointing left and right, for resizing horizontally.
+    ResizeHorizontal,
+    /// Arrows pointing up and down, for resizing vertically.
+    ResizeVertical,
+}
+
+impl SystemCursor {
+    const fn to_sdl(self) -> SDL_SystemCursor {
+        match self {
+            Self::Arrow => SDL_SystemCursor::SDL_SYSTEM_CURSOR_ARROW,
+            Self::IBeam => SDL_SystemCursor::SDL_SYSTEM_CURSOR_IBEAM,
+            Self::Wait => SDL_SystemCursor::SDL_SYSTEM_CURSOR_WAIT,
+            Self::WaitArrow => SDL_SystemCursor::SDL_SYSTEM_CURSOR_WAITARROW,
+            Self::Crosshair => SDL_SystemCursor::SDL_SYSTEM_CURSOR_CROSSHAIR,
+            Self::Hand => SDL_SystemCursor::SDL_SYSTEM_CURSOR_HAND,
+            Self::No => SDL_SystemCursor::SDL_SYSTEM_CURSOR_NO,
+            Self::Move => SDL_SystemCursor::SDL_SYSTEM_CURSOR_SIZEALL,
+            Self::ResizeHorizontal => SDL_SystemCursor::SDL_SYSTEM_CURSOR_SIZEWE,
+            Self::ResizeVertical => SDL_SystemCursor::SDL_SYSTEM_CURSOR_SIZENS,
+        }
+    }
+}
+
+struct CursorData(NonNull<SDL_Cursor>);
+
+impl Drop for CursorData {
+    fn drop(&mut self) {
+        unsafe { SDL_FreeCursor(self.0.as_ptr()) };
+    }
+}
+
+/// A mouse cursor, which can be [set][set_cursor] for the window.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// use baba::gfx::{Cursor, SystemCursor};
+///
+/// let crosshair = Cursor::load("resources/crosshair.png", Origin::CENTER);
+/// gfx::set_cursor(&crosshair);
+///
+/// // Or use one of the system's own
+/// gfx::set_cursor(&Cursor::system(SystemCursor::Hand));
+/// ```
+///
+/// The size cursors are shown at depends on the system, and they aren't scaled by the
+/// [`Viewport`][super::Viewport].
+#[derive(Clone, Default)]
+pub struct Cursor {
+    // The default cursor belongs to SDL, and isn't freed
+    data: Option<Rc<CursorData>>,
+}
+
+impl Cursor {
+    /// The system's default cursor, which is usually an arrow.
+    #[must_use]
+    pub const fn arrow() -> Self {
+        Self { data: None }
+    }
+
+    /// One of the system's own cursors. If it isn't available, the default cursor is used.
+    #[must_use]
+    pub fn system(kind: SystemCursor) -> Self {
+        let cursor = unsafe { SDL_CreateSystemCursor(kind.to_sdl()) };
+        let Some(ptr) = NonNull::new(cursor) else {
+            log::error!("Failed to create cursor: {}", SdlError::from_sdl());
+            return Self::arrow();
+        };
+        Self {
+            data: Some(Rc::new(CursorData(ptr))),
+        }
+    }
+
+    /// Loads an image as a cursor. The origin is the point of the image that clicks.
+    #[track_caller]
+    #[must_use]
+    pub fn load(path: impl AsRef<Path>, hotspot: Origin) -> Self {
+        Self::try_load(path, hotspot)
+            .inspect_err(|e| log::error!("Failed to load {e}"))
+            .unwrap_or_default()
+    }
+
+    /// Like [`load`][Self::load], but returns an error instead of outputting a warning.
+    #[track_caller]
+    pub fn try_load(path: impl AsRef<Path>, hotspot: Origin) -> Result<Self, TextureLoadError> {
+        let caller = Location::caller();
+        let path = path.as_ref();
+        crate::assets::record(path);
+        let cursor = || {
+            let image = Reader::open(path)?.decode()?.into_rgba8();
+            let (width, height) = image.dimensions();
+            Ok::<_, TextureLoadErrorKind>(Self::try_from_rgba8(width, height, &image, hotspot)?)
+        };
+        cursor().map_err(|kind| TextureLoadError::new(kind, Some(path), caller))
+    }
+
+    /// Creates a cursor from raw pixels, with 4 bytes per pixel in RGBA order, row by row.
+    ///
+    /// # Panics
+    ///
+    /// If `pixels` doesn't have exactly `width * height * 4` bytes.
+    #[must_use]
+    pub fn from_rgba8(width: u32, height: u32, pixels: &[u8], hotspot: Origin) -> Self {
+        Self::try_from_rgba8(width, height, pixels, hotspot)
+            .inspect_err(|e| log::error!("Failed to create cursor: {e}"))
+            .unwrap_or_default()
+    }
+
+    fn try_from_rgba8(
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+        hotspot: Origin,
+    ) -> Result<Self, SdlError> {
+        // SDL takes sizes as i32
+        let too_large = || SdlError(format!("{width}x{height} pixels is too large for a cursor"));
+        let pitch = (width as usize)
+            .checked_mul(4)
+            .filter(|&pitch| i32::try_from(pitch).is_ok() && i32::try_from(height).is_ok())
+            .ok_or_else(too_large)?;
+        let len = pitch.checked_mul(height as usize).ok_or_else(too_large)?;
+        assert_eq!(pixels.len(), len, "wrong size for {width}x{height} pixels");
+
+        // The surface is only read from, when the cursor is created
+        let surface = unsafe {
+            SDL_CreateRGBSurfaceWithFormatFrom(
+                pixels.as_ptr().cast_mut().cast(),
+                width as i32,
+                height as i32,
+                /* unused */ 0,
+                pitch as i32,
+                sdl2_sys::SDL_PixelFormatEnum::SDL_PIXELFORMAT_RGBA32 as u32,
+            )
+        };
+        if surface.is_null() {
+            return Err(SdlError::from_sdl());
+        }
+
+        let x = (hotspot.0.x * width as f32).min(width.saturating_sub(1) as f32);
+        let y = (hotspot.0.y * height as f32).min(height.saturating_sub(1) as f32);
+        let cursor = unsafe { SDL_CreateColorCursor(surface, x as i32, y as i32) };
+        unsafe { SDL_FreeSurface(surface) };
+
+        let ptr = NonNull::new(cursor).ok_or_else(SdlError::from_sdl)?;
+        Ok(Self {
+            data: Some(Rc::new(CursorData(ptr))),
+        })
+    }
+
+    fn as_ptr(&self) -> *mut SDL_Cursor {
+        self.data
+            .as_ref()
+            .map_or_else(|| unsafe { SDL_GetDefaultCursor() }, |data| data.0.as_ptr())
+    }
+}
+
+thread_local! {
+    // SDL doesn't keep the cursor alive, so it's kept here while it's in use
+    static CURSOR: RefCell<Cursor> = const { RefCell::new(Cursor::arrow()) };
+}
+
+/// Changes the mouse cursor shown over the window.
+pub fn set_cursor(cursor: &Cursor) {
+    unsafe { SDL_SetCursor(cursor.as_ptr()) };
+    CURSOR.set(cursor.clone());
+}
+
+/// Shows the mouse cursor over the window. It's shown by default.
+pub fn show_cursor() {
+    unsafe { SDL_ShowCursor(sdl2_sys::SDL_ENABLE as i32) };
+}
+
+/// Hides the mouse cursor over the window. The mouse still moves, and can still click.
+pub fn hide_cursor() {
+    unsafe { SDL_ShowCursor(sdl2_sys::SDL_DISABLE as i32) };
+}
+
+/// Is the mouse cursor shown over the window?
+#[must_use]
+pub fn is_cursor_visible() -> bool {
+    unsafe { SDL_ShowCursor(sdl2_sys::SDL_QUERY) == sdl2_sys::SDL_ENABLE as i32 }
+}
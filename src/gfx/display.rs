@@ -0,0 +1,119 @@
+use std::ffi::CStr;
+use std::mem::MaybeUninit;
+
+use sdl2_sys::{
+    SDL_GetDisplayBounds, SDL_GetDisplayDPI, SDL_GetDisplayMode, SDL_GetDisplayName,
+    SDL_GetDisplayUsableBounds, SDL_GetNumDisplayModes, SDL_GetNumVideoDisplays, SDL_Rect,
+};
+
+use crate::SdlError;
+
+/// A monitor connected to the computer, found with [`displays`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Display {
+    /// Index of the display, for [`WindowPosition::CenteredOn`][super::WindowPosition].
+    pub index: u32,
+    /// Name of the display, if the system knows it.
+    pub name: String,
+    /// Position of the display's top left corner, across all displays.
+    pub position: (i32, i32),
+    /// Size of the display, in the same units as window sizes.
+    pub size: (u32, u32),
+    /// Position of the part of the display which isn't covered by things like task bars.
+    pub usable_position: (i32, i32),
+    /// Size of the part of the display which isn't covered by things like task bars.
+    pub usable_size: (u32, u32),
+    /// Dots per inch, diagonally. This is `None` if the system doesn't know it.
+    pub dpi: Option<f32>,
+    /// Every mode the display supports, from largest to smallest.
+    pub modes: Vec<VideoMode>,
+}
+
+/// A resolution and refresh rate a [`Display`] supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoMode {
+    /// Width, in pixels.
+    pub width: u32,
+    /// Height, in pixels.
+    pub height: u32,
+    /// Refresh rate, or 0 if it isn't known.
+    pub refresh: u32,
+}
+
+/// Every display connected to the computer, with the main one first. This can only be called once
+/// the game is running.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// for display in gfx::displays() {
+///     let (width, height) = display.size;
+///     println!("{}: {width}x{height}", display.name);
+///     for mode in &display.modes {
+///         println!("  {}x{} @{}Hz", mode.width, mode.height, mode.refresh);
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn displays() -> Vec<Display> {
+    let count = unsafe { SDL_GetNumVideoDisplays() };
+    if count < 0 {
+        log::error!("Failed to query displays: {}", SdlError::from_sdl());
+        return Vec::new();
+    }
+    (0..count).map(display).collect()
+}
+
+#[allow(clippy::cast_sign_loss)]
+fn display(index: i32) -> Display {
+    let name = unsafe { SDL_GetDisplayName(index) };
+    let name = if name.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(name) }
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    let bounds = |f: unsafe extern "C" fn(i32, *mut SDL_Rect) -> i32| {
+        let mut rect = SDL_Rect {
+            x: 0,
+            y: 0,
+            w: 0,
+            h: 0,
+        };
+        unsafe { f(index, &raw mut rect) };
+        ((rect.x, rect.y), (rect.w as u32, rect.h as u32))
+    };
+    let (position, size) = bounds(SDL_GetDisplayBounds);
+    let (usable_position, usable_size) = bounds(SDL_GetDisplayUsableBounds);
+
+    let (mut diagonal, null) = (0., std::ptr::null_mut());
+    let found = unsafe { SDL_GetDisplayDPI(index, &raw mut diagonal, null, null) } == 0;
+    let dpi = found.then_some(diagonal);
+
+    let modes = (0..unsafe { SDL_GetNumDisplayModes(index) })
+        .filter_map(|i| {
+            let mut mode = MaybeUninit::zeroed();
+            if unsafe { SDL_GetDisplayMode(index, i, mode.as_mut_ptr()) } < 0 {
+                return None;
+            }
+            let mode = unsafe { mode.assume_init() };
+            Some(VideoMode {
+                width: mode.w as u32,
+                height: mode.h as u32,
+                refresh: mode.refresh_rate as u32,
+            })
+        })
+        .collect();
+
+    Display {
+        index: index as u32,
+        name,
+        position,
+        size,
+        usable_position,
+        usable_size,
+        dpi,
+        modes,
+    }
+}
@@ -0,0 +1,85 @@
+use super::{with_canvas, Color};
+
+/// A color adjustment for the whole screen, like the light changing through the day.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// gfx::set_color_grade(Grade::night());
+///
+/// // Or fade into the night, as time goes on
+/// # let hour = 20f32;
+/// let night = ((hour - 18.) / 3.).clamp(0., 1.);
+/// gfx::set_color_grade(Grade::NONE.lerp(&Grade::night(), night));
+/// ```
+///
+/// The grade is applied to everything drawn in a frame, right before it's displayed. For a
+/// setting players can adjust to suit their display, see [`set_gamma`][super::set_gamma].
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grade {
+    /// Every pixel is multiplied by this color. White leaves colors as they are.
+    pub tint: Color,
+    /// Brightens the screen when positive, or darkens it when negative, from -1 to 1.
+    pub brightness: f32,
+}
+
+impl Default for Grade {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl Grade {
+    /// Leaves colors as they are.
+    pub const NONE: Self = Self::new(Color::WHITE);
+
+    /// Creates a grade which tints the screen.
+    pub const fn new(tint: Color) -> Self {
+        Self {
+            tint,
+            brightness: 0.,
+        }
+    }
+
+    /// Sets the brightness of this grade.
+    pub const fn with_brightness(mut self, brightness: f32) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Dark and blue, for nighttime.
+    pub const fn night() -> Self {
+        Self::new(Color::from_rgb(110, 120, 190)).with_brightness(-0.2)
+    }
+
+    /// Warm and orange, for sunsets.
+    pub const fn dusk() -> Self {
+        Self::new(Color::from_rgb(255, 190, 150))
+    }
+
+    /// Blends between two grades, by `t` from 0 to 1.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            tint: self.tint.lerp_to_gamma(other.tint, t),
+            brightness: self.brightness + (other.brightness - self.brightness) * t,
+        }
+    }
+
+    /// Is this grade doing nothing?
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+}
+
+/// Sets the color grade for the screen. Use [`Grade::NONE`] to go back to normal.
+///
+/// Contrast and lookup tables would need shaders, which aren't supported yet.
+pub fn set_color_grade(grade: Grade) {
+    with_canvas(|canvas| canvas.set_color_grade(grade));
+}
+
+/// The color grade set with [`set_color_grade`].
+pub fn color_grade() -> Grade {
+    with_canvas(|canvas| canvas.color_grade())
+}
@@ -0,0 +1,86 @@
+use glam::{vec2, Vec2};
+
+use super::{Canvas, Color, Drawable, Texture, Transform, Vertex};
+
+/// A texture stretched to any size without stretching its borders, for frames and panels.
+///
+/// The texture is cut into a 3 by 3 grid. The corners are drawn as they are, the edges are
+/// stretched along one direction, and the middle is stretched to fill the rest.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let frame = Texture::empty();
+/// use baba::gfx::NineSlice;
+///
+/// // The frame has 4 pixel borders
+/// let panel = NineSlice::new(&frame, 4.).with_size(vec2(120., 40.));
+/// gfx::draw(&panel, vec2(10., 10.));
+/// ```
+#[must_use]
+#[derive(Clone)]
+pub struct NineSlice {
+    texture: Texture,
+    border: f32,
+    size: Vec2,
+}
+
+impl NineSlice {
+    /// Creates a nine-slice from a texture, with borders of a width in pixels. It's drawn at the
+    /// size of the texture, until it's [resized][Self::with_size].
+    pub fn new(texture: &Texture, border: f32) -> Self {
+        let size = vec2(texture.width() as f32, texture.height() as f32);
+        Self {
+            texture: texture.clone(),
+            border,
+            size,
+        }
+    }
+
+    /// Sets the size this is drawn at. It's never smaller than its borders.
+    pub const fn with_size(mut self, size: Vec2) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// The size this is drawn at.
+    #[must_use]
+    pub const fn size(&self) -> Vec2 {
+        self.size
+    }
+}
+
+impl Drawable for NineSlice {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        let texture_size = vec2(self.texture.width() as f32, self.texture.height() as f32);
+        if texture_size.min_element() <= 0. {
+            return;
+        }
+        let border = Vec2::splat(self.border).min(texture_size / 2.);
+        let size = self.size.max(border * 2.);
+
+        // Edges of the grid, on the screen and in the texture
+        let xs = [0., border.x, size.x - border.x, size.x];
+        let ys = [0., border.y, size.y - border.y, size.y];
+        let us =
+            [0., border.x, texture_size.x - border.x, texture_size.x].map(|u| u / texture_size.x);
+        let vs =
+            [0., border.y, texture_size.y - border.y, texture_size.y].map(|v| v / texture_size.y);
+
+        let mut verts = [Vertex::new(Vec2::ZERO, Color::WHITE, Vec2::ZERO); 16];
+        for (y, (&py, &v)) in ys.iter().zip(&vs).enumerate() {
+            for (x, (&px, &u)) in xs.iter().zip(&us).enumerate() {
+                let coord = transform.transform_point(vec2(px, py) - self.texture.origin() * size);
+                verts[y * 4 + x] = Vertex::new(coord, self.texture.tint(), vec2(u, v));
+            }
+        }
+
+        let mut indices = [0; 54];
+        for cell in 0..9 {
+            let first = (cell / 3 * 4 + cell % 3) as i32;
+            let quad = [first, first + 1, first + 4, first + 4, first + 1, first + 5];
+            indices[cell * 6..cell * 6 + 6].copy_from_slice(&quad);
+        }
+
+        canvas.draw_geometry(&self.texture, &verts, Some(&indices));
+    }
+}
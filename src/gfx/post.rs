@@ -0,0 +1,242 @@
+use glam::{vec2, Vec2};
+
+use super::{with_canvas, BlendMode, Canvas, Color, Grade, Transform, Vertex};
+
+/// An effect applied to the whole screen after everything is drawn, as part of a
+/// [`PostChain`].
+///
+/// Some effects need shaders, which aren't supported yet. On the plain SDL renderer, those are
+/// skipped, and the rest of the chain still works.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostEffect {
+    /// Makes bright areas glow. This needs shaders, so it's skipped for now.
+    Bloom {
+        /// How bright a pixel has to be to glow, from 0 to 1.
+        threshold: f32,
+        /// How strong the glow is.
+        intensity: f32,
+    },
+    /// Darkens the edges of the screen.
+    Vignette {
+        /// How dark the corners get, from 0 to 1.
+        strength: f32,
+        /// Size of the area in the middle which isn't darkened, as a fraction of the screen.
+        size: f32,
+        /// Color the edges fade to, usually black.
+        color: Color,
+    },
+    /// Adjusts colors, like [`set_color_grade`][super::set_color_grade], but at this point in
+    /// the chain.
+    Grade(Grade),
+    /// Looks like an old CRT monitor.
+    Crt {
+        /// How dark every other line is, from 0 to 1.
+        scanlines: f32,
+        /// How much the screen bulges. This needs shaders, so it's ignored for now.
+        curvature: f32,
+    },
+}
+
+impl PostEffect {
+    /// A vignette with black edges.
+    pub const fn vignette(strength: f32) -> Self {
+        Self::Vignette {
+            strength,
+            size: 0.6,
+            color: Color::BLACK,
+        }
+    }
+
+    fn apply(&self, canvas: &mut Canvas) {
+        let (width, height) = canvas.size();
+        #[allow(clippy::cast_precision_loss)]
+        let size = vec2(width as f32, height as f32);
+        match *self {
+            Self::Bloom { .. } => {}
+            Self::Vignette {
+                strength,
+                size: inner,
+                color,
+            } => draw_vignette(
+                canvas,
+                size,
+                inner,
+                color.gamma_multiply(strength.clamp(0., 1.)),
+            ),
+            Self::Grade(grade) => canvas.apply_grade(grade),
+            Self::Crt { scanlines, .. } => draw_scanlines(canvas, size, scanlines),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    name: String,
+    effect: PostEffect,
+    enabled: bool,
+}
+
+/// Effects applied to the screen in order, after everything is drawn, and before the
+/// [color grade][Grade].
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// use baba::gfx::{PostChain, PostEffect};
+///
+/// gfx::set_post_chain(
+///     PostChain::new()
+///         .with("bloom", PostEffect::Bloom { threshold: 0.8, intensity: 0.5 })
+///         .with("vignette", PostEffect::vignette(0.4))
+///         .with("crt", PostEffect::Crt { scanlines: 0.3, curvature: 0.1 }),
+/// );
+///
+/// // Later, from the options menu
+/// gfx::with_post_chain(|chain| chain.set_enabled("crt", false));
+/// ```
+#[must_use]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PostChain {
+    effects: Vec<Entry>,
+}
+
+impl PostChain {
+    /// Creates a chain with no effects.
+    pub const fn new() -> Self {
+        Self {
+            effects: Vec::new(),
+        }
+    }
+
+    /// Adds an effect at the end of the chain, with a name for changing it later.
+    pub fn with(mut self, name: impl Into<String>, effect: PostEffect) -> Self {
+        self.push(name, effect);
+        self
+    }
+
+    /// Adds an effect at the end of the chain, with a name for changing it later.
+    pub fn push(&mut self, name: impl Into<String>, effect: PostEffect) {
+        self.effects.push(Entry {
+            name: name.into(),
+            effect,
+            enabled: true,
+        });
+    }
+
+    /// Removes an effect, returning it.
+    pub fn remove(&mut self, name: &str) -> Option<PostEffect> {
+        let index = self.effects.iter().position(|e| e.name == name)?;
+        Some(self.effects.remove(index).effect)
+    }
+
+    /// An effect in the chain.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&PostEffect> {
+        self.effects
+            .iter()
+            .find(|e| e.name == name)
+            .map(|e| &e.effect)
+    }
+
+    /// An effect in the chain, for changing its parameters.
+    #[must_use]
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut PostEffect> {
+        let entry = self.effects.iter_mut().find(|e| e.name == name);
+        entry.map(|e| &mut e.effect)
+    }
+
+    /// Turns an effect on or off, keeping its place in the chain.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) {
+        if let Some(entry) = self.effects.iter_mut().find(|e| e.name == name) {
+            entry.enabled = enabled;
+        }
+    }
+
+    /// Is an effect in the chain and turned on?
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.effects.iter().any(|e| e.name == name && e.enabled)
+    }
+
+    /// Does the chain have no effects?
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Applies every effect which is turned on, in screen coordinates.
+    pub(crate) fn apply(&self, canvas: &mut Canvas) {
+        let view = canvas.set_view(Transform::IDENTITY);
+        let tint = canvas.set_tint(Color::WHITE);
+        let blend_mode = canvas.blend_mode();
+        for entry in self.effects.iter().filter(|e| e.enabled) {
+            entry.effect.apply(canvas);
+        }
+        let _ = canvas.set_view(view);
+        canvas.set_tint(tint);
+        canvas.set_blend_mode(blend_mode);
+    }
+}
+
+/// Fades from clear in an inner rectangle to `color` at the edges of the screen.
+fn draw_vignette(canvas: &mut Canvas, size: Vec2, inner: f32, color: Color) {
+    let margin = size * (1. - inner.clamp(0., 1.)) / 2.;
+    let corners = [Vec2::ZERO, vec2(size.x, 0.), size, vec2(0., size.y)];
+    let mut vertices = [Vertex::new(Vec2::ZERO, Color::TRANSPARENT, Vec2::ZERO); 8];
+    for (i, corner) in corners.into_iter().enumerate() {
+        let inward = vec2(
+            if corner.x > 0. { -margin.x } else { margin.x },
+            if corner.y > 0. { -margin.y } else { margin.y },
+        );
+        vertices[i] = Vertex::new(corner, color, Vec2::ZERO);
+        vertices[i + 4] = Vertex::new(corner + inward, Color::TRANSPARENT, Vec2::ZERO);
+    }
+    // A quad between each outer edge and the matching inner edge
+    let mut indices = [0; 24];
+    for (side, quad) in (0..4).zip(indices.chunks_exact_mut(6)) {
+        let next = (side + 1) % 4;
+        quad.copy_from_slice(&[side, next, next + 4, side, next + 4, side + 4]);
+    }
+    canvas.set_blend_mode(Some(BlendMode::PremultipliedAlpha));
+    canvas.draw_colored_geometry(&vertices, Some(&indices));
+}
+
+/// Darkens every other row of pixels.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn draw_scanlines(canvas: &mut Canvas, size: Vec2, strength: f32) {
+    if strength <= 0. {
+        return;
+    }
+    let shade = ((1. - strength.clamp(0., 1.)) * 255.) as u8;
+    let color = Color::from_rgb(shade, shade, shade);
+    let rows = (size.y / 2.).ceil() as i32;
+    let mut vertices = Vec::with_capacity(rows as usize * 4);
+    let mut indices = Vec::with_capacity(rows as usize * 6);
+    for row in 0..rows {
+        #[allow(clippy::cast_precision_loss)]
+        let y = row as f32 * 2. + 1.;
+        let first = row * 4;
+        for corner in [
+            vec2(0., y),
+            vec2(size.x, y),
+            vec2(size.x, y + 1.),
+            vec2(0., y + 1.),
+        ] {
+            vertices.push(Vertex::new(corner, color, Vec2::ZERO));
+        }
+        indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+    }
+    canvas.set_blend_mode(Some(BlendMode::Multiply));
+    canvas.draw_colored_geometry(&vertices, Some(&indices));
+}
+
+/// Sets the effects applied to the screen after everything is drawn.
+pub fn set_post_chain(chain: PostChain) {
+    with_canvas(|canvas| canvas.set_post_chain(chain));
+}
+
+/// Changes the effects applied to the screen, like to adjust their parameters while the game
+/// runs.
+pub fn with_post_chain<T>(f: impl FnOnce(&mut PostChain) -> T) -> T {
+    with_canvas(|canvas| f(canvas.post_chain_mut()))
+}
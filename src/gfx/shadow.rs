@@ -0,0 +1,97 @@
+use glam::{Affine2, Mat2, Vec2};
+
+use super::{draw, with_tint, Color, Drawable, Transform};
+
+/// A drop shadow: a darkened, squashed copy of something, drawn underneath it.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let (tree, position) = (Texture::empty(), Vec2::ZERO);
+/// // Late afternoon sun, coming from the left
+/// let shadow = Shadow::new().with_squash(0.4).with_lean(1.5);
+/// gfx::draw_with_shadow(&tree, &shadow, position);
+/// ```
+///
+/// The shadow is squashed towards the origin of what's drawn, so textures with their origin at
+/// their feet, like `Origin(vec2(0.5, 1.))`, cast shadows along the ground.
+///
+/// For a simple round blob under a character, draw a squashed circle instead:
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let feet = Vec2::ZERO;
+/// let blob = Shape::circle(8.).color(Color::from_black_alpha(80));
+/// gfx::draw(&blob, (feet, vec2(1., 0.4)));
+/// ```
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shadow {
+    /// How far the shadow is moved from what casts it.
+    pub offset: Vec2,
+    /// How tall the shadow is, compared to what casts it. 1 is a copy, and 0 is flat.
+    pub squash: f32,
+    /// How much the shadow leans to the right, for every pixel of height. Negative values lean
+    /// to the left. This follows the direction light is coming from.
+    pub lean: f32,
+    /// The color of the shadow. Everything drawn is multiplied by it.
+    pub color: Color,
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shadow {
+    /// A shadow cast by light from above, slightly offset to the bottom right.
+    pub const fn new() -> Self {
+        Self {
+            offset: Vec2::new(2., 2.),
+            squash: 1.,
+            lean: 0.,
+            color: Color::from_black_alpha(100),
+        }
+    }
+
+    /// Sets how far the shadow is moved from what casts it.
+    pub const fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets how tall the shadow is, compared to what casts it.
+    pub const fn with_squash(mut self, squash: f32) -> Self {
+        self.squash = squash;
+        self
+    }
+
+    /// Sets how much the shadow leans to the right, for every pixel of height.
+    pub const fn with_lean(mut self, lean: f32) -> Self {
+        self.lean = lean;
+        self
+    }
+
+    /// Sets the color of the shadow.
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Where the shadow of something drawn with a transform goes.
+    pub fn transform(&self, transform: Transform) -> Transform {
+        let origin = transform.to_affine().translation;
+        // Heights are negative above the origin, so leaning right moves them left
+        let squash = Mat2::from_cols(Vec2::X, Vec2::new(-self.lean, self.squash));
+        let shadow = Affine2::from_mat2_translation(squash, origin + self.offset)
+            * Affine2::from_translation(-origin);
+        Transform::from_affine(shadow) * transform
+    }
+}
+
+/// Draws an object with a [`Shadow`] underneath it.
+pub fn draw_with_shadow<T: Drawable>(object: &T, shadow: &Shadow, transform: impl Into<Transform>) {
+    let transform = transform.into();
+    with_tint(shadow.color, || draw(object, shadow.transform(transform)));
+    draw(object, transform);
+}
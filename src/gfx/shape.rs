@@ -0,0 +1,156 @@
+use glam::{vec2, Vec2};
+
+use crate::alloc::with_frame_arena;
+use crate::math::TAU;
+
+use super::{Canvas, Color, Drawable, Transform, Vertex};
+
+/// A simple shape, drawn with a solid color.
+///
+/// Shapes are filled by default, use [`outline`][Shape::outline] to only draw their borders.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// // A red square at 40, 10
+/// gfx::draw(&Shape::rect(vec2(16., 16.)).color(Color::RED), vec2(40., 10.));
+/// // A hollow circle, rotated along with the transform
+/// gfx::draw(&Shape::circle(8.).outline(2.), (vec2(40., 10.), vec2(2., 1.), degrees(45.)));
+/// ```
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub struct Shape {
+    kind: Kind,
+    color: Color,
+    outline: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Rect(Vec2),
+    Circle(f32),
+    Line(Vec2, Vec2, f32),
+}
+
+impl Shape {
+    /// A rectangle with its top-left corner at the origin.
+    pub const fn rect(size: Vec2) -> Self {
+        Self::new(Kind::Rect(size))
+    }
+
+    /// A circle centered on the origin.
+    pub const fn circle(radius: f32) -> Self {
+        Self::new(Kind::Circle(radius))
+    }
+
+    /// A line between two points.
+    pub const fn line(from: Vec2, to: Vec2, thickness: f32) -> Self {
+        Self::new(Kind::Line(from, to, thickness))
+    }
+
+    const fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            color: Color::WHITE,
+            outline: None,
+        }
+    }
+
+    /// Sets the color of this shape. Defaults to white.
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Only draws the border of this shape, with some thickness. The border is drawn inside of
+    /// the shape.
+    ///
+    /// This does nothing for lines.
+    pub const fn outline(mut self, thickness: f32) -> Self {
+        self.outline = Some(thickness);
+        self
+    }
+}
+
+const QUAD_IDX: [i32; 6] = [0, 1, 2, 2, 1, 3];
+// Outer corners are 0..4, inner corners are 4..8, both clockwise
+const FRAME_IDX: [i32; 24] = [
+    0, 1, 4, 4, 1, 5, 1, 2, 5, 5, 2, 6, 2, 3, 6, 6, 3, 7, 3, 0, 7, 7, 0, 4,
+];
+
+impl Drawable for Shape {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        let vertex = |p| Vertex::from_xy_color(transform.transform_point(p), self.color);
+
+        match (self.kind, self.outline) {
+            (Kind::Rect(size), None) => {
+                let verts = [Vec2::ZERO, vec2(size.x, 0.), vec2(0., size.y), size].map(vertex);
+                canvas.draw_colored_geometry(&verts, Some(&QUAD_IDX));
+            }
+            (Kind::Rect(size), Some(thickness)) => {
+                let t = Vec2::splat(thickness.min(size.x / 2.).min(size.y / 2.));
+                let verts = [
+                    Vec2::ZERO,
+                    vec2(size.x, 0.),
+                    size,
+                    vec2(0., size.y),
+                    t,
+                    vec2(size.x - t.x, t.y),
+                    size - t,
+                    vec2(t.x, size.y - t.y),
+                ]
+                .map(vertex);
+                canvas.draw_colored_geometry(&verts, Some(&FRAME_IDX));
+            }
+            (Kind::Line(from, to, thickness), _) => {
+                let normal = (to - from).perp().normalize_or_zero() * thickness / 2.;
+                let verts = [from + normal, to + normal, from - normal, to - normal].map(vertex);
+                canvas.draw_colored_geometry(&verts, Some(&QUAD_IDX));
+            }
+            (Kind::Circle(radius), None) => {
+                let segments = circle_segments(radius, &transform);
+                with_frame_arena(|arena| {
+                    // The last vertex is the center
+                    let verts = arena.alloc_slice_fill(segments + 1, vertex(Vec2::ZERO));
+                    let indices = arena.alloc_slice_fill(segments * 3, segments as i32);
+                    for i in 0..segments {
+                        verts[i] = vertex(circle_point(i, segments) * radius);
+                        indices[i * 3] = i as i32;
+                        indices[i * 3 + 1] = ((i + 1) % segments) as i32;
+                    }
+
+                    canvas.draw_colored_geometry(verts, Some(indices));
+                });
+            }
+            (Kind::Circle(radius), Some(thickness)) => {
+                let segments = circle_segments(radius, &transform);
+                let inner = (radius - thickness).max(0.);
+                with_frame_arena(|arena| {
+                    // Outer and inner vertices are interleaved
+                    let verts = arena.alloc_slice_fill(segments * 2, vertex(Vec2::ZERO));
+                    let indices = arena.alloc_slice_fill(segments * 6, 0);
+                    for i in 0..segments {
+                        let dir = circle_point(i, segments);
+                        verts[i * 2] = vertex(dir * radius);
+                        verts[i * 2 + 1] = vertex(dir * inner);
+
+                        let (a, b) = (i as i32 * 2, ((i + 1) % segments) as i32 * 2);
+                        indices[i * 6..i * 6 + 6].copy_from_slice(&[a, b, a + 1, a + 1, b, b + 1]);
+                    }
+
+                    canvas.draw_colored_geometry(verts, Some(indices));
+                });
+            }
+        }
+    }
+}
+
+fn circle_point(i: usize, segments: usize) -> Vec2 {
+    Vec2::from_angle(i as f32 / segments as f32 * TAU)
+}
+
+/// Picks how many segments a circle needs to look smooth, considering how much it's scaled.
+#[allow(clippy::cast_sign_loss)]
+fn circle_segments(radius: f32, transform: &Transform) -> usize {
+    let scale = transform.to_affine().matrix2.determinant().abs().sqrt();
+    ((radius * scale).sqrt() * 6.).clamp(12., 256.) as usize
+}
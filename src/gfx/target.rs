@@ -0,0 +1,103 @@
+use sdl2_sys::SDL_Texture;
+
+use crate::SdlError;
+
+use super::{with_canvas, Canvas, Drawable, Texture, TextureOptions, Transform, CANVAS};
+
+/// A texture which can be drawn onto, instead of the screen.
+///
+/// Draw to it with [`with_target`], then draw it like any other texture. This is useful for
+/// drawing a scene at a low resolution and scaling it up, or for post-processing.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// let target = RenderTarget::new(320, 180);
+///
+/// gfx::with_target(&target, || {
+///     gfx::clear(Color::BLACK);
+///     gfx::draw_circle(16., Color::RED, vec2(160., 90.));
+/// });
+/// gfx::draw(&target, (Vec2::ZERO, vec2(4., 4.)));
+/// ```
+#[must_use]
+#[derive(Clone)]
+pub struct RenderTarget {
+    texture: Texture,
+}
+
+impl RenderTarget {
+    /// Creates a render target with a size in pixels. It's initially transparent.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::new_with(width, height, TextureOptions::default())
+    }
+
+    /// Creates a render target with custom texture options.
+    pub fn new_with(width: u32, height: u32, options: impl Into<TextureOptions>) -> Self {
+        Self::try_new(width, height, options)
+            .inspect_err(|e| log::error!("Failed to create render target: {e}"))
+            .unwrap_or_else(|_| Self {
+                texture: Texture::empty(),
+            })
+    }
+
+    /// Like [`new_with`][Self::new_with], but returns an error instead of outputting a warning.
+    pub fn try_new(
+        width: u32,
+        height: u32,
+        options: impl Into<TextureOptions>,
+    ) -> Result<Self, SdlError> {
+        let texture = Texture::new_target(width, height, options)?;
+        let target = Self { texture };
+        with_target(&target, || super::clear(super::Color::TRANSPARENT));
+        Ok(target)
+    }
+
+    /// The texture being drawn to.
+    pub const fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// The width of this target.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.texture.width()
+    }
+
+    /// The height of this target.
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.texture.height()
+    }
+}
+
+impl Drawable for RenderTarget {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        self.texture.draw(canvas, transform);
+    }
+}
+
+/// Draws onto a [`RenderTarget`] instead of the screen, for the duration of `f`.
+///
+/// Calls can be nested; the previous target is restored afterwards. Inside of `f`, coordinates
+/// are in the target's pixels, and the [viewport][super::Viewport] doesn't apply.
+pub fn with_target<T>(target: &RenderTarget, f: impl FnOnce() -> T) -> T {
+    let previous = with_canvas(|canvas| canvas.set_target(target.texture.raw()));
+    let _restore = RestoreTarget(previous);
+    f()
+}
+
+/// Restores the previous target when dropped, so it's restored even if drawing panics.
+struct RestoreTarget(*mut SDL_Texture);
+
+impl Drop for RestoreTarget {
+    fn drop(&mut self) {
+        let _ = CANVAS.try_with(|canvas| {
+            // If drawing panicked, the canvas may still be borrowed
+            if let Ok(mut canvas) = canvas.try_borrow_mut() {
+                if let Some(canvas) = canvas.as_mut() {
+                    canvas.set_target(self.0);
+                }
+            }
+        });
+    }
+}
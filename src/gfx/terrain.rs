@@ -0,0 +1,271 @@
+use std::cell::Cell;
+
+use glam::Vec2;
+
+use crate::alloc::with_frame_arena;
+use crate::math::Rect;
+
+use super::{Canvas, Color, Drawable, Texture, Transform};
+
+/// Terrain made of pixels, which can be blown apart and built up again, like in Worms.
+///
+/// Every pixel with any opacity is solid. Changes are drawn right away, only re-uploading the
+/// part of the texture that changed.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let (pixels, explosion, player) = (vec![0; 640 * 360 * 4], Vec2::ZERO, Vec2::ZERO);
+/// let mut terrain = PixelTerrain::from_rgba8(640, 360, &pixels);
+///
+/// // Something blew up
+/// terrain.carve_circle(explosion, 24.);
+///
+/// // Is the player standing on something?
+/// let grounded = terrain.is_solid_at(player + vec2(0., 1.));
+///
+/// gfx::draw(&terrain, Vec2::ZERO);
+/// ```
+///
+/// Positions are in pixels of the terrain, from its top left corner.
+pub struct PixelTerrain {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    solid: Vec<u64>,
+    texture: Texture,
+    dirty: Cell<Option<Rect>>,
+}
+
+impl PixelTerrain {
+    /// Creates terrain from RGBA pixels, row by row. Transparent pixels are empty.
+    ///
+    /// # Panics
+    ///
+    /// If `pixels` doesn't have exactly `width * height * 4` bytes.
+    ///
+    /// If the terrain is too large for a texture on this GPU, the error is logged, and it still
+    /// works for collisions, but isn't drawn.
+    #[must_use]
+    pub fn from_rgba8(width: u32, height: u32, pixels: &[u8]) -> Self {
+        assert_eq!(
+            pixels.len(),
+            byte_len(width, height),
+            "wrong size for {width}x{height} pixels"
+        );
+
+        let mut solid = vec![0; (pixels.len() / 4).div_ceil(64)];
+        for (i, pixel) in pixels.chunks_exact(4).enumerate() {
+            if pixel[3] != 0 {
+                solid[i / 64] |= 1 << (i % 64);
+            }
+        }
+
+        let terrain = Self {
+            width,
+            height,
+            pixels: pixels.to_vec(),
+            solid,
+            texture: Texture::new_streaming(width, height),
+            dirty: Cell::new(None),
+        };
+        if terrain.has_texture() {
+            terrain.texture.update(None, pixels);
+        }
+        terrain
+    }
+
+    /// Creates empty terrain, to be filled in with [`fill_circle`][Self::fill_circle] and
+    /// [`fill_polygon`][Self::fill_polygon].
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::from_rgba8(width, height, &vec![0; byte_len(width, height)])
+    }
+
+    /// The width of the terrain, in pixels.
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the terrain, in pixels.
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Is the pixel at this position solid? Everything outside of the terrain is empty.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn is_solid(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return false;
+        }
+        let i = self.index(x as u32, y as u32);
+        self.solid[i / 64] & 1 << (i % 64) != 0
+    }
+
+    /// Is the pixel under a point solid?
+    #[must_use]
+    pub fn is_solid_at(&self, point: Vec2) -> bool {
+        let point = point.floor();
+        self.is_solid(point.x as i32, point.y as i32)
+    }
+
+    /// Does any solid pixel touch a circle?
+    #[must_use]
+    pub fn overlaps_circle(&self, center: Vec2, radius: f32) -> bool {
+        let mut hit = false;
+        self.for_circle(center, radius, |x, y| hit |= self.is_solid(x, y));
+        hit
+    }
+
+    /// Removes every pixel inside of a circle.
+    pub fn carve_circle(&mut self, center: Vec2, radius: f32) {
+        self.set_circle(center, radius, None);
+    }
+
+    /// Removes every pixel inside of a polygon.
+    pub fn carve_polygon(&mut self, points: &[Vec2]) {
+        self.set_polygon(points, None);
+    }
+
+    /// Makes every pixel inside of a circle solid, with a color.
+    pub fn fill_circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        self.set_circle(center, radius, Some(color));
+    }
+
+    /// Makes every pixel inside of a polygon solid, with a color.
+    pub fn fill_polygon(&mut self, points: &[Vec2], color: Color) {
+        self.set_polygon(points, Some(color));
+    }
+
+    /// Calls `f` with every pixel whose center is inside of a circle.
+    fn for_circle(&self, center: Vec2, radius: f32, mut f: impl FnMut(i32, i32)) {
+        let (top, bottom) = (center.y - radius, center.y + radius);
+        for y in self.rows(top, bottom) {
+            let dy = y as f32 + 0.5 - center.y;
+            let half = (radius * radius - dy * dy).max(0.).sqrt();
+            for x in self.columns(center.x - half, center.x + half) {
+                f(x, y);
+            }
+        }
+    }
+
+    fn set_circle(&mut self, center: Vec2, radius: f32, color: Option<Color>) {
+        let mut changed = Vec::new();
+        self.for_circle(center, radius, |x, y| changed.push((x, y)));
+        for (x, y) in changed {
+            self.set(x, y, color);
+        }
+    }
+
+    /// Fills a polygon with the even-odd rule, one row at a time.
+    fn set_polygon(&mut self, points: &[Vec2], color: Option<Color>) {
+        if points.len() < 3 {
+            return;
+        }
+        let top = points.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+        let bottom = points.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+
+        let mut crossings = Vec::new();
+        for y in self.rows(top, bottom) {
+            let center = y as f32 + 0.5;
+            crossings.clear();
+            for (i, &a) in points.iter().enumerate() {
+                let b = points[(i + 1) % points.len()];
+                if (a.y <= center) != (b.y <= center) {
+                    crossings.push(a.x + (center - a.y) / (b.y - a.y) * (b.x - a.x));
+                }
+            }
+            crossings.sort_by(f32::total_cmp);
+            for span in crossings.chunks_exact(2) {
+                for x in self.columns(span[0], span[1]) {
+                    self.set(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Rows of pixels whose centers are between two heights, clamped to the terrain.
+    fn rows(&self, top: f32, bottom: f32) -> std::ops::Range<i32> {
+        let top = (top - 0.5).ceil().max(0.) as i32;
+        let bottom = ((bottom - 0.5).floor() + 1.).min(self.height as f32) as i32;
+        top..bottom.max(top)
+    }
+
+    /// Columns of pixels whose centers are between two positions, clamped to the terrain.
+    fn columns(&self, left: f32, right: f32) -> std::ops::Range<i32> {
+        let left = (left - 0.5).ceil().max(0.) as i32;
+        let right = ((right - 0.5).floor() + 1.).min(self.width as f32) as i32;
+        left..right.max(left)
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn set(&mut self, x: i32, y: i32, color: Option<Color>) {
+        let (x, y) = (x as u32, y as u32);
+        let i = self.index(x, y);
+        let rgba = color.map_or([0; 4], |c| c.to_srgba_unmultiplied());
+        self.pixels[i * 4..i * 4 + 4].copy_from_slice(&rgba);
+        if rgba[3] == 0 {
+            self.solid[i / 64] &= !(1 << (i % 64));
+        } else {
+            self.solid[i / 64] |= 1 << (i % 64);
+        }
+
+        let dirty = match self.dirty.get_mut().take() {
+            None => Rect::new(x, y, 1, 1),
+            Some(r) => {
+                let (left, top) = (r.x.min(x), r.y.min(y));
+                let (right, bottom) = ((r.x + r.w).max(x + 1), (r.y + r.h).max(y + 1));
+                Rect::new(left, top, right - left, bottom - top)
+            }
+        };
+        self.dirty.set(Some(dirty));
+    }
+
+    /// Index of a pixel, which must be inside of the terrain.
+    const fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// Was the texture created? Creating it fails if the GPU doesn't support textures this
+    /// large, leaving an empty one.
+    fn has_texture(&self) -> bool {
+        self.texture.width() == self.width && self.texture.height() == self.height
+    }
+
+    /// Uploads the pixels which changed since the last time.
+    fn flush(&self) {
+        let Some(rect) = self.dirty.take().filter(|_| self.has_texture()) else {
+            return;
+        };
+        with_frame_arena(|arena| {
+            let row = (rect.w * 4) as usize;
+            let pixels = arena.alloc_slice_fill(row * rect.h as usize, 0u8);
+            for (y, dst) in (rect.y..rect.y + rect.h).zip(pixels.chunks_exact_mut(row)) {
+                let start = self.index(rect.x, y) * 4;
+                dst.copy_from_slice(&self.pixels[start..start + row]);
+            }
+            self.texture.update(Some(&rect), pixels);
+        });
+    }
+}
+
+impl Drawable for PixelTerrain {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        self.flush();
+        self.texture.draw(canvas, transform);
+    }
+}
+
+/// Bytes of RGBA pixels in terrain of this size.
+///
+/// # Panics
+///
+/// If the size overflows.
+fn byte_len(width: u32, height: u32) -> usize {
+    (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .expect("terrain is too large")
+}
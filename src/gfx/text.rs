@@ -0,0 +1,501 @@
+use std::collections::HashMap;
+use std::panic::Location;
+use std::path::Path;
+use std::rc::Rc;
+
+use glam::{vec2, Vec2};
+use sdl2::pixels::PixelFormatEnum;
+use sdl2_sys::ttf::{
+    TTF_CloseFont, TTF_FontHeight, TTF_FontLineSkip, TTF_GlyphIsProvided, TTF_GlyphMetrics,
+    TTF_Init, TTF_OpenFontRW, TTF_RenderGlyph_Blended, TTF_WasInit,
+};
+use sdl2_sys::{
+    SDL_BlendMode, SDL_Color, SDL_CreateRGBSurfaceWithFormat, SDL_FreeSurface, SDL_RWFromConstMem,
+    SDL_Rect, SDL_SetSurfaceBlendMode, SDL_Surface, SDL_UpperBlit,
+};
+use thiserror::Error;
+
+use crate::alloc::with_frame_arena;
+use crate::SdlError;
+
+use super::{draw, Canvas, Color, Drawable, Texture, TextureOptions, Transform, Vertex};
+
+/// Font load error.
+pub type LoadError = crate::LoadError<LoadErrorKind>;
+
+/// What went wrong while loading a font.
+#[derive(Debug, Error)]
+pub enum LoadErrorKind {
+    /// This font couldn't be opened.
+    #[error("couldn't read file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The font couldn't be read, or the glyphs couldn't be rendered.
+    #[error("couldn't read font: {0}")]
+    Font(#[from] SdlError),
+}
+
+/// Width of the glyph atlas. It grows vertically to fit all glyphs.
+const ATLAS_WIDTH: i32 = 512;
+
+/// Characters rendered by [`Font::load`]: printable ASCII and Latin-1.
+const DEFAULT_CHARS: [std::ops::RangeInclusive<char>; 2] = [' '..='~', '\u{a0}'..='ÿ'];
+
+#[derive(Clone, Copy)]
+struct Glyph {
+    x: f32,
+    y: f32,
+    w: f32,
+    offset: f32,
+    advance: f32,
+}
+
+struct FontData {
+    texture: Texture,
+    glyphs: HashMap<char, Glyph>,
+    height: f32,
+    line_height: f32,
+}
+
+/// A font, rendered at a fixed size.
+///
+/// Glyphs are rendered once into a texture when the font is loaded, so drawing text is as fast as
+/// drawing sprites.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// let font = Font::load("resources/font.ttf", 16.);
+/// gfx::draw_text(&font, "Hello, world!", vec2(10., 10.));
+/// ```
+#[must_use]
+#[derive(Clone)]
+pub struct Font {
+    data: Rc<FontData>,
+}
+
+impl Font {
+    /// Creates an empty font, which draws nothing. This is a placeholder value.
+    pub fn empty() -> Self {
+        let data = Rc::new(FontData {
+            texture: Texture::empty(),
+            glyphs: HashMap::new(),
+            height: 0.,
+            line_height: 0.,
+        });
+        Self { data }
+    }
+
+    /// Loads a TTF or OTF font with a size in points.
+    ///
+    /// Only ASCII and Latin-1 characters are available. Use [`load_with_chars`][Self::load_with_chars]
+    /// if you need more.
+    #[track_caller]
+    pub fn load(path: impl AsRef<Path>, size: f32) -> Self {
+        Self::load_with_chars(path, size, DEFAULT_CHARS.into_iter().flatten())
+    }
+
+    /// Loads a font, rendering a specific set of characters.
+    #[track_caller]
+    pub fn load_with_chars(
+        path: impl AsRef<Path>,
+        size: f32,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Self {
+        Self::try_load(path, size, chars)
+            .inspect_err(|e| log::error!("Failed to load {e}"))
+            .unwrap_or_else(|_| Self::empty())
+    }
+
+    /// Like [`load_with_chars`][Self::load_with_chars], but returns an error instead of
+    /// outputting a warning.
+    #[track_caller]
+    pub fn try_load(
+        path: impl AsRef<Path>,
+        size: f32,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<Self, LoadError> {
+        let caller = Location::caller();
+        let path = path.as_ref();
+        crate::assets::record(path);
+
+        std::fs::read(path)
+            .map_err(LoadErrorKind::from)
+            .and_then(|bytes| Self::from_bytes_with(&bytes, size, chars))
+            .map_err(|kind| LoadError::new(kind, Some(path), caller))
+    }
+
+    /// Loads a font from memory.
+    #[track_caller]
+    pub fn from_bytes(
+        bytes: &[u8],
+        size: f32,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<Self, LoadError> {
+        let caller = Location::caller();
+        Self::from_bytes_with(bytes, size, chars).map_err(|kind| LoadError::new(kind, None, caller))
+    }
+
+    fn from_bytes_with(
+        bytes: &[u8],
+        size: f32,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<Self, LoadErrorKind> {
+        if unsafe { TTF_WasInit() } == 0 && unsafe { TTF_Init() } < 0 {
+            return Err(SdlError::from_sdl())?;
+        }
+
+        let rw = unsafe { SDL_RWFromConstMem(bytes.as_ptr().cast(), bytes.len() as i32) };
+        let font = unsafe { TTF_OpenFontRW(rw, 1, size.round() as i32) };
+        if font.is_null() {
+            return Err(SdlError::from_sdl())?;
+        }
+
+        let height = unsafe { TTF_FontHeight(font) };
+        let line_height = unsafe { TTF_FontLineSkip(font) };
+        let white = SDL_Color {
+            r: 255,
+            g: 255,
+            b: 255,
+            a: 255,
+        };
+
+        // Render every glyph, and pack them into rows
+        let mut surfaces = Vec::new();
+        let mut glyphs = HashMap::new();
+        let (mut x, mut y) = (0, 0);
+        for ch in chars {
+            // SDL_ttf only supports the basic multilingual plane
+            let Ok(code) = u16::try_from(u32::from(ch)) else {
+                continue;
+            };
+            if glyphs.contains_key(&ch) || unsafe { TTF_GlyphIsProvided(font, code) } == 0 {
+                continue;
+            }
+
+            let (mut minx, mut maxx, mut advance) = (0, 0, 0);
+            let null = std::ptr::null_mut();
+            unsafe {
+                TTF_GlyphMetrics(
+                    font,
+                    code,
+                    &raw mut minx,
+                    &raw mut maxx,
+                    null,
+                    null,
+                    &raw mut advance,
+                )
+            };
+
+            let surface = unsafe { TTF_RenderGlyph_Blended(font, code, white) };
+            if surface.is_null() {
+                log::warn!("Failed to render {ch:?}: {}", SdlError::from_sdl());
+                continue;
+            }
+
+            let w = unsafe { (*surface).w };
+            // Older versions of SDL_ttf render only the glyph's box, without its bearing
+            let offset = if w == maxx - minx { minx } else { 0 };
+            if x + w > ATLAS_WIDTH {
+                x = 0;
+                y += height;
+            }
+
+            surfaces.push((surface, x, y));
+            glyphs.insert(
+                ch,
+                Glyph {
+                    x: x as f32,
+                    y: y as f32,
+                    w: w as f32,
+                    offset: offset as f32,
+                    advance: advance as f32,
+                },
+            );
+            x += w;
+        }
+        unsafe { TTF_CloseFont(font) };
+
+        let texture = unsafe { pack_atlas(&surfaces, y + height) };
+        for (surface, _, _) in surfaces {
+            unsafe { SDL_FreeSurface(surface) };
+        }
+
+        let data = Rc::new(FontData {
+            texture: texture?,
+            glyphs,
+            height: height as f32,
+            line_height: line_height as f32,
+        });
+        Ok(Self { data })
+    }
+
+    /// Creates a font from 8x8 glyphs, one byte per row with the lowest bit on the left, for
+    /// consecutive characters starting at `first`.
+    pub(crate) fn from_bitmap(first: char, bitmap: &[[u8; 8]], line_height: f32) -> Self {
+        const PER_ROW: usize = 16;
+
+        let width = PER_ROW * 8;
+        let height = bitmap.len().div_ceil(PER_ROW) * 8;
+        let mut pixels = vec![0; width * height * 4];
+        let mut glyphs = HashMap::new();
+        for ((i, rows), ch) in bitmap.iter().enumerate().zip(first..) {
+            let (x, y) = (i % PER_ROW * 8, i / PER_ROW * 8);
+            for (dy, row) in rows.iter().enumerate() {
+                for dx in (0..8).filter(|dx| row >> dx & 1 != 0) {
+                    let start = ((y + dy) * width + x + dx) * 4;
+                    pixels[start..start + 4].fill(255);
+                }
+            }
+            let glyph = Glyph {
+                x: x as f32,
+                y: y as f32,
+                w: 8.,
+                offset: 0.,
+                advance: 8.,
+            };
+            glyphs.insert(ch, glyph);
+        }
+
+        let data = Rc::new(FontData {
+            texture: Texture::from_rgba8(width as u32, height as u32, &pixels),
+            glyphs,
+            height: 8.,
+            line_height,
+        });
+        Self { data }
+    }
+
+    /// Height of a line of text.
+    #[must_use]
+    pub fn line_height(&self) -> f32 {
+        self.data.line_height
+    }
+
+    /// Measures the size of some text, when drawn with this font.
+    #[must_use]
+    pub fn measure(&self, text: &str) -> Vec2 {
+        let mut size = Vec2::ZERO;
+        for (i, line) in text.lines().enumerate() {
+            let width = line
+                .chars()
+                .filter_map(|ch| self.glyph(ch))
+                .map(|g| g.advance)
+                .sum();
+            size.x = size.x.max(width);
+            size.y = i as f32 * self.data.line_height + self.data.height;
+        }
+        size
+    }
+
+    /// The texture with all of the glyphs in this font.
+    pub fn texture(&self) -> &Texture {
+        &self.data.texture
+    }
+
+    fn glyph(&self, ch: char) -> Option<&Glyph> {
+        let glyphs = &self.data.glyphs;
+        glyphs.get(&ch).or_else(|| glyphs.get(&'?'))
+    }
+}
+
+/// Copies glyphs into a single texture.
+///
+/// # Safety
+///
+/// All surfaces must be valid pointers.
+unsafe fn pack_atlas(
+    surfaces: &[(*mut SDL_Surface, i32, i32)],
+    height: i32,
+) -> Result<Texture, LoadErrorKind> {
+    let format = PixelFormatEnum::RGBA32 as u32;
+    let atlas =
+        unsafe { SDL_CreateRGBSurfaceWithFormat(0, ATLAS_WIDTH, height.max(1), 32, format) };
+    if atlas.is_null() {
+        return Err(SdlError::from_sdl().into());
+    }
+
+    for &(surface, x, y) in surfaces {
+        let mut dst = SDL_Rect { x, y, w: 0, h: 0 };
+        unsafe {
+            // Copy alpha as-is, instead of blending it with the empty atlas
+            SDL_SetSurfaceBlendMode(surface, SDL_BlendMode::SDL_BLENDMODE_NONE);
+            SDL_UpperBlit(surface, std::ptr::null(), atlas, &raw mut dst);
+        }
+    }
+
+    let texture = unsafe { Texture::from_surface(atlas, TextureOptions::default()) };
+    unsafe { SDL_FreeSurface(atlas) };
+
+    Ok(texture)
+}
+
+/// Some text, drawn with a [`Font`].
+///
+/// Lines are separated by `\n`. Characters which aren't in the font are drawn as `?`.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let font = Font::empty();
+/// gfx::draw(&Text::new(&font, "Game over!").color(Color::RED), vec2(10., 10.));
+/// ```
+///
+/// Text can have a drop shadow and an outline, to keep it readable over busy backgrounds. These
+/// are drawn together with the text itself, in a single draw call:
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let font = Font::empty();
+/// let title = Text::new(&font, "Level 3")
+///     .outline(1., Color::BLACK)
+///     .shadow(vec2(2., 2.), Color::from_black_alpha(128));
+/// gfx::draw(&title, vec2(10., 10.));
+/// ```
+#[must_use]
+#[derive(Clone, Copy)]
+pub struct Text<'a> {
+    font: &'a Font,
+    string: &'a str,
+    color: Color,
+    shadow: Option<(Vec2, Color)>,
+    outline: Option<(f32, Color)>,
+}
+
+impl<'a> Text<'a> {
+    /// Creates some text with a font.
+    pub const fn new(font: &'a Font, string: &'a str) -> Self {
+        let color = Color::WHITE;
+        Self {
+            font,
+            string,
+            color,
+            shadow: None,
+            outline: None,
+        }
+    }
+
+    /// Sets the color of this text. Defaults to white.
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Adds a drop shadow, moved by `offset` from the text. If the text also has an outline, the
+    /// shadow is outlined too, and its color should be opaque, like the outline's.
+    pub const fn shadow(mut self, offset: Vec2, color: Color) -> Self {
+        self.shadow = Some((offset, color));
+        self
+    }
+
+    /// Adds an outline around each glyph, `thickness` pixels wide.
+    ///
+    /// The outline is drawn as copies of the glyph around it, which overlap, so its color should
+    /// be opaque. Translucent colors get more opaque where the copies overlap. Thick outlines
+    /// need more copies, so they're slower to draw. Above 4 pixels, the number of copies stops
+    /// growing, and they're spread out instead, so thin parts of glyphs may show gaps.
+    pub const fn outline(mut self, thickness: f32, color: Color) -> Self {
+        self.outline = Some((thickness, color));
+        self
+    }
+
+    /// Offsets and colors for every copy of the glyphs, from back to front.
+    fn passes(&self) -> impl Iterator<Item = (Vec2, Color)> {
+        let (ring, fill) = (self.outline, self.color);
+        let outline = move |(offset, color): (Vec2, Color)| {
+            let around = ring.into_iter().flat_map(move |(thickness, _)| {
+                outline_offsets(thickness).map(move |around| (offset + around, color))
+            });
+            around.chain([(offset, color)])
+        };
+        let shadow = self.shadow.into_iter().flat_map(outline);
+        let border = ring.into_iter().flat_map(|(thickness, color)| {
+            outline_offsets(thickness).map(move |offset| (offset, color))
+        });
+        shadow.chain(border).chain([(Vec2::ZERO, fill)])
+    }
+}
+
+/// Offsets filling a disc `thickness` pixels wide, so the copies of a glyph drawn there cover
+/// its outline. Up to [`MAX_OUTLINE_STEPS`] pixels, they're at most a pixel apart, which leaves
+/// no gaps.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn outline_offsets(thickness: f32) -> impl Iterator<Item = Vec2> {
+    // Thicker outlines are spaced out more than a pixel, to keep the number of copies reasonable,
+    // so they can have gaps
+    let steps = (thickness.ceil() as i32).clamp(1, MAX_OUTLINE_STEPS);
+    let spacing = thickness / steps as f32;
+    let range = -steps..=steps;
+    let grid = range
+        .clone()
+        .flat_map(move |y| range.clone().map(move |x| (x, y)));
+    // Slightly more than the radius, so a thickness of 1 covers the corners too
+    grid.filter(move |&(x, y)| (x, y) != (0, 0) && x * x + y * y <= steps * steps + steps)
+        .map(move |(x, y)| vec2(x as f32, y as f32) * spacing)
+}
+
+/// Most steps from a glyph to the edge of its outline.
+const MAX_OUTLINE_STEPS: i32 = 4;
+
+const QUAD_IDX: [i32; 6] = [0, 1, 2, 2, 1, 3];
+
+impl Drawable for Text<'_> {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        let data = &self.font.data;
+        let texture = &data.texture;
+        let atlas_size = vec2(texture.width() as f32, texture.height() as f32);
+        let quad_count = self.string.chars().count() * self.passes().count();
+
+        with_frame_arena(|arena| {
+            let fill = Vertex::from_xy_uv(Vec2::ZERO, Vec2::ZERO);
+            let verts = arena.alloc_slice_fill(quad_count * 4, fill);
+            let indices = arena.alloc_slice_fill(quad_count * 6, 0);
+
+            let mut quads = 0;
+            for (offset, color) in self.passes() {
+                let mut pen = offset;
+                for ch in self.string.chars() {
+                    if ch == '\n' {
+                        pen = vec2(offset.x, pen.y + data.line_height);
+                        continue;
+                    }
+                    let Some(glyph) = self.font.glyph(ch) else {
+                        continue;
+                    };
+
+                    let size = vec2(glyph.w, data.height);
+                    let uv = vec2(glyph.x, glyph.y) / atlas_size;
+                    let uv_size = size / atlas_size;
+                    for (i, corner) in [vec2(0., 0.), vec2(1., 0.), vec2(0., 1.), vec2(1., 1.)]
+                        .into_iter()
+                        .enumerate()
+                    {
+                        let coord = vec2(pen.x + glyph.offset, pen.y) + corner * size;
+                        let coord = transform.transform_point(coord);
+                        verts[quads * 4 + i] = Vertex::new(coord, color, uv + corner * uv_size);
+                    }
+                    for (i, index) in QUAD_IDX.into_iter().enumerate() {
+                        indices[quads * 6 + i] = (quads * 4) as i32 + index;
+                    }
+
+                    quads += 1;
+                    pen.x += glyph.advance;
+                }
+            }
+
+            canvas.draw_geometry(texture, &verts[..quads * 4], Some(&indices[..quads * 6]));
+        });
+    }
+}
+
+/// Draws some text on the screen.
+///
+/// This is a shorthand for drawing [`Text`]. To draw text which changes every frame, you can
+/// use [`hud!`][crate::fmt::hud] to avoid allocating:
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let font = Font::empty();
+/// # let score = 0;
+/// gfx::draw_text(&font, &baba::fmt::hud!("Score: {score}"), vec2(10., 10.));
+/// ```
+pub fn draw_text(font: &Font, text: &str, transform: impl Into<Transform>) {
+    draw(&Text::new(font, text), transform);
+}
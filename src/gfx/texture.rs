@@ -1,3 +1,4 @@
+use std::panic::Location;
 use std::path::Path;
 use std::rc::Rc;
 
@@ -9,19 +10,22 @@ use thiserror::Error;
 use crate::math::Rect;
 use crate::SdlError;
 
-use super::{with_canvas, Canvas, Drawable, Transform, Vertex};
+use super::{with_canvas, Canvas, Color, Drawable, Transform, Vertex};
 
 /// Texture load error.
+pub type LoadError = crate::LoadError<LoadErrorKind>;
+
+/// What went wrong while loading a texture.
 #[derive(Debug, Error)]
-pub enum LoadError {
+pub enum LoadErrorKind {
     /// This texture couldn't be opened.
-    #[error(transparent)]
+    #[error("couldn't read file: {0}")]
     Io(#[from] std::io::Error),
     /// The image data couldn't be decoded.
-    #[error(transparent)]
+    #[error("couldn't decode image: {0}")]
     Decode(#[from] image::ImageError),
     /// The renderer failed to create a texture, for unknown reasons.
-    #[error(transparent)]
+    #[error("couldn't create texture: {0}")]
     Renderer(#[from] SdlError),
 }
 
@@ -57,14 +61,58 @@ pub enum ScaleMode {
     // Anisotropic = 2,
 }
 
+/// How colors are combined with what's already drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// No blending, colors are replaced, including alpha.
+    None,
+    /// Alpha blending. This is the usual for images with transparency.
+    #[default]
+    Alpha,
+    /// Additive blending, colors are added together. This makes things glow, and is good for
+    /// particles and lights.
+    Additive,
+    /// Colors are multiplied together, darkening what's underneath. Good for shadows.
+    Multiply,
+    /// Alpha blending for colors which are already multiplied by their alpha. Textures loaded with
+    /// [`Options::premultiply`] use this, which avoids dark fringes around translucent edges when
+    /// they're scaled with linear filtering.
+    PremultipliedAlpha,
+}
+
+impl BlendMode {
+    pub(crate) fn to_sdl(self) -> sdl2_sys::SDL_BlendMode {
+        use sdl2_sys::{SDL_BlendFactor, SDL_BlendOperation};
+
+        match self {
+            Self::None => sdl2_sys::SDL_BlendMode::SDL_BLENDMODE_NONE,
+            Self::Alpha => sdl2_sys::SDL_BlendMode::SDL_BLENDMODE_BLEND,
+            Self::Additive => sdl2_sys::SDL_BlendMode::SDL_BLENDMODE_ADD,
+            Self::Multiply => sdl2_sys::SDL_BlendMode::SDL_BLENDMODE_MUL,
+            Self::PremultipliedAlpha => unsafe {
+                let one = SDL_BlendFactor::SDL_BLENDFACTOR_ONE;
+                let inverse = SDL_BlendFactor::SDL_BLENDFACTOR_ONE_MINUS_SRC_ALPHA;
+                let add = SDL_BlendOperation::SDL_BLENDOPERATION_ADD;
+                sdl2_sys::SDL_ComposeCustomBlendMode(one, inverse, add, one, inverse, add)
+            },
+        }
+    }
+}
+
 /// Texture load options.
 #[derive(Default)]
 pub struct Options {
-    // blend: BlendMode,
+    /// How this texture is blended when drawn. Defaults to alpha blending if the image has
+    /// transparency, or none otherwise.
+    pub blend: Option<BlendMode>,
     /// How this texture is scaled. The default depends on engine [settings][crate::Settings].
     pub scaling: Option<ScaleMode>,
     /// The origin point for this texture. Defaults to top left.
     pub origin: Origin,
+    /// Multiply colors by their alpha when loading an image with transparency, and blend it with
+    /// [`BlendMode::PremultipliedAlpha`] unless another blend mode is given. This is best for
+    /// translucent images drawn with [`ScaleMode::Linear`].
+    pub premultiply: bool,
 }
 
 impl From<ScaleMode> for Options {
@@ -76,6 +124,15 @@ impl From<ScaleMode> for Options {
     }
 }
 
+impl From<BlendMode> for Options {
+    fn from(blend: BlendMode) -> Self {
+        Self {
+            blend: Some(blend),
+            ..Default::default()
+        }
+    }
+}
+
 impl From<Origin> for Options {
     fn from(origin: Origin) -> Self {
         Self {
@@ -100,29 +157,103 @@ impl TextureData {
         }
     }
 
-    fn from_image(img: image::DynamicImage, opts: &Options) -> Result<Self, LoadError> {
+    fn from_image(img: image::DynamicImage, opts: &Options) -> Result<Self, LoadErrorKind> {
         let w = img.width();
         let h = img.height();
-        let (format, mut data) = if img.color().has_alpha() {
-            (PixelFormatEnum::RGBA32, img.into_rgba8().into_raw())
-        } else {
-            (PixelFormatEnum::RGB24, img.into_rgb8().into_raw())
+        if !img.color().has_alpha() {
+            let data = img.into_rgb8().into_raw();
+            return Ok(Self::from_pixels(
+                w,
+                h,
+                PixelFormatEnum::RGB24,
+                &data,
+                opts,
+            )?);
+        }
+
+        let mut data = img.into_rgba8().into_raw();
+        if !opts.premultiply {
+            return Ok(Self::from_pixels(
+                w,
+                h,
+                PixelFormatEnum::RGBA32,
+                &data,
+                opts,
+            )?);
+        }
+        for pixel in data.chunks_exact_mut(4) {
+            let alpha = u16::from(pixel[3]);
+            for c in &mut pixel[..3] {
+                // Rounded division by 255
+                #[allow(clippy::cast_possible_truncation)]
+                let premultiplied = ((u16::from(*c) * alpha + 127) / 255) as u8;
+                *c = premultiplied;
+            }
+        }
+        let opts = Options {
+            blend: Some(opts.blend.unwrap_or(BlendMode::PremultipliedAlpha)),
+            scaling: opts.scaling,
+            origin: opts.origin,
+            premultiply: true,
         };
-        let pitch = w * format.byte_size_per_pixel() as u32;
+        Ok(Self::from_pixels(
+            w,
+            h,
+            PixelFormatEnum::RGBA32,
+            &data,
+            &opts,
+        )?)
+    }
 
-        with_canvas(|canvas| unsafe {
-            let surface = sdl2_sys::SDL_CreateRGBSurfaceWithFormatFrom(
-                data.as_mut_ptr().cast(),
+    fn from_pixels(
+        w: u32,
+        h: u32,
+        format: PixelFormatEnum,
+        data: &[u8],
+        opts: &Options,
+    ) -> Result<Self, SdlError> {
+        // SDL takes sizes as i32
+        let too_large = || SdlError(format!("{w}x{h} pixels is too large for a texture"));
+        let pitch = (w as usize)
+            .checked_mul(format.byte_size_per_pixel())
+            .filter(|&pitch| i32::try_from(pitch).is_ok() && i32::try_from(h).is_ok())
+            .ok_or_else(too_large)?;
+        let len = pitch.checked_mul(h as usize).ok_or_else(too_large)?;
+        assert_eq!(data.len(), len, "wrong size for {w}x{h} pixels");
+
+        // The surface is only read from, when it's uploaded
+        let surface = unsafe {
+            sdl2_sys::SDL_CreateRGBSurfaceWithFormatFrom(
+                data.as_ptr().cast_mut().cast(),
                 w as i32,
                 h as i32,
                 /* unused */ 0,
                 pitch as i32,
                 format as u32,
-            );
-            if surface.is_null() {
-                return Err(SdlError::from_sdl())?;
-            }
+            )
+        };
+        if surface.is_null() {
+            return Err(SdlError::from_sdl());
+        }
 
+        let texture = unsafe { Self::from_surface(surface, opts) };
+        unsafe { sdl2_sys::SDL_FreeSurface(surface) };
+
+        Ok(texture)
+    }
+
+    /// Uploads a surface to the GPU. The surface isn't freed.
+    ///
+    /// # Safety
+    ///
+    /// `surface` must be a valid pointer.
+    #[allow(clippy::cast_sign_loss)]
+    unsafe fn from_surface(surface: *mut sdl2_sys::SDL_Surface, opts: &Options) -> Self {
+        span!(DEBUG, "texture_upload");
+        let w = unsafe { (*surface).w as u32 };
+        let h = unsafe { (*surface).h as u32 };
+
+        with_canvas(|canvas| unsafe {
             let ptr = sdl2_sys::SDL_CreateTextureFromSurface(canvas.renderer(), surface);
             if ptr.is_null() {
                 log::warn!("Failed to create a texture: {}", SdlError::from_sdl());
@@ -132,11 +263,124 @@ impl TextureData {
                 let scale = std::mem::transmute::<ScaleMode, sdl2_sys::SDL_ScaleMode>(scale);
                 sdl2_sys::SDL_SetTextureScaleMode(ptr, scale);
             }
+            if let Some(blend) = opts.blend {
+                sdl2_sys::SDL_SetTextureBlendMode(ptr, blend.to_sdl());
+            }
+
+            Self { ptr, w, h }
+        })
+    }
+
+    /// Creates a texture which can be rendered to.
+    fn new_target(w: u32, h: u32, opts: &Options) -> Result<Self, SdlError> {
+        let access = sdl2_sys::SDL_TextureAccess::SDL_TEXTUREACCESS_TARGET;
+        Self::new(w, h, PixelFormatEnum::RGBA8888, access, opts)
+    }
+
+    /// Creates a texture which is meant to be updated often.
+    fn new_streaming(w: u32, h: u32, opts: &Options) -> Result<Self, SdlError> {
+        let access = sdl2_sys::SDL_TextureAccess::SDL_TEXTUREACCESS_STREAMING;
+        Self::new(w, h, PixelFormatEnum::RGBA32, access, opts)
+    }
+
+    fn new(
+        w: u32,
+        h: u32,
+        format: PixelFormatEnum,
+        access: sdl2_sys::SDL_TextureAccess,
+        opts: &Options,
+    ) -> Result<Self, SdlError> {
+        with_canvas(|canvas| unsafe {
+            let ptr = sdl2_sys::SDL_CreateTexture(
+                canvas.renderer(),
+                format as u32,
+                access as i32,
+                w as i32,
+                h as i32,
+            );
+            if ptr.is_null() {
+                return Err(SdlError::from_sdl());
+            }
+
+            if let Some(scale) = opts.scaling {
+                let scale = std::mem::transmute::<ScaleMode, sdl2_sys::SDL_ScaleMode>(scale);
+                sdl2_sys::SDL_SetTextureScaleMode(ptr, scale);
+            }
+            if let Some(blend) = opts.blend {
+                sdl2_sys::SDL_SetTextureBlendMode(ptr, blend.to_sdl());
+            }
 
             Ok(Self { ptr, w, h })
         })
     }
 
+    /// Replaces pixels in a rectangle, given as RGBA bytes.
+    fn update(&self, rect: &Rect, pixels: &[u8]) -> Result<(), SdlError> {
+        assert!(
+            rect.x + rect.w <= self.w && rect.y + rect.h <= self.h,
+            "{rect:?} is outside of the {}x{} texture",
+            self.w,
+            self.h
+        );
+        assert_eq!(
+            pixels.len(),
+            (rect.w * rect.h * 4) as usize,
+            "wrong size for {}x{} pixels",
+            rect.w,
+            rect.h
+        );
+
+        let mut format = 0;
+        let query = std::ptr::null_mut();
+        if unsafe { sdl2_sys::SDL_QueryTexture(self.ptr, &raw mut format, query, query, query) }
+            != 0
+        {
+            return Err(SdlError::from_sdl());
+        }
+
+        // Textures loaded from images use whichever format the renderer prefers
+        let rgba = PixelFormatEnum::RGBA32 as u32;
+        let mut converted = Vec::new();
+        let (pixels, pitch) = if format == rgba {
+            (pixels, rect.w * 4)
+        } else {
+            let size = PixelFormatEnum::try_from(format)
+                .map_err(|()| SdlError(format!("unknown pixel format {format:#x}")))?
+                .byte_size_per_pixel() as u32;
+            converted.resize((rect.w * rect.h * size) as usize, 0);
+            let result = unsafe {
+                sdl2_sys::SDL_ConvertPixels(
+                    rect.w as i32,
+                    rect.h as i32,
+                    rgba,
+                    pixels.as_ptr().cast(),
+                    (rect.w * 4) as i32,
+                    format,
+                    converted.as_mut_ptr().cast(),
+                    (rect.w * size) as i32,
+                )
+            };
+            if result != 0 {
+                return Err(SdlError::from_sdl());
+            }
+            (converted.as_slice(), rect.w * size)
+        };
+
+        let rect = sdl2_sys::SDL_Rect {
+            x: rect.x as i32,
+            y: rect.y as i32,
+            w: rect.w as i32,
+            h: rect.h as i32,
+        };
+        let data = pixels.as_ptr().cast();
+        if unsafe { sdl2_sys::SDL_UpdateTexture(self.ptr, &raw const rect, data, pitch as i32) }
+            != 0
+        {
+            return Err(SdlError::from_sdl());
+        }
+        Ok(())
+    }
+
     pub const fn raw(&self) -> *mut sdl2_sys::SDL_Texture {
         self.ptr
     }
@@ -154,6 +398,7 @@ impl Drop for TextureData {
 pub struct Texture {
     data: Rc<TextureData>,
     origin: Vec2,
+    tint: Color,
 }
 
 impl Texture {
@@ -161,10 +406,12 @@ impl Texture {
     pub fn empty() -> Self {
         let data = Rc::new(TextureData::empty());
         let origin = Vec2::ZERO;
-        Self { data, origin }
+        let tint = Color::WHITE;
+        Self { data, origin, tint }
     }
 
     /// Loads a texture at a given path.
+    #[track_caller]
     pub fn load(path: impl AsRef<Path>) -> Self {
         Self::load_with(path, Options::default())
     }
@@ -172,7 +419,7 @@ impl Texture {
     /// Loads a texture at a given path, with custom options.
     ///
     /// You may specify a [`ScaleMode`] or an [`Origin`] for the texture, or both using [`TextureOptions`][Options].
-    /// 
+    ///
     /// ```no_run
     /// # use baba::prelude::*;
     /// // Create a texture which is positioned around its center.
@@ -180,29 +427,165 @@ impl Texture {
     /// // Create a texture with linear scaling
     /// let my_texture = Texture::load_with("resources/image.png", ScaleMode::Linear);
     /// ```
+    #[track_caller]
     pub fn load_with(path: impl AsRef<Path>, options: impl Into<Options>) -> Self {
-        Self::try_load(path.as_ref(), options)
-            .inspect_err(|e| log::error!("Failed to load {}: {e}", path.as_ref().display()))
+        Self::try_load(path, options)
+            .inspect_err(|e| log::error!("Failed to load {e}"))
             .unwrap_or_else(|_| Self::empty())
     }
 
     /// Like [`load`][Texture::load], but returns an error instead of outputting a warning.
+    #[track_caller]
     pub fn try_load(
         path: impl AsRef<Path>,
         options: impl Into<Options>,
     ) -> Result<Self, LoadError> {
-        Self::from_image(Reader::open(path.as_ref())?.decode()?, options.into())
+        let caller = Location::caller();
+        let path = path.as_ref();
+        crate::assets::record(path);
+        let options = options.into();
+        let image = || Ok::<_, LoadErrorKind>(Reader::open(path)?.decode()?);
+
+        image()
+            .and_then(|img| Self::from_image_with(img, &options))
+            .map_err(|kind| LoadError::new(kind, Some(path), caller))
     }
 
     /// Creates a texture from an image in memory.
+    #[track_caller]
     pub fn from_image(
         img: image::DynamicImage,
         options: impl Into<Options>,
     ) -> Result<Self, LoadError> {
+        let caller = Location::caller();
+        let options = options.into();
+        Self::from_image_with(img, &options).map_err(|kind| LoadError::new(kind, None, caller))
+    }
+
+    fn from_image_with(img: image::DynamicImage, options: &Options) -> Result<Self, LoadErrorKind> {
+        let origin = options.origin.0;
+        let data = Rc::new(TextureData::from_image(img, options)?);
+        let tint = Color::WHITE;
+        Ok(Self { data, origin, tint })
+    }
+
+    /// Creates a texture from raw pixels, with 4 bytes per pixel in RGBA order, row by row.
+    ///
+    /// This is useful for generated images, or pixels from other libraries:
+    ///
+    /// ```no_run
+    /// # use baba::prelude::*;
+    /// let pixels: Vec<u8> = (0..64 * 64)
+    ///     .flat_map(|i| [(i % 64 * 4) as u8, (i / 64 * 4) as u8, 128, 255])
+    ///     .collect();
+    /// let gradient = Texture::from_rgba8(64, 64, &pixels);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `pixels` doesn't have exactly `width * height * 4` bytes.
+    pub fn from_rgba8(width: u32, height: u32, pixels: &[u8]) -> Self {
+        Self::from_pixels(width, height, PixelFormatEnum::RGBA32, pixels)
+    }
+
+    /// Creates a texture from raw pixels, with 3 bytes per pixel in RGB order, row by row.
+    ///
+    /// # Panics
+    ///
+    /// If `pixels` doesn't have exactly `width * height * 3` bytes.
+    pub fn from_rgb8(width: u32, height: u32, pixels: &[u8]) -> Self {
+        Self::from_pixels(width, height, PixelFormatEnum::RGB24, pixels)
+    }
+
+    fn from_pixels(width: u32, height: u32, format: PixelFormatEnum, pixels: &[u8]) -> Self {
+        let options = Options::default();
+        match TextureData::from_pixels(width, height, format, pixels, &options) {
+            Ok(data) => Self {
+                data: Rc::new(data),
+                origin: Vec2::ZERO,
+                tint: Color::WHITE,
+            },
+            Err(e) => {
+                log::error!("Failed to create texture: {e}");
+                Self::empty()
+            }
+        }
+    }
+
+    /// Creates a texture from an SDL surface. The surface isn't freed.
+    ///
+    /// # Safety
+    ///
+    /// `surface` must be a valid pointer.
+    pub(crate) unsafe fn from_surface(
+        surface: *mut sdl2_sys::SDL_Surface,
+        options: impl Into<Options>,
+    ) -> Self {
         let options = options.into();
         let origin = options.origin.0;
-        let data = Rc::new(TextureData::from_image(img, &options)?);
-        Ok(Self { data, origin })
+        let data = Rc::new(unsafe { TextureData::from_surface(surface, &options) });
+        let tint = Color::WHITE;
+        Self { data, origin, tint }
+    }
+
+    /// Creates a texture which can be drawn to, with uninitialised contents.
+    pub(crate) fn new_target(
+        width: u32,
+        height: u32,
+        options: impl Into<Options>,
+    ) -> Result<Self, SdlError> {
+        let options = options.into();
+        let origin = options.origin.0;
+        let data = Rc::new(TextureData::new_target(width, height, &options)?);
+        let tint = Color::WHITE;
+        Ok(Self { data, origin, tint })
+    }
+
+    /// Creates a blank texture, which is meant to be [updated][Self::update] often. Its contents
+    /// start out undefined.
+    ///
+    /// ```no_run
+    /// # use baba::prelude::*;
+    /// let mut framebuffer = vec![0; 256 * 240 * 4];
+    /// let screen = Texture::new_streaming(256, 240);
+    ///
+    /// // Every frame
+    /// for pixel in framebuffer.chunks_mut(4) {
+    ///     pixel.copy_from_slice(&[0, 0, 0, 255]);
+    /// }
+    /// screen.update(None, &framebuffer);
+    /// gfx::draw(&screen, Vec2::ZERO);
+    /// ```
+    pub fn new_streaming(width: u32, height: u32) -> Self {
+        let options = Options::from(BlendMode::Alpha);
+        match TextureData::new_streaming(width, height, &options) {
+            Ok(data) => Self {
+                data: Rc::new(data),
+                origin: Vec2::ZERO,
+                tint: Color::WHITE,
+            },
+            Err(e) => {
+                log::error!("Failed to create texture: {e}");
+                Self::empty()
+            }
+        }
+    }
+
+    /// Replaces the pixels in a rectangle of this texture, or the whole texture if `rect` is
+    /// `None`. Pixels are given as RGBA bytes, row by row.
+    ///
+    /// Any texture can be updated, but ones created with [`new_streaming`][Self::new_streaming]
+    /// are faster to update every frame. Every copy of this texture sees the new pixels.
+    ///
+    /// # Panics
+    ///
+    /// If `rect` goes outside of the texture, or `pixels` doesn't have exactly 4 bytes for every
+    /// pixel in it.
+    pub fn update(&self, rect: Option<&Rect>, pixels: &[u8]) {
+        let full = Rect::new(0, 0, self.width(), self.height());
+        if let Err(e) = self.data.update(rect.unwrap_or(&full), pixels) {
+            log::error!("Failed to update texture: {e}");
+        }
     }
 
     /// Creates a slice which points to part of this texture. Useful for spritesheets.
@@ -217,6 +600,26 @@ impl Texture {
         self
     }
 
+    /// Creates a copy of this texture which is drawn multiplied by a color. This is cheap, as the
+    /// image itself is shared. A translucent color fades the texture, whichever
+    /// [blend mode][BlendMode] it uses.
+    ///
+    /// ```no_run
+    /// # use baba::prelude::*;
+    /// # let texture = Texture::empty();
+    /// // Flash red when hurt
+    /// gfx::draw(&texture.tinted(Color::RED), vec2(40., 10.));
+    /// // Fade out
+    /// let faded = Color::from_rgba_unmultiplied(255, 255, 255, 64);
+    /// gfx::draw(&texture.tinted(faded), vec2(40., 10.));
+    /// ```
+    pub fn tinted(&self, tint: Color) -> Self {
+        Self {
+            tint,
+            ..self.clone()
+        }
+    }
+
     /// The width of this texture.
     #[must_use]
     pub fn width(&self) -> u32 {
@@ -232,6 +635,14 @@ impl Texture {
     pub(crate) fn raw(&self) -> *mut sdl2_sys::SDL_Texture {
         self.data.raw()
     }
+
+    pub(crate) const fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    pub(crate) const fn tint(&self) -> Color {
+        self.tint
+    }
 }
 
 /// A [`Texture`] which only draws a small rectangle of it.
@@ -242,6 +653,15 @@ pub struct TextureSlice {
     rect: Rect,
 }
 
+impl TextureSlice {
+    /// Creates a copy of this slice which is drawn multiplied by a color.
+    pub fn tinted(&self, tint: Color) -> Self {
+        let texture = self.texture.tinted(tint);
+        let rect = self.rect.clone();
+        Self { texture, rect }
+    }
+}
+
 const QUAD_VERTS: [Vec2; 4] = [vec2(0., 0.), vec2(1., 0.), vec2(0., 1.), vec2(1., 1.)];
 const QUAD_IDX: [i32; 6] = [0, 1, 2, 2, 1, 3];
 
@@ -249,8 +669,8 @@ impl Drawable for Texture {
     fn draw(&self, canvas: &mut Canvas, transform: Transform) {
         let size = vec2(self.data.w as f32, self.data.h as f32);
         let transform = transform.scale(size);
-        let verts =
-            QUAD_VERTS.map(|p| Vertex::from_xy_uv(transform.transform_point(p - self.origin), p));
+        let verts = QUAD_VERTS
+            .map(|p| Vertex::new(transform.transform_point(p - self.origin), self.tint, p));
 
         canvas.draw_geometry(self, &verts, Some(&QUAD_IDX));
     }
@@ -272,8 +692,14 @@ impl Drawable for TextureSlice {
         );
         let transform = transform.scale(size);
 
-        let verts = QUAD_VERTS
-            .map(|p| Vertex::from_xy_uv(transform.transform_point(p - origin), p * uv_size + uv));
+        let tint = self.texture.tint;
+        let verts = QUAD_VERTS.map(|p| {
+            Vertex::new(
+                transform.transform_point(p - origin),
+                tint,
+                p * uv_size + uv,
+            )
+        });
 
         canvas.draw_geometry(&self.texture, &verts, Some(&QUAD_IDX));
     }
@@ -35,6 +35,25 @@ impl Transform {
         Self(Affine2::from_angle(angle))
     }
 
+    /// Create a transform which leans things over, by angles (in radians) along each axis. A
+    /// positive `x` leans the top to the left, like italic text leaning the other way.
+    ///
+    /// ```no_run
+    /// # use baba::prelude::*;
+    /// # use baba::gfx::Transform;
+    /// # let (texture, position) = (Texture::empty(), Vec2::ZERO);
+    /// // Grass swaying in the wind
+    /// let sway = (baba::time::elapsed() * 2.).sin() * 0.2;
+    /// gfx::draw(&texture, Transform::from_translation(position).skew(sway, 0.));
+    /// ```
+    #[inline]
+    pub fn from_skew(x: f32, y: f32) -> Self {
+        Self(Affine2::from_mat2(Mat2::from_cols(
+            Vec2::new(1., y.tan()),
+            Vec2::new(x.tan(), 1.),
+        )))
+    }
+
     /// Translate this transform by `coords`.
     #[inline]
     pub fn translate(self, coords: Vec2) -> Self {
@@ -53,6 +72,18 @@ impl Transform {
         self * Self::from_rotation(angle)
     }
 
+    /// Skew this transform by angles (in radians) along each axis.
+    #[inline]
+    pub fn skew(self, x: f32, y: f32) -> Self {
+        self * Self::from_skew(x, y)
+    }
+
+    /// The transform which undoes this one.
+    #[inline]
+    pub fn inverse(self) -> Self {
+        Self(self.0.inverse())
+    }
+
     /// Get the affine transformation matrix for this transform.
     #[must_use]
     pub const fn to_affine(self) -> Affine2 {
@@ -67,6 +98,42 @@ impl Transform {
     pub fn transform_point(&self, point: Vec2) -> Vec2 {
         self.0.transform_point2(point)
     }
+
+    /// Transform a 2D vector with this object, like a direction or a velocity.
+    ///
+    /// This may scale and rotate, but doesn't translate.
+    #[must_use]
+    #[inline]
+    pub fn transform_vector(&self, vector: Vec2) -> Vec2 {
+        self.0.transform_vector2(vector)
+    }
+
+    /// Split this transform into its translation, scale and rotation (in radians), in the same
+    /// order as the tuples transforms can be created from.
+    ///
+    /// ```
+    /// # use baba::prelude::*;
+    /// # use baba::gfx::Transform;
+    /// // A sprite's transform, and a click in the same space
+    /// let sprite = Transform::from((vec2(50., 20.), Vec2::splat(2.), 0.5));
+    /// let click = vec2(54., 27.);
+    ///
+    /// // Where the click is on the sprite itself, for picking
+    /// let local = sprite.inverse().transform_point(click);
+    /// let (translation, scale, angle) = sprite.decompose();
+    /// assert!(translation.abs_diff_eq(vec2(50., 20.), 1e-4));
+    /// assert!(scale.abs_diff_eq(Vec2::splat(2.), 1e-4));
+    /// assert!((angle - 0.5).abs() < 1e-4);
+    /// ```
+    ///
+    /// Transforms which are skewed, or scaled unevenly after rotating, can't be split exactly,
+    /// so the result only approximates them.
+    #[must_use]
+    #[inline]
+    pub fn decompose(self) -> (Vec2, Vec2, f32) {
+        let (scale, angle, translation) = self.0.to_scale_angle_translation();
+        (translation, scale, angle)
+    }
 }
 
 impl Mul for Transform {
@@ -110,6 +177,17 @@ macro_rules! impl_translation_scale_rotation {
                     .rotate(angle.into())
             }
         }
+
+        impl From<($T, $U, $V, Vec2)> for Transform {
+            /// Create a transform with translation, scale, rotation and skew.
+            #[inline]
+            fn from((translation, scale, angle, skew): ($T, $U, $V, Vec2)) -> Self {
+                Self::from_translation(translation.into())
+                    .scale(scale.into())
+                    .rotate(angle.into())
+                    .skew(skew.x, skew.y)
+            }
+        }
     };
 }
 
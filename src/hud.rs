@@ -0,0 +1,1086 @@
+//! Widgets for heads-up displays, like health bars, tooltips, radial menus, input overlays, and
+//! markers pointing at things in the world, a brightness calibration screen, and cinematic bars.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let (hp, max_hp) = (7., 10.);
+//! use baba::hud::Bar;
+//!
+//! let mut health = Bar::new(vec2(80., 6.))
+//!     .with_colors(Color::from_rgb(200, 40, 40), Color::from_gray(30))
+//!     .with_segments(10);
+//!
+//! // Every frame
+//! health.set(hp / max_hp);
+//! health.update();
+//! gfx::draw(&health, vec2(4., 4.));
+//! ```
+
+use std::f32::consts::TAU;
+
+use glam::{vec2, Vec2};
+
+use crate::alloc::with_frame_arena;
+use crate::gfx::{
+    self, Camera, Canvas, Color, Drawable, Font, NineSlice, Origin, Shape, Text, Texture,
+    Transform, Vertex,
+};
+use crate::input::gamepad::{self, Button};
+use crate::input::{self, KeyCode, MouseButton};
+use crate::math::Ease;
+use crate::time;
+
+/// Which way a [`Bar`] fills up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FillDirection {
+    /// From the left edge to the right.
+    #[default]
+    Right,
+    /// From the right edge to the left.
+    Left,
+    /// From the bottom edge to the top.
+    Up,
+    /// From the top edge to the bottom.
+    Down,
+}
+
+/// A bar which shows how full something is, like health, mana or experience.
+///
+/// When the value goes down, the part that was lost stays visible for a moment in the lag
+/// color, then drains away, so it's easy to see how much was lost.
+#[must_use]
+#[derive(Clone)]
+pub struct Bar {
+    size: Vec2,
+    value: f32,
+    lag: f32,
+    lag_speed: f32,
+    direction: FillDirection,
+    segments: u32,
+    fill: Color,
+    background: Color,
+    lag_color: Color,
+    tick_color: Color,
+    frame: Option<(NineSlice, f32)>,
+}
+
+impl Bar {
+    /// Creates a full bar of a size, in pixels.
+    pub const fn new(size: Vec2) -> Self {
+        Self {
+            size,
+            value: 1.,
+            lag: 1.,
+            lag_speed: 0.5,
+            direction: FillDirection::Right,
+            segments: 1,
+            fill: Color::WHITE,
+            background: Color::from_black_alpha(160),
+            lag_color: Color::from_rgb(255, 220, 120),
+            tick_color: Color::from_black_alpha(120),
+            frame: None,
+        }
+    }
+
+    /// Sets which way the bar fills up. Defaults to the right.
+    pub const fn with_direction(mut self, direction: FillDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the color of the filled part, and the empty part behind it.
+    pub const fn with_colors(mut self, fill: Color, background: Color) -> Self {
+        self.fill = fill;
+        self.background = background;
+        self
+    }
+
+    /// Sets the color of what was just lost, and how fast it drains, as a fraction of the bar
+    /// per second. A speed of 0 turns it off. Defaults to 0.5.
+    pub const fn with_lag(mut self, color: Color, speed: f32) -> Self {
+        self.lag_color = color;
+        self.lag_speed = speed;
+        self
+    }
+
+    /// Splits the bar into a number of segments, with ticks between them.
+    pub const fn with_segments(mut self, segments: u32) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the color of the ticks between segments.
+    pub const fn with_tick_color(mut self, color: Color) -> Self {
+        self.tick_color = color;
+        self
+    }
+
+    /// Draws a frame around the bar, with some space between them.
+    pub fn with_frame(mut self, frame: &NineSlice, padding: f32) -> Self {
+        self.frame = Some((frame.clone(), padding));
+        self
+    }
+
+    /// Sets how full the bar is, from 0 to 1.
+    pub fn set(&mut self, value: f32) {
+        self.value = value.clamp(0., 1.);
+        if self.value > self.lag || self.lag_speed <= 0. {
+            self.lag = self.value;
+        }
+    }
+
+    /// How full the bar is, from 0 to 1.
+    #[must_use]
+    pub const fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Drains the lag. This should be called every frame.
+    pub fn update(&mut self) {
+        self.lag = (self.lag - self.lag_speed * time::delta()).max(self.value);
+    }
+
+    /// The part of the bar filled up to a fraction, as a position and size.
+    fn filled(&self, fraction: f32) -> (Vec2, Vec2) {
+        let Vec2 { x: w, y: h } = self.size;
+        match self.direction {
+            FillDirection::Right => (Vec2::ZERO, vec2(w * fraction, h)),
+            FillDirection::Left => (vec2(w * (1. - fraction), 0.), vec2(w * fraction, h)),
+            FillDirection::Up => (vec2(0., h * (1. - fraction)), vec2(w, h * fraction)),
+            FillDirection::Down => (Vec2::ZERO, vec2(w, h * fraction)),
+        }
+    }
+}
+
+impl Drawable for Bar {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        Shape::rect(self.size)
+            .color(self.background)
+            .draw(canvas, transform);
+        for (fraction, color) in [(self.lag, self.lag_color), (self.value, self.fill)] {
+            let (position, size) = self.filled(fraction);
+            if size.min_element() > 0. {
+                Shape::rect(size)
+                    .color(color)
+                    .draw(canvas, transform.translate(position));
+            }
+        }
+
+        let vertical = matches!(self.direction, FillDirection::Up | FillDirection::Down);
+        for i in 1..self.segments {
+            let t = i as f32 / self.segments as f32;
+            let (position, size) = if vertical {
+                (vec2(0., (self.size.y * t).round()), vec2(self.size.x, 1.))
+            } else {
+                (vec2((self.size.x * t).round(), 0.), vec2(1., self.size.y))
+            };
+            Shape::rect(size)
+                .color(self.tick_color)
+                .draw(canvas, transform.translate(position));
+        }
+
+        if let Some((frame, padding)) = &self.frame {
+            let frame = frame.clone().with_size(self.size + padding * 2.);
+            frame.draw(canvas, transform.translate(Vec2::splat(-padding)));
+        }
+    }
+}
+
+/// Where to point at something in the world from the screen, found with [`locate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Indicator {
+    /// Where to draw the marker, in screen coordinates. If the target is off screen, this is on
+    /// the edge of the screen, in its direction.
+    pub position: Vec2,
+    /// Direction from the center of the screen towards the target.
+    pub direction: Vec2,
+    /// Is the target on the screen?
+    pub on_screen: bool,
+    /// Distance from the center of the screen to the target, in world units.
+    pub distance: f32,
+}
+
+impl Indicator {
+    /// How visible a marker should be, fading from 1 at `near` to 0 at `far`, in world units.
+    #[must_use]
+    pub fn fade(&self, near: f32, far: f32) -> f32 {
+        if far <= near {
+            return f32::from(self.distance <= near);
+        }
+        1. - ((self.distance - near) / (far - near)).clamp(0., 1.)
+    }
+
+    /// Draws a triangle at the indicator's position, pointing towards the target. Nothing is
+    /// drawn if the target is on screen.
+    pub fn draw_arrow(&self, size: f32, color: Color) {
+        if self.on_screen {
+            return;
+        }
+        let back = self.position - self.direction * size;
+        let width = self.direction.perp() * size / 2.;
+        let verts = [self.position, back + width, back - width]
+            .map(|point| Vertex::from_xy_color(point, color));
+        gfx::with_canvas(|canvas| canvas.draw_colored_geometry(&verts, None));
+    }
+}
+
+/// Finds where to show a marker for something in the world, like a quest objective.
+///
+/// Pass the camera the world is drawn with, if any. Off-screen targets are pinned to the edge of
+/// the screen, `margin` pixels in, so an arrow can point at them. Draw markers outside of
+/// [`with_camera`][gfx::with_camera], since they're in screen coordinates.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let (camera, chest, icon) = (Camera::default(), Vec2::ZERO, Texture::empty());
+/// let marker = baba::hud::locate(chest, Some(&camera), 12.);
+/// let alpha = marker.fade(200., 800.);
+/// if marker.on_screen {
+///     gfx::draw(&icon.tinted(Color::WHITE.gamma_multiply(alpha)), marker.position);
+/// } else {
+///     marker.draw_arrow(8., Color::YELLOW.gamma_multiply(alpha));
+/// }
+/// ```
+#[must_use]
+pub fn locate(target: Vec2, camera: Option<&Camera>, margin: f32) -> Indicator {
+    let center = gfx::screen_size() / 2.;
+    let (screen, world_center) = match camera {
+        Some(camera) => (
+            camera.world_to_screen(target),
+            camera.screen_to_world(center),
+        ),
+        None => (target, center),
+    };
+
+    let offset = screen - center;
+    let half = (center - margin).max(Vec2::ZERO);
+    // How far along the offset the edge of the screen is
+    let scale = [(half.x, offset.x), (half.y, offset.y)]
+        .into_iter()
+        .filter(|&(_, o)| o != 0.)
+        .map(|(h, o)| h / o.abs())
+        .fold(f32::INFINITY, f32::min);
+    let on_screen = scale >= 1.;
+
+    Indicator {
+        position: if on_screen {
+            screen
+        } else {
+            center + offset * scale
+        },
+        direction: offset.normalize_or_zero(),
+        on_screen,
+        distance: world_center.distance(target),
+    }
+}
+
+/// A box of text which shows up next to the mouse after hovering over something for a moment.
+///
+/// Tell the tooltip what's being hovered every frame, and it takes care of the delay. It's kept
+/// inside of the screen, flipping to the other side of the cursor near the edges.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let (font, camera, button, chest) = (Font::empty(), Camera::default(), Vec2::ZERO, Vec2::ZERO);
+/// use baba::hud::Tooltip;
+///
+/// let mut tooltip = Tooltip::new(&font);
+///
+/// // Every frame, find what's under the mouse, whether it's on the screen or in the world
+/// let mouse = mouse_position();
+/// let world_mouse = camera.screen_to_world(mouse);
+/// let hovered = if mouse.distance(button) < 8. {
+///     Some("Save the game")
+/// } else if world_mouse.distance(chest) < 16. {
+///     Some("A locked chest.\nMaybe there's a key somewhere?")
+/// } else {
+///     None
+/// };
+/// tooltip.update(hovered);
+///
+/// // After drawing everything else
+/// tooltip.draw();
+/// ```
+#[must_use]
+#[derive(Clone)]
+pub struct Tooltip {
+    font: Font,
+    text: String,
+    hovered: f32,
+    delay: f32,
+    padding: f32,
+    color: Color,
+    background: Color,
+}
+
+impl Tooltip {
+    /// Creates a tooltip, with text drawn in a font.
+    pub fn new(font: &Font) -> Self {
+        Self {
+            font: font.clone(),
+            text: String::new(),
+            hovered: 0.,
+            delay: 0.5,
+            padding: 4.,
+            color: Color::WHITE,
+            background: Color::from_black_alpha(200),
+        }
+    }
+
+    /// Sets how long something has to be hovered before the tooltip shows up, in seconds.
+    /// Defaults to 0.5.
+    pub const fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets the color of the text and the box behind it.
+    pub const fn with_colors(mut self, text: Color, background: Color) -> Self {
+        self.color = text;
+        self.background = background;
+        self
+    }
+
+    /// Sets the space between the text and the edges of the box. Defaults to 4.
+    pub const fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the text of whatever is being hovered, or `None` if nothing is. This should be
+    /// called every frame. Hovering something else starts the delay over.
+    pub fn update(&mut self, hovered: Option<&str>) {
+        match hovered {
+            Some(text) if text == self.text => self.hovered += time::delta(),
+            Some(text) => {
+                text.clone_into(&mut self.text);
+                self.hovered = 0.;
+            }
+            None => {
+                self.text.clear();
+                self.hovered = 0.;
+            }
+        }
+    }
+
+    /// Is the tooltip being shown?
+    #[must_use]
+    pub fn is_visible(&self) -> bool {
+        !self.text.is_empty() && self.hovered >= self.delay
+    }
+
+    /// Draws the tooltip next to the mouse, if it's visible. This is in screen coordinates, so
+    /// draw it outside of [`with_camera`][gfx::with_camera].
+    pub fn draw(&self) {
+        if !self.is_visible() {
+            return;
+        }
+        let screen = gfx::screen_size();
+        let size = self.font.measure(&self.text) + self.padding * 2.;
+        let mouse = input::mouse_position();
+
+        // Below and to the right of the cursor, unless that goes off the screen
+        let cursor = vec2(12., 16.);
+        let mut position = mouse + cursor;
+        if position.x + size.x > screen.x {
+            position.x = mouse.x - size.x;
+        }
+        if position.y + size.y > screen.y {
+            position.y = mouse.y - size.y;
+        }
+        let position = position
+            .clamp(Vec2::ZERO, (screen - size).max(Vec2::ZERO))
+            .round();
+
+        gfx::draw(&Shape::rect(size).color(self.background), position);
+        let text = Text::new(&self.font, &self.text).color(self.color);
+        gfx::draw(&text, position + self.padding);
+    }
+}
+
+/// A wheel of choices, picked by pointing in their direction, like a weapon or emote wheel.
+///
+/// The first slice is at the top, and the rest go around clockwise. Point at them with a
+/// controller stick, or with the mouse relative to the center of the menu. Pointing inside of the
+/// deadzone keeps the last choice, so letting go of the stick doesn't lose it.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let (sword, bow, bomb) = (Texture::empty(), Texture::empty(), Texture::empty());
+/// use baba::hud::RadialMenu;
+/// use baba::input::gamepad::{self, Button};
+///
+/// let mut wheel = RadialMenu::new(3, 60.)
+///     .with_icon(0, &sword)
+///     .with_icon(1, &bow)
+///     .with_icon(2, &bomb);
+///
+/// // Every frame, while the wheel is open
+/// wheel.point(gamepad::right_stick(0));
+/// gfx::draw(&wheel, gfx::screen_size() / 2.);
+///
+/// if gamepad::is_button_pressed(0, Button::A) {
+///     if let Some(weapon) = wheel.selected() {
+///         println!("Equipped weapon {weapon}");
+///     }
+/// }
+/// ```
+#[must_use]
+#[derive(Clone)]
+pub struct RadialMenu {
+    icons: Vec<Option<Texture>>,
+    radius: f32,
+    inner_radius: f32,
+    deadzone: f32,
+    gap: f32,
+    color: Color,
+    selected_color: Color,
+    selected: Option<usize>,
+}
+
+impl RadialMenu {
+    /// Creates a menu with some number of slices, reaching out to a radius in pixels.
+    pub fn new(slices: usize, radius: f32) -> Self {
+        Self {
+            icons: vec![None; slices],
+            radius,
+            inner_radius: radius / 3.,
+            deadzone: 0.4,
+            gap: 2.,
+            color: Color::from_black_alpha(180),
+            selected_color: Color::from_rgba_unmultiplied(255, 255, 255, 120),
+            selected: None,
+        }
+    }
+
+    /// Sets the icon drawn in the middle of a slice.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn with_icon(mut self, index: usize, icon: &Texture) -> Self {
+        self.icons[index] = Some(icon.clone().with_origin(Origin::CENTER));
+        self
+    }
+
+    /// Sets the radius of the hole in the middle. Defaults to a third of the radius.
+    pub const fn with_inner_radius(mut self, radius: f32) -> Self {
+        self.inner_radius = radius;
+        self
+    }
+
+    /// Sets how far the stick has to be pushed to pick something, from 0 to 1. Defaults to 0.4.
+    pub const fn with_deadzone(mut self, deadzone: f32) -> Self {
+        self.deadzone = deadzone;
+        self
+    }
+
+    /// Sets the space between slices, in pixels. Defaults to 2.
+    pub const fn with_gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Sets the color of the slices, and of the one which is picked.
+    pub const fn with_colors(mut self, color: Color, selected: Color) -> Self {
+        self.color = color;
+        self.selected_color = selected;
+        self
+    }
+
+    /// Number of slices.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.icons.len()
+    }
+
+    /// Does this menu have no slices?
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.icons.is_empty()
+    }
+
+    /// Which slice a direction points at, or `None` if it's inside of the deadzone.
+    #[must_use]
+    pub fn slice_at(&self, direction: Vec2) -> Option<usize> {
+        if self.icons.is_empty() || direction.length() < self.deadzone {
+            return None;
+        }
+        // Clockwise from the top, with the first slice centered on it
+        let step = TAU / self.icons.len() as f32;
+        let angle = direction.x.atan2(-direction.y) + step / 2.;
+        #[allow(clippy::cast_sign_loss)]
+        let index = (angle.rem_euclid(TAU) / step) as usize;
+        Some(index % self.icons.len())
+    }
+
+    /// Points at a slice, with a stick position or any other direction. For the mouse, divide
+    /// its offset from the center by the radius, so the deadzone works the same way.
+    pub fn point(&mut self, direction: Vec2) {
+        if let Some(index) = self.slice_at(direction) {
+            self.selected = Some(index);
+        }
+    }
+
+    /// The slice which is picked, if any.
+    #[must_use]
+    pub const fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Picks a slice directly, or `None` to pick nothing, like when the menu is opened again.
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|&i| i < self.icons.len());
+    }
+}
+
+impl Drawable for RadialMenu {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        const STEPS: usize = 12;
+
+        let count = self.icons.len();
+        if count == 0 {
+            return;
+        }
+        let step = TAU / count as f32;
+        // Directions are clockwise from the top, since y points down
+        let direction = |angle: f32| vec2(angle.sin(), -angle.cos());
+
+        with_frame_arena(|arena| {
+            let vertex = Vertex::from_xy_color(Vec2::ZERO, self.color);
+            let verts = arena.alloc_slice_fill(count * (STEPS + 1) * 2, vertex);
+            let indices = arena.alloc_slice_fill(count * STEPS * 6, 0);
+
+            for slice in 0..count {
+                let color = if self.selected == Some(slice) {
+                    self.selected_color
+                } else {
+                    self.color
+                };
+                let start = (slice as f32 - 0.5) * step;
+                let base = slice * (STEPS + 1) * 2;
+                for i in 0..=STEPS {
+                    let along = direction(start + step * i as f32 / STEPS as f32);
+                    // Push the edges in, so there's an even gap between slices
+                    let side = if i == 0 {
+                        along.perp() * self.gap / 2.
+                    } else if i == STEPS {
+                        -along.perp() * self.gap / 2.
+                    } else {
+                        Vec2::ZERO
+                    };
+                    for (j, radius) in [self.inner_radius, self.radius].into_iter().enumerate() {
+                        let vertex = &mut verts[base + i * 2 + j];
+                        vertex.coord = transform.transform_point(along * radius + side);
+                        vertex.color = color;
+                    }
+                }
+                for i in 0..STEPS {
+                    let a = (base + i * 2) as i32;
+                    let quad = [a, a + 1, a + 2, a + 2, a + 1, a + 3];
+                    let first = (slice * STEPS + i) * 6;
+                    indices[first..first + 6].copy_from_slice(&quad);
+                }
+            }
+
+            canvas.draw_colored_geometry(verts, Some(indices));
+        });
+
+        let middle = self.inner_radius.midpoint(self.radius);
+        for (slice, icon) in self.icons.iter().enumerate() {
+            if let Some(icon) = icon {
+                let position = direction(slice as f32 * step) * middle;
+                icon.draw(canvas, transform.translate(position.round()));
+            }
+        }
+    }
+}
+
+/// Shows the keys and buttons being pressed, for recording tutorials, streams and bug reports.
+///
+/// Quick taps stay on screen for a moment after they're released, so they show up in recordings
+/// no matter the frame rate.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let font = Font::empty();
+/// use baba::hud::InputOverlay;
+///
+/// let mut overlay = InputOverlay::new(&font).with_anchor(Origin::BOTTOM_LEFT);
+///
+/// // Every frame
+/// if is_key_pressed(KeyCode::F8) {
+///     overlay.toggle();
+/// }
+/// overlay.update();
+///
+/// // After drawing everything else
+/// overlay.draw();
+/// ```
+#[must_use]
+#[derive(Clone)]
+pub struct InputOverlay {
+    font: Font,
+    anchor: Origin,
+    margin: f32,
+    padding: f32,
+    linger: f32,
+    color: Color,
+    background: Color,
+    visible: bool,
+    entries: Vec<OverlayEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct OverlayEntry {
+    label: String,
+    alpha: f32,
+}
+
+impl InputOverlay {
+    /// Creates an overlay, with labels drawn in a font. It's visible to begin with.
+    pub fn new(font: &Font) -> Self {
+        Self {
+            font: font.clone(),
+            anchor: Origin::BOTTOM_LEFT,
+            margin: 8.,
+            padding: 3.,
+            linger: 0.3,
+            color: Color::WHITE,
+            background: Color::from_black_alpha(180),
+            visible: true,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Sets which part of the screen the overlay is placed on, like
+    /// [`Origin::BOTTOM_RIGHT`]. Defaults to the bottom left.
+    pub const fn with_anchor(mut self, anchor: Origin) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Sets the space between the overlay and the edges of the screen. Defaults to 8.
+    pub const fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets how long released keys stay on screen, in seconds. Defaults to 0.3.
+    pub const fn with_linger(mut self, linger: f32) -> Self {
+        self.linger = linger;
+        self
+    }
+
+    /// Sets the color of the labels and the boxes behind them.
+    pub const fn with_colors(mut self, text: Color, background: Color) -> Self {
+        self.color = text;
+        self.background = background;
+        self
+    }
+
+    /// Shows or hides the overlay.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        if !visible {
+            self.entries.clear();
+        }
+    }
+
+    /// Shows the overlay if it's hidden, or hides it if it's shown.
+    pub fn toggle(&mut self) {
+        self.set_visible(!self.visible);
+    }
+
+    /// Is the overlay being shown?
+    #[must_use]
+    pub const fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Reads which keys and buttons are held. This should be called every frame.
+    pub fn update(&mut self) {
+        if !self.visible {
+            return;
+        }
+        let keys = input::get_held_keys().map(|key| format!("{key:?}"));
+        let mouse = [
+            MouseButton::Left,
+            MouseButton::Middle,
+            MouseButton::Right,
+            MouseButton::X1,
+            MouseButton::X2,
+        ]
+        .into_iter()
+        .filter(|&button| input::is_mouse_down(button))
+        .map(|button| format!("Mouse {button:?}"));
+        let buttons = gamepad::connected()
+            .flat_map(gamepad::get_held_buttons)
+            .map(|button| format!("{button:?}"));
+        let held: Vec<String> = keys.chain(mouse).chain(buttons).collect();
+
+        let fade = if self.linger > 0. {
+            time::delta() / self.linger
+        } else {
+            1.
+        };
+        for entry in &mut self.entries {
+            entry.alpha = if held.contains(&entry.label) {
+                1.
+            } else {
+                entry.alpha - fade
+            };
+        }
+        self.entries.retain(|entry| entry.alpha > 0.);
+        for label in held {
+            if !self.entries.iter().any(|entry| entry.label == label) {
+                self.entries.push(OverlayEntry { label, alpha: 1. });
+            }
+        }
+    }
+
+    /// Draws the overlay, if it's visible. This is in screen coordinates, so draw it outside of
+    /// [`with_camera`][gfx::with_camera].
+    pub fn draw(&self) {
+        if !self.visible || self.entries.is_empty() {
+            return;
+        }
+        let spacing = self.padding;
+        let sizes: Vec<Vec2> = self
+            .entries
+            .iter()
+            .map(|entry| self.font.measure(&entry.label) + self.padding * 2.)
+            .collect();
+        let total = vec2(
+            sizes.iter().map(|size| size.x).sum::<f32>() + spacing * (sizes.len() - 1) as f32,
+            sizes.iter().map(|size| size.y).fold(0., f32::max),
+        );
+
+        let area = (gfx::screen_size() - self.margin * 2.).max(Vec2::ZERO);
+        let mut position = (area * self.anchor.0 + self.margin - total * self.anchor.0).round();
+        for (entry, size) in self.entries.iter().zip(&sizes) {
+            let background = Shape::rect(*size).color(self.background.gamma_multiply(entry.alpha));
+            gfx::draw(&background, position);
+            let text =
+                Text::new(&self.font, &entry.label).color(self.color.gamma_multiply(entry.alpha));
+            gfx::draw(&text, position + self.padding);
+            position.x += size.x + spacing;
+        }
+    }
+}
+
+/// A screen for players to set the brightness for their display, by adjusting it until a dark
+/// logo is barely visible.
+///
+/// Left and right adjust the brightness, with the arrow keys or a controller's d-pad, and it's
+/// applied right away with [`gfx::set_brightness`]. Enter, space or the A button confirm it.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let (font, logo) = (Font::empty(), Texture::empty());
+/// # let mut saved_brightness = 0.;
+/// use baba::hud::BrightnessCalibration;
+///
+/// let mut calibration = BrightnessCalibration::new(&font).with_logo(&logo);
+///
+/// // Every frame, while the screen is open
+/// if calibration.update() {
+///     saved_brightness = calibration.value();
+///     // Save the settings, and close the screen
+/// }
+/// calibration.draw();
+/// ```
+///
+/// Next time the game starts, pass the saved value to [`Game::brightness`][crate::Game::brightness].
+#[must_use]
+#[derive(Clone)]
+pub struct BrightnessCalibration {
+    font: Font,
+    logo: Option<Texture>,
+    text: String,
+    step: f32,
+}
+
+impl BrightnessCalibration {
+    /// Creates a calibration screen, with text drawn in a font.
+    pub fn new(font: &Font) -> Self {
+        Self {
+            font: font.clone(),
+            logo: None,
+            text: "Adjust the brightness until the logo is barely visible".to_owned(),
+            step: 0.02,
+        }
+    }
+
+    /// Sets the logo to show. It's drawn in a very dark gray, so it should be white. By default,
+    /// a circle is shown.
+    pub fn with_logo(mut self, logo: &Texture) -> Self {
+        self.logo = Some(logo.clone().with_origin(Origin::CENTER));
+        self
+    }
+
+    /// Sets the instructions shown below the logo, like in another language.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Sets how much each press changes the brightness. Defaults to 0.02.
+    pub const fn with_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// The chosen brightness, to save in the player's settings.
+    #[must_use]
+    pub fn value(&self) -> f32 {
+        gfx::brightness()
+    }
+
+    /// Adjusts the brightness from input. Returns `true` when the player confirms it. This should
+    /// be called every frame while the screen is open.
+    pub fn update(&mut self) -> bool {
+        let pads: Vec<usize> = gamepad::connected().collect();
+        let pressed = |key, button| {
+            input::is_key_pressed(key)
+                || pads
+                    .iter()
+                    .any(|&pad| gamepad::is_button_pressed(pad, button))
+        };
+
+        let mut change = 0.;
+        if pressed(KeyCode::Left, Button::DPadLeft) {
+            change -= self.step;
+        }
+        if pressed(KeyCode::Right, Button::DPadRight) {
+            change += self.step;
+        }
+        if change != 0. {
+            gfx::set_brightness((gfx::brightness() + change).clamp(-0.5, 0.5));
+        }
+
+        pressed(KeyCode::Return, Button::A) || input::is_key_pressed(KeyCode::Space)
+    }
+
+    /// Draws the screen, covering everything else. This is in screen coordinates, so draw it
+    /// outside of [`with_camera`][gfx::with_camera].
+    pub fn draw(&self) {
+        let screen = gfx::screen_size();
+        let center = (screen / 2.).round();
+        gfx::draw(&Shape::rect(screen).color(Color::BLACK), Vec2::ZERO);
+
+        // Dark enough to disappear on displays that are too dark
+        let shade = Color::from_gray(12);
+        match &self.logo {
+            Some(logo) => gfx::draw(&logo.tinted(shade), center),
+            None => gfx::draw(
+                &Shape::circle(screen.min_element() / 8.).color(shade),
+                center,
+            ),
+        }
+
+        let lines = [
+            self.text.clone(),
+            format!("< {:+.0}% >", gfx::brightness() * 100.),
+        ];
+        let mut y = center.y + screen.y / 4.;
+        for line in &lines {
+            let size = self.font.measure(line);
+            let position = vec2(center.x - size.x / 2., y).round();
+            gfx::draw(&Text::new(&self.font, line).color(Color::GRAY), position);
+            y += size.y + 4.;
+        }
+    }
+}
+
+/// Called when [`Cinematic`] bars finish moving, with whether they're now in.
+type CinematicCallback = Box<dyn FnMut(bool)>;
+
+/// Black bars which slide in at the top and bottom of the screen for cutscenes, and can lock
+/// input so the player can only skip.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// use baba::hud::Cinematic;
+///
+/// let mut cinematic = Cinematic::new()
+///     .with_allowed_keys(&[KeyCode::Escape])
+///     .on_finished(|entered| {
+///         if !entered {
+///             // Give control back to the player
+///         }
+///     });
+/// cinematic.start();
+///
+/// // Every frame
+/// cinematic.update();
+/// if is_key_pressed(KeyCode::Escape) {
+///     cinematic.stop();
+/// }
+/// cinematic.draw();
+/// ```
+///
+/// Only the keyboard and controllers are locked, not the mouse. Input is unlocked when the
+/// cinematic is stopped or dropped.
+#[must_use]
+pub struct Cinematic {
+    height: f32,
+    duration: f32,
+    ease: Ease,
+    color: Color,
+    progress: f32,
+    active: bool,
+    allowed_keys: Option<Vec<KeyCode>>,
+    allowed_buttons: Option<Vec<Button>>,
+    on_finished: Option<CinematicCallback>,
+}
+
+impl Default for Cinematic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cinematic {
+    /// Creates bars which are out of the screen, until [`start`][Self::start] is called.
+    pub const fn new() -> Self {
+        Self {
+            height: 0.12,
+            duration: 0.6,
+            ease: Ease::InOutCubic,
+            color: Color::BLACK,
+            progress: 0.,
+            active: false,
+            allowed_keys: None,
+            allowed_buttons: None,
+            on_finished: None,
+        }
+    }
+
+    /// Sets the height of each bar, as a fraction of the screen's height. Defaults to 0.12.
+    pub const fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets how long the bars take to slide in or out, in seconds. Defaults to 0.6.
+    pub const fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets how the bars move. Defaults to [`Ease::InOutCubic`].
+    pub const fn with_ease(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    /// Sets the color of the bars. Defaults to black.
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Locks the keyboard while the bars are in, except for these keys. See
+    /// [`input::set_allowed_keys`].
+    pub fn with_allowed_keys(mut self, keys: &[KeyCode]) -> Self {
+        self.allowed_keys = Some(keys.to_vec());
+        self
+    }
+
+    /// Locks controllers while the bars are in, except for these buttons. See
+    /// [`gamepad::set_allowed_buttons`].
+    pub fn with_allowed_buttons(mut self, buttons: &[Button]) -> Self {
+        self.allowed_buttons = Some(buttons.to_vec());
+        self
+    }
+
+    /// Calls a function when the bars finish sliding, with `true` once they're in and `false`
+    /// once they're out.
+    pub fn on_finished(mut self, f: impl FnMut(bool) + 'static) -> Self {
+        self.on_finished = Some(Box::new(f));
+        self
+    }
+
+    /// Slides the bars in, and locks input if allowed keys or buttons were set. The mouse isn't
+    /// locked.
+    pub fn start(&mut self) {
+        self.active = true;
+        if let Some(keys) = &self.allowed_keys {
+            input::set_allowed_keys(Some(keys));
+        }
+        if let Some(buttons) = &self.allowed_buttons {
+            gamepad::set_allowed_buttons(Some(buttons));
+        }
+    }
+
+    /// Slides the bars out, and unlocks input right away.
+    pub fn stop(&mut self) {
+        self.active = false;
+        if self.allowed_keys.is_some() {
+            input::set_allowed_keys(None);
+        }
+        if self.allowed_buttons.is_some() {
+            gamepad::set_allowed_buttons(None);
+        }
+    }
+
+    /// Are the bars in, or sliding in?
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Are the bars sliding in or out?
+    #[must_use]
+    pub fn is_animating(&self) -> bool {
+        let target = if self.active { 1. } else { 0. };
+        (self.progress - target).abs() > f32::EPSILON
+    }
+
+    /// How far in the bars are, from 0 when they're out to 1 when they're in.
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        self.ease.apply(self.progress)
+    }
+
+    /// Slides the bars. This should be called every frame.
+    pub fn update(&mut self) {
+        if !self.is_animating() {
+            return;
+        }
+        let step = time::delta() / self.duration.max(f32::EPSILON);
+        self.progress = if self.active {
+            (self.progress + step).min(1.)
+        } else {
+            (self.progress - step).max(0.)
+        };
+        if !self.is_animating() {
+            if let Some(f) = &mut self.on_finished {
+                f(self.active);
+            }
+        }
+    }
+
+    /// Draws the bars over the whole screen. This is in screen coordinates, so draw it outside
+    /// of [`with_camera`][gfx::with_camera].
+    pub fn draw(&self) {
+        let screen = gfx::screen_size();
+        let height = (screen.y * self.height * self.progress()).round();
+        if height <= 0. {
+            return;
+        }
+        let bar = Shape::rect(vec2(screen.x, height)).color(self.color);
+        gfx::draw(&bar, Vec2::ZERO);
+        gfx::draw(&bar, vec2(0., screen.y - height));
+    }
+}
+
+impl Drop for Cinematic {
+    fn drop(&mut self) {
+        // Don't leave input locked if the cutscene is thrown away, like when changing scenes
+        if self.active {
+            self.stop();
+        }
+    }
+}
@@ -1,35 +1,100 @@
 //! Input handling.
 //!
 //! Currently provides keyboard support with [`is_key_pressed`], [`is_key_down`],
-//! [`get_pressed_keys`] and [`get_held_keys`]
+//! [`is_key_released`], [`get_pressed_keys`], [`get_held_keys`] and [`get_released_keys`], and
+//! mouse support with [`mouse_position`],
+//! [`mouse_wheel`], [`is_mouse_pressed`] and [`is_mouse_down`]. Touch screens are supported with
+//! [`touches`], and game controllers through the [`gamepad`] module. Typed text is available with
+//! [`text`], and can be edited with a [`TextEdit`].
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
+use glam::Vec2;
 use parking_lot::Mutex;
 
+use crate::time;
+
+pub mod gamepad;
 mod keycode;
+mod modifiers;
+mod mouse;
+mod text_edit;
 pub use keycode::KeyCode;
+pub use modifiers::Modifiers;
+pub use mouse::MouseButton;
+pub use text_edit::TextEdit;
 
 struct InputState {
     pressed: BTreeSet<KeyCode>,
     just_pressed: BTreeSet<KeyCode>,
+    just_released: BTreeSet<KeyCode>,
+    just_repeated: BTreeSet<KeyCode>,
+    text: String,
+    allowed: Option<Vec<KeyCode>>,
+    modifiers: Modifiers,
+    mouse_position: Vec2,
+    mouse_wheel: Vec2,
+    mouse_pressed: BTreeSet<MouseButton>,
+    mouse_just_pressed: BTreeSet<MouseButton>,
+    touches: BTreeMap<i64, Vec2>,
+    history: VecDeque<(KeyCode, f32)>,
+    history_fresh: usize,
+}
+
+impl InputState {
+    /// Is this key let through by [`set_allowed_keys`]?
+    fn allows(&self, key: KeyCode) -> bool {
+        self.allowed.as_ref().is_none_or(|keys| keys.contains(&key))
+    }
 }
 
+/// How many key presses are remembered for [`sequence`].
+const HISTORY_LEN: usize = 64;
+
 static INPUT_STATE: Mutex<InputState> = Mutex::new(InputState {
     pressed: BTreeSet::new(),
     just_pressed: BTreeSet::new(),
+    just_released: BTreeSet::new(),
+    just_repeated: BTreeSet::new(),
+    text: String::new(),
+    allowed: None,
+    modifiers: Modifiers::NONE,
+    mouse_position: Vec2::ZERO,
+    mouse_wheel: Vec2::ZERO,
+    mouse_pressed: BTreeSet::new(),
+    mouse_just_pressed: BTreeSet::new(),
+    touches: BTreeMap::new(),
+    history: VecDeque::new(),
+    history_fresh: 0,
 });
 
 /// Was this key pressed this frame?
 #[must_use]
 pub fn is_key_pressed(key: KeyCode) -> bool {
-    INPUT_STATE.lock().just_pressed.contains(&key)
+    let input = INPUT_STATE.lock();
+    input.allows(key) && input.just_pressed.contains(&key)
 }
 
 /// Is this key being held down?
 #[must_use]
 pub fn is_key_down(key: KeyCode) -> bool {
-    INPUT_STATE.lock().pressed.contains(&key)
+    let input = INPUT_STATE.lock();
+    input.allows(key) && input.pressed.contains(&key)
+}
+
+/// Was this key released this frame?
+#[must_use]
+pub fn is_key_released(key: KeyCode) -> bool {
+    let input = INPUT_STATE.lock();
+    input.allows(key) && input.just_released.contains(&key)
+}
+
+/// Was this key pressed this frame, or repeated by the system while held down? This is the usual
+/// for keys that move through menus or text.
+#[must_use]
+pub fn is_key_repeated(key: KeyCode) -> bool {
+    let input = INPUT_STATE.lock();
+    input.allows(key) && (input.just_pressed.contains(&key) || input.just_repeated.contains(&key))
 }
 
 /// Get a list of keys pressed within this frame.
@@ -42,6 +107,80 @@ pub fn get_held_keys() -> impl ExactSizeIterator<Item = KeyCode> {
     INPUT_STATE.lock().pressed.clone().into_iter()
 }
 
+/// Get a list of keys released within this frame.
+pub fn get_released_keys() -> impl ExactSizeIterator<Item = KeyCode> {
+    INPUT_STATE.lock().just_released.clone().into_iter()
+}
+
+/// Were these keys just pressed one after another, like a cheat code?
+///
+/// This is `true` on the frame the last key is pressed, if the keys before it were the latest
+/// ones pressed, with at most `timeout` seconds between each of them. Only the last 64 key presses
+/// are remembered.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// use KeyCode::*;
+///
+/// const KONAMI: &[KeyCode] = &[Up, Up, Down, Down, Left, Right, Left, Right, B, A];
+/// if input::sequence(KONAMI, 1.) {
+///     // 30 lives!
+/// }
+/// ```
+#[must_use]
+pub fn sequence(keys: &[KeyCode], timeout: f32) -> bool {
+    let input = INPUT_STATE.lock();
+    let history = &input.history;
+    let count = keys.len();
+    if count == 0 || count > history.len() {
+        return false;
+    }
+    // Any key pressed this frame could finish the sequence
+    (history.len() - input.history_fresh..history.len()).any(|end| {
+        let Some(start) = (end + 1).checked_sub(count) else {
+            return false;
+        };
+        let matches = (start..=end).all(|i| history[i].0 == keys[i - start]);
+        matches && (start + 1..=end).all(|i| history[i].1 - history[i - 1].1 <= timeout)
+    })
+}
+
+/// Only lets some keys through, like during a cutscene where only skipping is allowed. Other keys
+/// read as not pressed, and no [`text`] is typed. `None` lets every key through again.
+///
+/// This doesn't affect the lists of keys, like [`get_pressed_keys`].
+pub fn set_allowed_keys(keys: Option<&[KeyCode]>) {
+    INPUT_STATE.lock().allowed = keys.map(<[KeyCode]>::to_vec);
+}
+
+/// Text typed this frame, following the keyboard layout and input methods.
+///
+/// This is what should go in text fields, rather than checking keys one by one.
+#[must_use]
+pub fn text() -> String {
+    let input = INPUT_STATE.lock();
+    if input.allowed.is_some() {
+        return String::new();
+    }
+    input.text.clone()
+}
+
+/// Simulate typing some text.
+pub fn type_text(text: &str) {
+    INPUT_STATE.lock().text.push_str(text);
+}
+
+/// Modifier keys currently being held down.
+#[must_use]
+pub fn modifiers() -> Modifiers {
+    INPUT_STATE.lock().modifiers
+}
+
+/// Simulate holding modifier keys. This is updated on every key event.
+pub fn set_modifiers(modifiers: Modifiers) {
+    INPUT_STATE.lock().modifiers = modifiers;
+}
+
 /// Simulate pressing a key.
 ///
 /// [`is_key_pressed`] will return `true` for this frame, and [`is_key_down`] will return `true` until you call [`release_key`].
@@ -49,18 +188,128 @@ pub fn press_key(key: KeyCode) {
     let mut input = INPUT_STATE.lock();
     input.pressed.insert(key);
     input.just_pressed.insert(key);
+
+    if input.history.len() == HISTORY_LEN {
+        input.history.pop_front();
+    }
+    input.history.push_back((key, time::elapsed()));
+    input.history_fresh = (input.history_fresh + 1).min(input.history.len());
+}
+
+/// Simulate the system repeating a key which is held down.
+///
+/// [`is_key_repeated`] will return `true` for this frame.
+pub fn repeat_key(key: KeyCode) {
+    INPUT_STATE.lock().just_repeated.insert(key);
 }
 
 /// Simulate releasing a key.
 ///
-/// [`is_key_down`] will stop returning `true` for this key.
+/// [`is_key_down`] will stop returning `true` for this key, and [`is_key_released`] will return
+/// `true` for this frame.
 pub fn release_key(key: KeyCode) {
-    INPUT_STATE.lock().pressed.remove(&key);
+    let mut input = INPUT_STATE.lock();
+    input.pressed.remove(&key);
+    input.just_released.insert(key);
+}
+
+/// Current position of the mouse cursor.
+///
+/// If a [`Viewport`][crate::gfx::Viewport] is set, this is in viewport coordinates: the window's
+/// scaling and letterboxing are already undone, so it can be compared with what's drawn no matter
+/// the window size. Use [`gfx::logical_to_window`][crate::gfx::logical_to_window] to get window
+/// pixels.
+#[must_use]
+pub fn mouse_position() -> Vec2 {
+    INPUT_STATE.lock().mouse_position
 }
 
-/// Clears all keys pressed this frame.
+/// How far the mouse wheel was scrolled this frame.
 ///
-/// Data for [`is_key_pressed`] will be cleared.
+/// `y` is positive when scrolling away from the user, and `x` is positive when scrolling to the
+/// right. Most mice only have a vertical wheel, but touchpads can scroll both ways.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let mut camera = Camera::default();
+/// camera.zoom *= 1.1f32.powf(input::mouse_wheel().y);
+/// ```
+#[must_use]
+pub fn mouse_wheel() -> Vec2 {
+    INPUT_STATE.lock().mouse_wheel
+}
+
+/// Was this mouse button pressed this frame?
+#[must_use]
+pub fn is_mouse_pressed(button: MouseButton) -> bool {
+    INPUT_STATE.lock().mouse_just_pressed.contains(&button)
+}
+
+/// Is this mouse button being held down?
+#[must_use]
+pub fn is_mouse_down(button: MouseButton) -> bool {
+    INPUT_STATE.lock().mouse_pressed.contains(&button)
+}
+
+/// Simulate moving the mouse.
+pub fn move_mouse(position: Vec2) {
+    INPUT_STATE.lock().mouse_position = position;
+}
+
+/// Simulate scrolling the mouse wheel. This adds to the amount scrolled this frame.
+pub fn scroll_mouse(delta: Vec2) {
+    INPUT_STATE.lock().mouse_wheel += delta;
+}
+
+/// Simulate pressing a mouse button.
+///
+/// [`is_mouse_pressed`] will return `true` for this frame, and [`is_mouse_down`] will return
+/// `true` until you call [`release_mouse`].
+pub fn press_mouse(button: MouseButton) {
+    let mut input = INPUT_STATE.lock();
+    input.mouse_pressed.insert(button);
+    input.mouse_just_pressed.insert(button);
+}
+
+/// Simulate releasing a mouse button.
+///
+/// [`is_mouse_down`] will stop returning `true` for this button.
+pub fn release_mouse(button: MouseButton) {
+    INPUT_STATE.lock().mouse_pressed.remove(&button);
+}
+
+/// Positions of every finger touching the screen, in the same coordinates as
+/// [`mouse_position`].
+///
+/// Touching with one finger also moves the mouse and presses the left mouse button, so games that
+/// only need single taps or drags don't have to handle touches at all.
+pub fn touches() -> impl ExactSizeIterator<Item = Vec2> {
+    INPUT_STATE.lock().touches.clone().into_values()
+}
+
+/// Simulate a finger touching the screen, or moving if it's already touching. Each finger has its
+/// own `id`.
+pub fn touch(id: i64, position: Vec2) {
+    INPUT_STATE.lock().touches.insert(id, position);
+}
+
+/// Simulate a finger being lifted from the screen.
+pub fn release_touch(id: i64) {
+    INPUT_STATE.lock().touches.remove(&id);
+}
+
+/// Clears all keys and buttons pressed this frame.
+///
+/// Data for [`is_key_pressed`], [`is_key_released`], [`is_key_repeated`], [`text`],
+/// [`is_mouse_pressed`], [`mouse_wheel`] and [`gamepad::is_button_pressed`] will be cleared.
 pub fn clear() {
-    INPUT_STATE.lock().just_pressed.clear();
+    let mut input = INPUT_STATE.lock();
+    input.just_pressed.clear();
+    input.just_released.clear();
+    input.just_repeated.clear();
+    input.text.clear();
+    input.mouse_just_pressed.clear();
+    input.mouse_wheel = Vec2::ZERO;
+    input.history_fresh = 0;
+    gamepad::clear();
 }
@@ -0,0 +1,394 @@
+//! Game controller support.
+//!
+//! Controllers are identified by an index, which is assigned when they're connected. The first
+//! controller is `0`, the second is `1` and so on. If a controller is disconnected, its index
+//! is reused by the next one.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! use baba::input::gamepad::{self, Button};
+//!
+//! for pad in gamepad::connected() {
+//!     let movement = gamepad::left_stick(pad);
+//!     if gamepad::is_button_pressed(pad, Button::A) {
+//!         info!("Player {pad} jumped!");
+//!     }
+//! }
+//! ```
+//!
+//! The controller the player pressed a button on last is the [`active`] one. If it's
+//! disconnected, an [`ActiveDisconnected`] [event][crate::events] is sent, so the game can pause
+//! and ask for it back. [`Game::pause_on_disconnect`][crate::Game::pause_on_disconnect] does this
+//! automatically.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::ffi::CStr;
+use std::ptr::NonNull;
+
+use glam::{vec2, Vec2};
+use sdl2::GameControllerSubsystem;
+use sdl2_sys::{
+    SDL_GameController, SDL_GameControllerAxis, SDL_GameControllerClose, SDL_GameControllerGetAxis,
+    SDL_GameControllerGetJoystick, SDL_GameControllerName, SDL_GameControllerOpen,
+    SDL_JoystickInstanceID,
+};
+
+use crate::{events, SdlError};
+
+/// A button on a controller. Uses Xbox names.
+#[repr(u8)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, bytemuck::CheckedBitPattern)]
+pub enum Button {
+    /// Bottom face button. Cross on `PlayStation` controllers.
+    A = 0,
+    /// Right face button. Circle on `PlayStation` controllers.
+    B = 1,
+    /// Left face button. Square on `PlayStation` controllers.
+    X = 2,
+    /// Top face button. Triangle on `PlayStation` controllers.
+    Y = 3,
+    /// Back or select button.
+    Back = 4,
+    /// Guide or home button.
+    Guide = 5,
+    /// Start button.
+    Start = 6,
+    /// Pressing the left stick.
+    LeftStick = 7,
+    /// Pressing the right stick.
+    RightStick = 8,
+    /// Left bumper.
+    LeftShoulder = 9,
+    /// Right bumper.
+    RightShoulder = 10,
+    /// D-pad up.
+    DPadUp = 11,
+    /// D-pad down.
+    DPadDown = 12,
+    /// D-pad left.
+    DPadLeft = 13,
+    /// D-pad right.
+    DPadRight = 14,
+    /// Extra button, like share or capture.
+    Misc = 15,
+    /// First back paddle.
+    Paddle1 = 16,
+    /// Second back paddle.
+    Paddle2 = 17,
+    /// Third back paddle.
+    Paddle3 = 18,
+    /// Fourth back paddle.
+    Paddle4 = 19,
+    /// Touchpad click.
+    Touchpad = 20,
+}
+
+/// An analog input on a controller.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum Axis {
+    /// Left stick, horizontal. Ranges from -1 (left) to 1 (right).
+    LeftX,
+    /// Left stick, vertical. Ranges from -1 (up) to 1 (down).
+    LeftY,
+    /// Right stick, horizontal. Ranges from -1 (left) to 1 (right).
+    RightX,
+    /// Right stick, vertical. Ranges from -1 (up) to 1 (down).
+    RightY,
+    /// Left trigger. Ranges from 0 to 1.
+    TriggerLeft,
+    /// Right trigger. Ranges from 0 to 1.
+    TriggerRight,
+}
+
+impl Axis {
+    const fn raw(self) -> SDL_GameControllerAxis {
+        match self {
+            Self::LeftX => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_LEFTX,
+            Self::LeftY => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_LEFTY,
+            Self::RightX => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_RIGHTX,
+            Self::RightY => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_RIGHTY,
+            Self::TriggerLeft => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_TRIGGERLEFT,
+            Self::TriggerRight => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_TRIGGERRIGHT,
+        }
+    }
+}
+
+/// Sent as an [event][crate::events] when the [`active`] controller is disconnected.
+///
+/// ```no_run
+/// use baba::input::gamepad::ActiveDisconnected;
+///
+/// # let mut paused = false;
+/// if baba::events::drain::<ActiveDisconnected>().len() > 0 {
+///     paused = true;
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveDisconnected {
+    /// Index the controller had.
+    pub pad: usize,
+}
+
+struct Gamepad {
+    controller: NonNull<SDL_GameController>,
+    instance: i32,
+    pressed: BTreeSet<Button>,
+    just_pressed: BTreeSet<Button>,
+}
+
+impl Drop for Gamepad {
+    fn drop(&mut self) {
+        unsafe { SDL_GameControllerClose(self.controller.as_ptr()) };
+    }
+}
+
+struct GamepadState {
+    #[allow(dead_code)] // keeps the subsystem initialised
+    subsystem: Option<GameControllerSubsystem>,
+    pads: Vec<Option<Gamepad>>,
+    connected: Vec<usize>,
+    disconnected: Vec<usize>,
+    active: Option<usize>,
+    active_lost: bool,
+    allowed: Option<Vec<Button>>,
+}
+
+thread_local! {
+    static GAMEPADS: RefCell<GamepadState> = const {
+        RefCell::new(GamepadState {
+            subsystem: None,
+            pads: Vec::new(),
+            connected: Vec::new(),
+            disconnected: Vec::new(),
+            active: None,
+            active_lost: false,
+            allowed: None,
+        })
+    };
+}
+
+fn with_pad<T>(pad: usize, f: impl FnOnce(&Gamepad) -> T) -> Option<T> {
+    GAMEPADS.with_borrow(|state| state.pads.get(pad)?.as_ref().map(f))
+}
+
+/// Is this button let through by [`set_allowed_buttons`]?
+fn allows(button: Button) -> bool {
+    GAMEPADS.with_borrow(|state| {
+        state
+            .allowed
+            .as_ref()
+            .is_none_or(|buttons| buttons.contains(&button))
+    })
+}
+
+/// Only lets some buttons through, on every controller. Other buttons read as not pressed.
+/// `None` lets every button through again. See [`input::set_allowed_keys`][super::set_allowed_keys].
+pub fn set_allowed_buttons(buttons: Option<&[Button]>) {
+    GAMEPADS.with_borrow_mut(|state| state.allowed = buttons.map(<[Button]>::to_vec));
+}
+
+/// Get a list of connected controllers.
+#[must_use]
+pub fn connected() -> impl ExactSizeIterator<Item = usize> {
+    GAMEPADS.with_borrow(|state| {
+        let pads = state.pads.iter().enumerate();
+        let pads: Vec<_> = pads.filter(|(_, p)| p.is_some()).map(|(i, _)| i).collect();
+        pads.into_iter()
+    })
+}
+
+/// Get a list of controllers connected this frame.
+#[must_use]
+pub fn just_connected() -> impl ExactSizeIterator<Item = usize> {
+    GAMEPADS.with_borrow(|state| state.connected.clone().into_iter())
+}
+
+/// Get a list of controllers disconnected this frame.
+#[must_use]
+pub fn just_disconnected() -> impl ExactSizeIterator<Item = usize> {
+    GAMEPADS.with_borrow(|state| state.disconnected.clone().into_iter())
+}
+
+/// The controller a button was pressed on last, if it's still connected. This is the one to
+/// listen to in single player games.
+#[must_use]
+pub fn active() -> Option<usize> {
+    GAMEPADS.with_borrow(|state| state.active)
+}
+
+/// Is this controller connected?
+#[must_use]
+pub fn is_connected(pad: usize) -> bool {
+    with_pad(pad, |_| ()).is_some()
+}
+
+/// The name of this controller, if it's connected.
+#[must_use]
+pub fn name(pad: usize) -> Option<String> {
+    with_pad(pad, |p| {
+        let name = unsafe { SDL_GameControllerName(p.controller.as_ptr()) };
+        if name.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(name) }
+                .to_string_lossy()
+                .into_owned()
+        }
+    })
+}
+
+/// Was this button pressed this frame?
+#[must_use]
+pub fn is_button_pressed(pad: usize, button: Button) -> bool {
+    allows(button) && with_pad(pad, |p| p.just_pressed.contains(&button)).unwrap_or(false)
+}
+
+/// Is this button being held down?
+#[must_use]
+pub fn is_button_down(pad: usize, button: Button) -> bool {
+    allows(button) && with_pad(pad, |p| p.pressed.contains(&button)).unwrap_or(false)
+}
+
+/// Get a list of buttons being held down on this controller.
+#[must_use]
+pub fn get_held_buttons(pad: usize) -> impl ExactSizeIterator<Item = Button> {
+    with_pad(pad, |p| p.pressed.clone())
+        .unwrap_or_default()
+        .into_iter()
+}
+
+/// Current value of an axis. This is `0` if the controller isn't connected.
+///
+/// See [`Axis`] for the ranges of each axis.
+#[must_use]
+pub fn axis(pad: usize, axis: Axis) -> f32 {
+    with_pad(pad, |p| {
+        let value = unsafe { SDL_GameControllerGetAxis(p.controller.as_ptr(), axis.raw()) };
+        (f32::from(value) / f32::from(i16::MAX)).max(-1.)
+    })
+    .unwrap_or(0.)
+}
+
+/// Position of the left stick.
+#[must_use]
+pub fn left_stick(pad: usize) -> Vec2 {
+    vec2(axis(pad, Axis::LeftX), axis(pad, Axis::LeftY))
+}
+
+/// Position of the right stick.
+#[must_use]
+pub fn right_stick(pad: usize) -> Vec2 {
+    vec2(axis(pad, Axis::RightX), axis(pad, Axis::RightY))
+}
+
+pub(crate) fn init(sdl: &sdl2::Sdl) {
+    match sdl.game_controller() {
+        Ok(subsystem) => GAMEPADS.with_borrow_mut(|state| state.subsystem = Some(subsystem)),
+        Err(e) => log::warn!("Failed to initialise controllers: {e}"),
+    }
+}
+
+pub(crate) fn connect(device: i32) {
+    let Some(controller) = NonNull::new(unsafe { SDL_GameControllerOpen(device) }) else {
+        log::warn!("Failed to open controller: {}", SdlError::from_sdl());
+        return;
+    };
+    let instance =
+        unsafe { SDL_JoystickInstanceID(SDL_GameControllerGetJoystick(controller.as_ptr())) };
+
+    GAMEPADS.with_borrow_mut(|state| {
+        // Opening the same device twice returns the same controller
+        if state.pads.iter().flatten().any(|p| p.instance == instance) {
+            unsafe { SDL_GameControllerClose(controller.as_ptr()) };
+            return;
+        }
+
+        let pad = Gamepad {
+            controller,
+            instance,
+            pressed: BTreeSet::new(),
+            just_pressed: BTreeSet::new(),
+        };
+        let index = state
+            .pads
+            .iter()
+            .position(Option::is_none)
+            .unwrap_or_else(|| {
+                state.pads.push(None);
+                state.pads.len() - 1
+            });
+        state.pads[index] = Some(pad);
+
+        log::info!("Controller {index} connected");
+        state.connected.push(index);
+    });
+}
+
+pub(crate) fn disconnect(instance: i32) {
+    GAMEPADS.with_borrow_mut(|state| {
+        let is_instance = |p: &Option<Gamepad>| p.as_ref().is_some_and(|p| p.instance == instance);
+        if let Some(index) = state.pads.iter().position(is_instance) {
+            state.pads[index] = None;
+
+            log::info!("Controller {index} disconnected");
+            state.disconnected.push(index);
+            if state.active == Some(index) {
+                state.active = None;
+                state.active_lost = true;
+                events::emit(ActiveDisconnected { pad: index });
+            }
+        }
+    });
+}
+
+pub(crate) fn press_button(instance: i32, button: u8) {
+    let Ok(button) = bytemuck::checked::try_cast(button) else {
+        return;
+    };
+
+    GAMEPADS.with_borrow_mut(|state| {
+        let is_instance = |p: &Option<Gamepad>| p.as_ref().is_some_and(|p| p.instance == instance);
+        if let Some(index) = state.pads.iter().position(is_instance) {
+            let pad = state.pads[index].as_mut().unwrap();
+            pad.pressed.insert(button);
+            pad.just_pressed.insert(button);
+            state.active = Some(index);
+        }
+    });
+}
+
+pub(crate) fn release_button(instance: i32, button: u8) {
+    let Ok(button) = bytemuck::checked::try_cast(button) else {
+        return;
+    };
+
+    GAMEPADS.with_borrow_mut(|state| {
+        if let Some(pad) = state
+            .pads
+            .iter_mut()
+            .flatten()
+            .find(|p| p.instance == instance)
+        {
+            pad.pressed.remove(&button);
+        }
+    });
+}
+
+/// Was the active controller disconnected this frame?
+pub(crate) fn active_lost() -> bool {
+    GAMEPADS.with_borrow(|state| state.active_lost)
+}
+
+/// Clears all buttons pressed and controllers connected this frame.
+pub fn clear() {
+    GAMEPADS.with_borrow_mut(|state| {
+        state.active_lost = false;
+        state.connected.clear();
+        state.disconnected.clear();
+        for pad in state.pads.iter_mut().flatten() {
+            pad.just_pressed.clear();
+        }
+    });
+}
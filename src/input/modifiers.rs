@@ -0,0 +1,49 @@
+use sdl2_sys::SDL_Keymod;
+
+/// Modifier keys being held down, like Shift or Ctrl.
+///
+/// Left and right keys count as the same modifier.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// if input::modifiers().ctrl && is_key_pressed(KeyCode::S) {
+///     // Save
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Modifiers {
+    /// Either Shift key.
+    pub shift: bool,
+    /// Either Ctrl key.
+    pub ctrl: bool,
+    /// Either Alt key. This is Option on macOS.
+    pub alt: bool,
+    /// Either "GUI" key. This is the Windows key, or Command on macOS.
+    pub gui: bool,
+}
+
+impl Modifiers {
+    /// No modifiers.
+    pub const NONE: Self = Self {
+        shift: false,
+        ctrl: false,
+        alt: false,
+        gui: false,
+    };
+
+    /// Is no modifier being held?
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        !(self.shift || self.ctrl || self.alt || self.gui)
+    }
+
+    pub(crate) const fn from_sdl(mods: u16) -> Self {
+        Self {
+            shift: mods & SDL_Keymod::KMOD_SHIFT as u16 != 0,
+            ctrl: mods & SDL_Keymod::KMOD_CTRL as u16 != 0,
+            alt: mods & SDL_Keymod::KMOD_ALT as u16 != 0,
+            gui: mods & SDL_Keymod::KMOD_GUI as u16 != 0,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+/// A button on the mouse.
+#[repr(u8)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, bytemuck::CheckedBitPattern)]
+pub enum MouseButton {
+    /// Left mouse button.
+    Left = 1,
+    /// Middle mouse button, usually the scroll wheel.
+    Middle = 2,
+    /// Right mouse button.
+    Right = 3,
+    /// First extra button, usually "back".
+    X1 = 4,
+    /// Second extra button, usually "forward".
+    X2 = 5,
+}
@@ -0,0 +1,319 @@
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{is_key_repeated, modifiers, KeyCode};
+use crate::clipboard;
+
+/// Text being edited, with a caret and a selection, for text fields and chat or console boxes.
+///
+/// The caret moves by grapheme, so accented letters and emoji made of several characters are
+/// treated as one. [`update`][Self::update] handles typing and the usual editing keys, and
+/// drawing is left to the game.
+///
+/// ```no_run
+/// # use baba::prelude::*;
+/// # let font = Font::empty();
+/// use baba::input::TextEdit;
+///
+/// let mut name = TextEdit::new("Player");
+///
+/// // Every frame
+/// name.update();
+/// gfx::draw_text(&font, name.text(), vec2(10., 10.));
+/// let caret = font.measure(&name.text()[..name.caret()]).x;
+/// gfx::draw(&Shape::rect(vec2(1., font.line_height())), vec2(10. + caret, 10.));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextEdit {
+    text: String,
+    caret: usize,
+    anchor: usize,
+    max_len: Option<usize>,
+}
+
+impl TextEdit {
+    /// Starts editing some text, with the caret at the end.
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let caret = text.len();
+        Self {
+            text,
+            caret,
+            anchor: caret,
+            max_len: None,
+        }
+    }
+
+    /// Limits how many graphemes can be typed in.
+    #[must_use]
+    pub const fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// The text being edited.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces all of the text, moving the caret to the end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        *self = Self {
+            max_len: self.max_len,
+            ..Self::new(text)
+        };
+    }
+
+    /// Removes all of the text, returning it. This is handy for sending a chat message.
+    pub fn take(&mut self) -> String {
+        let text = std::mem::take(&mut self.text);
+        self.caret = 0;
+        self.anchor = 0;
+        text
+    }
+
+    /// Byte position of the caret in the text.
+    #[must_use]
+    pub const fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Byte range of the selected text, or `None` if nothing is selected.
+    #[must_use]
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let range = self.caret.min(self.anchor)..self.caret.max(self.anchor);
+        (!range.is_empty()).then_some(range)
+    }
+
+    /// The selected text, which is empty if nothing is selected.
+    #[must_use]
+    pub fn selected_text(&self) -> &str {
+        self.selection().map_or("", |range| &self.text[range])
+    }
+
+    /// Selects all of the text.
+    pub const fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.text.len();
+    }
+
+    /// Types some text at the caret, replacing the selection. Text over the
+    /// [maximum length][Self::with_max_len] is cut off.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let text = match self.max_len {
+            Some(max) => {
+                let room = max.saturating_sub(self.text.graphemes(true).count());
+                let end = text
+                    .grapheme_indices(true)
+                    .nth(room)
+                    .map_or(text.len(), |(i, _)| i);
+                &text[..end]
+            }
+            None => text,
+        };
+        self.text.insert_str(self.caret, text);
+        self.caret += text.len();
+        self.anchor = self.caret;
+    }
+
+    /// Deletes the selection, or the grapheme before the caret.
+    pub fn backspace(&mut self) {
+        if !self.delete_selection() {
+            self.anchor = self.prev_boundary(self.caret);
+            self.delete_selection();
+        }
+    }
+
+    /// Deletes the selection, or the grapheme after the caret.
+    pub fn delete(&mut self) {
+        if !self.delete_selection() {
+            self.anchor = self.next_boundary(self.caret);
+            self.delete_selection();
+        }
+    }
+
+    /// Moves the caret one grapheme to the left. If `select` is `true`, the selection is
+    /// extended, otherwise it's cleared.
+    pub fn move_left(&mut self, select: bool) {
+        let to = match self.selection() {
+            Some(range) if !select => range.start,
+            _ => self.prev_boundary(self.caret),
+        };
+        self.move_to(to, select);
+    }
+
+    /// Moves the caret one grapheme to the right, like [`move_left`][Self::move_left].
+    pub fn move_right(&mut self, select: bool) {
+        let to = match self.selection() {
+            Some(range) if !select => range.end,
+            _ => self.next_boundary(self.caret),
+        };
+        self.move_to(to, select);
+    }
+
+    /// Moves the caret to the start of the previous word.
+    pub fn move_word_left(&mut self, select: bool) {
+        let to = self
+            .text
+            .split_word_bound_indices()
+            .rev()
+            .find(|(i, word)| *i < self.caret && !word.trim().is_empty())
+            .map_or(0, |(i, _)| i);
+        self.move_to(to, select);
+    }
+
+    /// Moves the caret to the end of the next word.
+    pub fn move_word_right(&mut self, select: bool) {
+        let to = self
+            .text
+            .split_word_bound_indices()
+            .map(|(i, word)| (i + word.len(), word))
+            .find(|(end, word)| *end > self.caret && !word.trim().is_empty())
+            .map_or(self.text.len(), |(end, _)| end);
+        self.move_to(to, select);
+    }
+
+    /// Moves the caret to the start of the text.
+    pub const fn move_home(&mut self, select: bool) {
+        self.move_to(0, select);
+    }
+
+    /// Moves the caret to the end of the text.
+    pub const fn move_end(&mut self, select: bool) {
+        self.move_to(self.text.len(), select);
+    }
+
+    /// Copies the selection to the clipboard.
+    pub fn copy(&self) {
+        let selected = self.selected_text();
+        if selected.is_empty() {
+            return;
+        }
+        if let Err(e) = clipboard::set(selected) {
+            log::error!("Failed to copy text: {e}");
+        }
+    }
+
+    /// Copies the selection to the clipboard, and deletes it.
+    pub fn cut(&mut self) {
+        self.copy();
+        self.delete_selection();
+    }
+
+    /// Types the text in the clipboard.
+    pub fn paste(&mut self) {
+        self.insert(&clipboard::get());
+    }
+
+    /// Handles typed [text][super::text] and editing keys for this frame: arrows to move, with
+    /// Shift to select and Ctrl (or Alt on macOS) to move by word, Home and End, Backspace and
+    /// Delete, and Ctrl (or Command on macOS) with A, C, X and V. Returns whether the text
+    /// changed.
+    pub fn update(&mut self) -> bool {
+        let before = self.text.len();
+        let mods = modifiers();
+        // AltGr is reported as Ctrl+Alt on Windows, and types text rather than shortcuts
+        let shortcut = (mods.ctrl && !mods.alt) || mods.gui;
+        let by_word = if cfg!(target_os = "macos") {
+            mods.alt
+        } else {
+            mods.ctrl
+        };
+        let mut changed = false;
+
+        // SDL doesn't send text for shortcuts, so typed text is always inserted
+        let typed = super::text();
+        if !typed.is_empty() {
+            self.insert(&typed);
+            changed = true;
+        }
+
+        if shortcut {
+            if is_key_repeated(KeyCode::A) {
+                self.select_all();
+            }
+            if is_key_repeated(KeyCode::C) {
+                self.copy();
+            }
+            if is_key_repeated(KeyCode::X) {
+                self.cut();
+            }
+            if is_key_repeated(KeyCode::V) {
+                let pasted = clipboard::get();
+                changed |= !pasted.is_empty();
+                self.insert(&pasted);
+            }
+        }
+
+        if is_key_repeated(KeyCode::Backspace) {
+            if by_word && self.selection().is_none() {
+                self.move_word_left(true);
+            }
+            self.backspace();
+        }
+        if is_key_repeated(KeyCode::Delete) {
+            if by_word && self.selection().is_none() {
+                self.move_word_right(true);
+            }
+            self.delete();
+        }
+        if is_key_repeated(KeyCode::Left) {
+            if by_word {
+                self.move_word_left(mods.shift);
+            } else {
+                self.move_left(mods.shift);
+            }
+        }
+        if is_key_repeated(KeyCode::Right) {
+            if by_word {
+                self.move_word_right(mods.shift);
+            } else {
+                self.move_right(mods.shift);
+            }
+        }
+        if is_key_repeated(KeyCode::Home) {
+            self.move_home(mods.shift);
+        }
+        if is_key_repeated(KeyCode::End) {
+            self.move_end(mods.shift);
+        }
+
+        changed || self.text.len() != before
+    }
+
+    const fn move_to(&mut self, to: usize, select: bool) {
+        self.caret = to;
+        if !select {
+            self.anchor = to;
+        }
+    }
+
+    /// Deletes the selected text, returning whether there was any.
+    fn delete_selection(&mut self) -> bool {
+        let Some(range) = self.selection() else {
+            return false;
+        };
+        self.text.replace_range(range.clone(), "");
+        self.caret = range.start;
+        self.anchor = range.start;
+        true
+    }
+
+    fn prev_boundary(&self, from: usize) -> usize {
+        self.text[..from]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self, from: usize) -> usize {
+        self.text[from..]
+            .graphemes(true)
+            .next()
+            .map_or(from, |g| from + g.len())
+    }
+}
@@ -0,0 +1,377 @@
+//! Grid inventories, with stacking and drag-and-drop.
+//!
+//! [`Inventory`] is only a model, it doesn't care how items look or how the player interacts with
+//! it. [`GridView`] is an optional helper which lays it out on screen and handles the mouse.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! use baba::inventory::{GridView, Inventory, Item};
+//!
+//! #[derive(Clone, PartialEq)]
+//! enum Loot {
+//!     Coin,
+//!     Sword,
+//! }
+//!
+//! impl Item for Loot {
+//!     fn max_stack(&self) -> u32 {
+//!         match self {
+//!             Self::Coin => 99,
+//!             Self::Sword => 1,
+//!         }
+//!     }
+//! }
+//!
+//! let mut bag = Inventory::new(5, 4);
+//! bag.insert(&Loot::Coin, 150); // fills two slots
+//! bag.insert(&Loot::Sword, 1);
+//!
+//! // every frame
+//! let view = GridView::new(vec2(10., 10.), vec2(32., 32.));
+//! view.handle_mouse(&mut bag);
+//! view.draw(&bag, |item, count, position| {
+//!     // draw your item's sprite here
+//! });
+//! ```
+
+use glam::{vec2, Vec2};
+
+use crate::gfx::{self, Color};
+use crate::input::{self, MouseButton};
+
+/// Something which can be stored in an [`Inventory`].
+///
+/// Items stack if they're equal, up to [`max_stack`][Item::max_stack].
+pub trait Item: Clone + PartialEq {
+    /// How many of this item fit in a single slot. Defaults to 1, which means it doesn't stack.
+    fn max_stack(&self) -> u32 {
+        1
+    }
+}
+
+/// Some amount of an item in a slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack<T> {
+    /// The item.
+    pub item: T,
+    /// How many of it there are. This is never 0.
+    pub count: u32,
+}
+
+/// A stack being dragged around, taken from a slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drag<T> {
+    /// The slot this stack was taken from.
+    pub from: usize,
+    /// The stack being dragged.
+    pub stack: Stack<T>,
+}
+
+/// A grid of item slots.
+#[derive(Debug, Clone)]
+pub struct Inventory<T> {
+    width: usize,
+    slots: Vec<Option<Stack<T>>>,
+    drag: Option<Drag<T>>,
+}
+
+impl<T: Item> Inventory<T> {
+    /// Creates an empty inventory with a size in slots.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            slots: vec![None; width * height],
+            drag: None,
+        }
+    }
+
+    /// Width of the grid, in slots.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the grid, in slots.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.slots.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    /// Number of slots.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Are all slots empty?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+
+    /// Index of the slot at a grid position.
+    #[must_use]
+    pub fn slot_at(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height()).then_some(y * self.width + x)
+    }
+
+    /// The stack in a slot.
+    #[must_use]
+    pub fn get(&self, slot: usize) -> Option<&Stack<T>> {
+        self.slots.get(slot)?.as_ref()
+    }
+
+    /// All slots, in order.
+    pub fn slots(&self) -> impl Iterator<Item = Option<&Stack<T>>> {
+        self.slots.iter().map(Option::as_ref)
+    }
+
+    /// Counts how many of an item are stored, in every slot.
+    #[must_use]
+    pub fn count(&self, item: &T) -> u32 {
+        let stacks = self.slots.iter().flatten();
+        stacks.filter(|s| &s.item == item).map(|s| s.count).sum()
+    }
+
+    /// Stores some amount of an item, filling existing stacks first, then empty slots.
+    ///
+    /// Returns how many didn't fit.
+    pub fn insert(&mut self, item: &T, mut count: u32) -> u32 {
+        let max = item.max_stack().max(1);
+
+        for stack in self.slots.iter_mut().flatten() {
+            if count == 0 {
+                return 0;
+            }
+            if &stack.item == item && stack.count < max {
+                let moved = count.min(max - stack.count);
+                stack.count += moved;
+                count -= moved;
+            }
+        }
+
+        for slot in self.slots.iter_mut().filter(|s| s.is_none()) {
+            if count == 0 {
+                return 0;
+            }
+            let moved = count.min(max);
+            *slot = Some(Stack {
+                item: item.clone(),
+                count: moved,
+            });
+            count -= moved;
+        }
+
+        count
+    }
+
+    /// Takes up to `count` items out of a slot.
+    pub fn remove(&mut self, slot: usize, count: u32) -> Option<Stack<T>> {
+        let entry = self.slots.get_mut(slot)?;
+        let stack = entry.as_mut()?;
+        if count >= stack.count {
+            return entry.take();
+        }
+
+        stack.count -= count;
+        Some(Stack {
+            item: stack.item.clone(),
+            count,
+        })
+    }
+
+    /// Takes away some amount of an item, from any slots. Returns whether there were enough of
+    /// them; if not, nothing is removed.
+    pub fn consume(&mut self, item: &T, mut count: u32) -> bool {
+        if self.count(item) < count {
+            return false;
+        }
+
+        for slot in self.slots.iter_mut().rev() {
+            if let Some(stack) = slot.as_mut().filter(|s| &s.item == item) {
+                let taken = count.min(stack.count);
+                stack.count -= taken;
+                count -= taken;
+                if stack.count == 0 {
+                    *slot = None;
+                }
+            }
+        }
+        true
+    }
+
+    /// Puts a stack in a slot. If it already has the same item, they're merged as much as
+    /// possible, otherwise they're swapped.
+    ///
+    /// Returns whatever is left over, which may be the previous contents of the slot.
+    pub fn place(&mut self, slot: usize, stack: Stack<T>) -> Option<Stack<T>> {
+        let Some(entry) = self.slots.get_mut(slot) else {
+            return Some(stack);
+        };
+
+        match entry {
+            Some(existing) if existing.item == stack.item => {
+                let max = stack.item.max_stack().max(1);
+                let moved = stack.count.min(max.saturating_sub(existing.count));
+                existing.count += moved;
+
+                let count = stack.count - moved;
+                (count > 0).then_some(Stack { count, ..stack })
+            }
+            _ => entry.replace(stack),
+        }
+    }
+
+    /// Moves a stack between two slots, merging or swapping them.
+    pub fn move_stack(&mut self, from: usize, to: usize) {
+        if from == to || to >= self.slots.len() {
+            return;
+        }
+        let Some(stack) = self.slots.get_mut(from).and_then(Option::take) else {
+            return;
+        };
+
+        self.slots[from] = self.place(to, stack);
+    }
+
+    /// The stack being dragged, if any.
+    #[must_use]
+    pub const fn drag(&self) -> Option<&Drag<T>> {
+        self.drag.as_ref()
+    }
+
+    /// Picks up the stack in a slot. Returns whether something was picked up.
+    pub fn begin_drag(&mut self, slot: usize) -> bool {
+        if self.drag.is_some() {
+            return false;
+        }
+
+        let stack = self.slots.get_mut(slot).and_then(Option::take);
+        self.drag = stack.map(|stack| Drag { from: slot, stack });
+        self.drag.is_some()
+    }
+
+    /// Drops the dragged stack on a slot, merging or swapping it with what's there.
+    ///
+    /// If something was swapped out, it goes back to where the drag started.
+    pub fn end_drag(&mut self, slot: usize) {
+        let Some(drag) = self.drag.take() else {
+            return;
+        };
+
+        if let Some(rest) = self.place(slot, drag.stack) {
+            self.put_back(drag.from, rest);
+        }
+    }
+
+    /// Puts the dragged stack back where it came from.
+    pub fn cancel_drag(&mut self) {
+        if let Some(drag) = self.drag.take() {
+            self.put_back(drag.from, drag.stack);
+        }
+    }
+
+    fn put_back(&mut self, slot: usize, stack: Stack<T>) {
+        let rest = self.place(slot, stack);
+        if let Some(rest) = rest {
+            let count = self.insert(&rest.item, rest.count);
+            if count > 0 {
+                log::warn!("Inventory is full, {count} items were lost");
+            }
+        }
+    }
+}
+
+/// Lays out an [`Inventory`] on the screen.
+#[derive(Debug, Clone)]
+pub struct GridView {
+    /// Position of the top-left slot.
+    pub position: Vec2,
+    /// Size of each slot.
+    pub slot_size: Vec2,
+    /// Space between slots.
+    pub spacing: f32,
+    /// Color for the slot backgrounds. Defaults to translucent black.
+    pub background: Color,
+    /// Color for the slot borders. Defaults to gray.
+    pub border: Color,
+}
+
+impl GridView {
+    /// Creates a view at a position on the screen.
+    #[must_use]
+    pub const fn new(position: Vec2, slot_size: Vec2) -> Self {
+        Self {
+            position,
+            slot_size,
+            spacing: 2.,
+            background: Color::from_black_alpha(128),
+            border: Color::GRAY,
+        }
+    }
+
+    /// Position of a slot's top-left corner on the screen.
+    #[must_use]
+    pub fn slot_position<T: Item>(&self, inventory: &Inventory<T>, slot: usize) -> Vec2 {
+        let width = inventory.width().max(1);
+        let cell = vec2((slot % width) as f32, (slot / width) as f32);
+        self.position + cell * (self.slot_size + self.spacing)
+    }
+
+    /// The slot under a point on the screen.
+    #[must_use]
+    pub fn slot_at_point<T: Item>(&self, inventory: &Inventory<T>, point: Vec2) -> Option<usize> {
+        let offset = point - self.position;
+        let cell = (offset / (self.slot_size + self.spacing)).floor();
+        let inside = offset - cell * (self.slot_size + self.spacing);
+        if cell.min_element() < 0. || inside.x >= self.slot_size.x || inside.y >= self.slot_size.y {
+            return None;
+        }
+
+        #[allow(clippy::cast_sign_loss)]
+        inventory.slot_at(cell.x as usize, cell.y as usize)
+    }
+
+    /// Drags stacks around with the left mouse button.
+    ///
+    /// Dropping a stack outside of the grid puts it back.
+    pub fn handle_mouse<T: Item>(&self, inventory: &mut Inventory<T>) {
+        let slot = self.slot_at_point(inventory, input::mouse_position());
+
+        if input::is_mouse_pressed(MouseButton::Left) {
+            if let Some(slot) = slot {
+                inventory.begin_drag(slot);
+            }
+        } else if !input::is_mouse_down(MouseButton::Left) && inventory.drag().is_some() {
+            match slot {
+                Some(slot) => inventory.end_drag(slot),
+                None => inventory.cancel_drag(),
+            }
+        }
+    }
+
+    /// Draws the slots, and calls `draw_item` with each stack, its count, and where to draw it.
+    ///
+    /// The dragged stack is drawn last, following the mouse.
+    pub fn draw<T: Item>(
+        &self,
+        inventory: &Inventory<T>,
+        mut draw_item: impl FnMut(&T, u32, Vec2),
+    ) {
+        for (slot, stack) in inventory.slots().enumerate() {
+            let position = self.slot_position(inventory, slot);
+            gfx::draw_rect(self.slot_size, self.background, position);
+            gfx::draw_rect_outline(self.slot_size, 1., self.border, position);
+
+            if let Some(stack) = stack {
+                draw_item(&stack.item, stack.count, position);
+            }
+        }
+
+        if let Some(drag) = inventory.drag() {
+            let position = input::mouse_position() - self.slot_size / 2.;
+            draw_item(&drag.stack.item, drag.stack.count, position);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Background work, on the engine's worker threads.
+//!
+//! Slow tasks like pathfinding or generating a chunk of the world can be run without stalling
+//! the game. [`spawn`] a job, then check on it every frame:
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # fn find_path(from: Vec2, to: Vec2) -> Vec<Vec2> { vec![] }
+//! use baba::jobs::{self, Job};
+//!
+//! struct Enemy {
+//!     position: Vec2,
+//!     path: Vec<Vec2>,
+//!     pathfinding: Option<Job<Vec<Vec2>>>,
+//! }
+//!
+//! impl Enemy {
+//!     fn update(&mut self, player: Vec2) {
+//!         if let Some(job) = &self.pathfinding {
+//!             match job.try_take() {
+//!                 Ok(Some(path)) => {
+//!                     self.path = path;
+//!                     self.pathfinding = None;
+//!                 }
+//!                 Ok(None) => {}
+//!                 // Try again next frame
+//!                 Err(_) => self.pathfinding = None,
+//!             }
+//!         } else if self.path.is_empty() {
+//!             let from = self.position;
+//!             self.pathfinding = Some(jobs::spawn(move || find_path(from, player)));
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Workers run at a low thread priority, so they don't compete with the main thread for time.
+//! The number of workers can be set with [`Settings::worker_threads`][crate::Settings].
+//!
+//! Jobs can't use [`gfx`][crate::gfx], as drawing and creating textures can only be done from
+//! the main thread. Decode data in a job, then create the texture when it's done.
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::panic::AssertUnwindSafe;
+use std::sync::{mpsc, Arc, OnceLock};
+use std::thread;
+
+use parking_lot::{Condvar, Mutex};
+use thiserror::Error;
+
+/// How soon a job should run, compared to others waiting.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Runs once nothing else is waiting, like prefetching things which may be needed later.
+    Low,
+    /// The default.
+    #[default]
+    Normal,
+    /// Runs before other waiting jobs, for things the game is waiting on.
+    High,
+}
+
+type Task = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct Queue {
+    // One queue per priority, from low to high
+    tasks: Mutex<[VecDeque<Task>; 3]>,
+    available: Condvar,
+}
+
+impl Queue {
+    fn push(&self, priority: Priority, task: Task) {
+        self.tasks.lock()[priority as usize].push_back(task);
+        self.available.notify_one();
+    }
+
+    fn pop(&self) -> Task {
+        let mut tasks = self.tasks.lock();
+        loop {
+            if let Some(task) = tasks.iter_mut().rev().find_map(VecDeque::pop_front) {
+                return task;
+            }
+            self.available.wait(&mut tasks);
+        }
+    }
+}
+
+static QUEUE: OnceLock<Arc<Queue>> = OnceLock::new();
+
+/// Starts the worker threads. Does nothing if they're already running.
+///
+/// With a count of 0, there's one worker per CPU core, minus the main thread's.
+pub(crate) fn init(count: usize) {
+    start(count);
+}
+
+fn start(count: usize) -> &'static Queue {
+    QUEUE.get_or_init(|| {
+        let count = if count > 0 {
+            count
+        } else {
+            let cores = thread::available_parallelism().map_or(2, usize::from);
+            cores.saturating_sub(1).max(1)
+        };
+
+        let queue = Arc::new(Queue::default());
+        for i in 0..count {
+            let queue = queue.clone();
+            let worker = thread::Builder::new()
+                .name(format!("baba worker {i}"))
+                .spawn(move || work(&queue));
+            if let Err(e) = worker {
+                log::error!("Failed to start worker thread: {e}");
+            }
+        }
+        log::debug!("Started {count} worker threads");
+        queue
+    })
+}
+
+fn work(queue: &Queue) {
+    unsafe {
+        sdl2_sys::SDL_SetThreadPriority(sdl2_sys::SDL_ThreadPriority::SDL_THREAD_PRIORITY_LOW);
+    }
+    loop {
+        let task = queue.pop();
+        // A panicking job is reported through its handle, and shouldn't take the worker with it
+        let _ = std::panic::catch_unwind(AssertUnwindSafe(task));
+    }
+}
+
+/// A job panicked instead of returning a result. The panic itself was already printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("job panicked")]
+pub struct JobPanicked;
+
+/// The result of a job, which may still be running.
+///
+/// Dropping this doesn't cancel the job, but its result is thrown away.
+#[must_use]
+pub struct Job<T> {
+    result: mpsc::Receiver<T>,
+    taken: Cell<bool>,
+}
+
+impl<T> Job<T> {
+    /// Takes the result if the job has finished, or returns `Ok(None)` if it's still running.
+    ///
+    /// This only returns the result once; after that, it's always `Ok(None)`.
+    ///
+    /// # Errors
+    ///
+    /// If the job panicked. It won't ever finish, so it could be spawned again.
+    pub fn try_take(&self) -> Result<Option<T>, JobPanicked> {
+        if self.taken.get() {
+            return Ok(None);
+        }
+        match self.result.try_recv() {
+            Ok(result) => {
+                self.taken.set(true);
+                Ok(Some(result))
+            }
+            Err(mpsc::TryRecvError::Empty) => Ok(None),
+            Err(mpsc::TryRecvError::Disconnected) => Err(JobPanicked),
+        }
+    }
+
+    /// Blocks until the job is finished, and returns its result.
+    ///
+    /// # Panics
+    ///
+    /// If the job panicked, or its result was already taken.
+    #[must_use]
+    pub fn wait(self) -> T {
+        assert!(!self.taken.get(), "job result was already taken");
+        self.result.recv().expect("job panicked")
+    }
+}
+
+/// Runs a function on a worker thread.
+pub fn spawn<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> Job<T> {
+    spawn_with(Priority::Normal, f)
+}
+
+/// Runs a function on a worker thread, before or after other waiting jobs.
+pub fn spawn_with<T: Send + 'static>(
+    priority: Priority,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Job<T> {
+    let (sender, result) = mpsc::sync_channel(1);
+    let task = Box::new(move || {
+        let _ = sender.send(f());
+    });
+    start(0).push(priority, task);
+    Job {
+        result,
+        taken: Cell::new(false),
+    }
+}
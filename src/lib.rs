@@ -33,6 +33,16 @@
 //! development, so loads more documentation are still coming.
 //!
 //! [modules]: #modules
+//!
+//! ## Profiling
+//!
+//! With the `tracing` feature, the engine emits [`tracing`](https://docs.rs/tracing) spans for
+//! each frame, event processing, updates, draws and presenting. Install any subscriber, like
+//! `tracing-tracy` or `tracing-chrome`, to see where time goes.
+//!
+//! The `puffin` feature does the same with [`puffin`](https://docs.rs/puffin) scopes, and marks
+//! the end of each frame. Scopes are only recorded after calling `puffin::set_scopes_on(true)`,
+//! and can be viewed by serving them with `puffin_http`.
 
 #![warn(
     clippy::pedantic,
@@ -52,13 +62,59 @@
     clippy::missing_panics_doc
 )]
 
+/// Enters a profiling span until the end of the scope, if the `tracing` or `puffin` features are
+/// enabled.
+macro_rules! span {
+    ($level:ident, $name:literal) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $name).entered();
+        #[cfg(feature = "puffin")]
+        puffin::profile_scope!($name);
+    };
+}
+
+pub mod alloc;
+pub mod analytics;
+pub mod animator;
+pub mod assets;
+pub mod audio;
+pub mod clipboard;
+pub mod combo;
+#[cfg(feature = "data")]
+pub mod data;
+pub mod debug;
 mod error;
+pub mod events;
+pub mod flags;
+pub mod floating_text;
+pub mod fmt;
+pub mod fsm;
 mod game;
 pub mod gfx;
+pub mod hud;
 pub mod input;
+pub mod inventory;
+pub mod jobs;
 pub mod math;
-pub use error::{Error, SdlError};
-pub use game::{Framerate, Game, Settings, WindowSettings};
+mod message_box;
+pub mod presence;
+pub mod procgen;
+pub mod rope;
+pub mod scene;
+pub mod streaming;
+pub mod system;
+pub mod time;
+pub mod timeline;
+pub mod timer;
+pub mod turns;
+pub mod tween;
+pub mod water;
+pub mod weather;
+pub use error::{Error, LoadError, SdlError};
+pub use game::{
+    quit, BackgroundBehavior, Framerate, Game, QuitResponse, Runner, Settings, WindowSettings,
+};
+pub use message_box::{message_box, MessageKind};
 
 /// A [`Result`][std::result] type for baba programs.
 pub type Result<T = (), E = Error> = std::result::Result<T, E>;
@@ -139,11 +195,15 @@ pub mod prelude {
     pub use crate::game::{Framerate, Settings, WindowSettings};
     #[doc(inline)]
     pub use crate::gfx::{
-        self, Color, Drawable, Origin, ScaleMode, Texture, TextureOptions, TextureSlice, Transform,
-        Vertex, Viewport, ViewportScaling,
+        self, BlendMode, Camera, Color, Drawable, Font, Grade, Origin, PixelTerrain, RenderTarget,
+        ScaleMode, Shadow, Shape, SpriteBatch, Text, Texture, TextureOptions, TextureSlice,
+        Transform, Vertex, Viewport, ViewportScaling,
     };
     #[doc(inline)]
-    pub use crate::input::{self, is_key_down, is_key_pressed, KeyCode};
+    pub use crate::input::{
+        self, is_key_down, is_key_pressed, is_key_released, is_mouse_down, is_mouse_pressed,
+        mouse_position, mouse_wheel, KeyCode, MouseButton,
+    };
     #[doc(inline)]
     pub use crate::math::*;
 
@@ -2,6 +2,20 @@
 //!
 //! This supports linear algebra using [`glam`].
 
+mod circle;
+mod ease;
+pub mod noise;
+mod rect;
+pub mod rng;
+mod segment;
+pub mod steering;
+pub mod wrap;
+
+pub use circle::Circle;
+pub use ease::Ease;
+pub use rect::FRect;
+pub use segment::{raycast, Hit, Segment};
+
 pub use glam::{
     dvec2, dvec3, dvec4, ivec2, ivec3, ivec4, mat2, mat3, mat4, uvec2, uvec3, uvec4, vec2, vec3,
     vec4, Affine2, DVec2, DVec3, DVec4, EulerRot, FloatExt, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4,
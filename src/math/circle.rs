@@ -0,0 +1,57 @@
+use glam::Vec2;
+
+use super::FRect;
+
+/// A circle, for simple collisions.
+///
+/// ```
+/// # use baba::prelude::*;
+/// let bullet = Circle::new(vec2(12., 8.), 2.);
+/// let enemy = Circle::new(vec2(16., 8.), 3.);
+/// let wall = FRect::new(20., 0., 8., 32.);
+///
+/// assert!(bullet.overlaps(&enemy));
+/// assert!(!bullet.overlaps_rect(&wall));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circle {
+    /// Position of the center
+    pub center: Vec2,
+    /// Distance from the center to the edge
+    pub radius: f32,
+}
+
+impl Circle {
+    /// Create a new circle from its center and radius.
+    #[must_use]
+    pub const fn new(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Is a point inside this circle, or on its edge?
+    #[must_use]
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        self.center.distance_squared(point) <= self.radius * self.radius
+    }
+
+    /// Do two circles overlap? Circles which only touch don't.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let radii = self.radius + other.radius;
+        self.center.distance_squared(other.center) < radii * radii
+    }
+
+    /// Does this circle overlap a rectangle? Shapes which only touch don't.
+    #[must_use]
+    pub fn overlaps_rect(&self, rect: &FRect) -> bool {
+        let closest = self.center.clamp(rect.min(), rect.max());
+        self.center.distance_squared(closest) < self.radius * self.radius
+    }
+
+    /// The smallest rectangle containing this circle.
+    #[must_use]
+    pub fn bounds(&self) -> FRect {
+        FRect::from_center(self.center, Vec2::splat(self.radius * 2.))
+    }
+}
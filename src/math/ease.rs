@@ -0,0 +1,98 @@
+use std::f32::consts::PI;
+
+/// A curve that changes how a value moves from start to end over time, so it can speed up and
+/// slow down naturally.
+///
+/// `In` curves start slow, `Out` curves end slow, and `InOut` curves do both.
+///
+/// ```
+/// # use baba::prelude::*;
+/// use baba::math::Ease;
+///
+/// let (start, end) = (vec2(0., 100.), vec2(200., 100.));
+/// let t = 0.25; // How far along the animation is, from 0 to 1
+/// let position = start.lerp(end, Ease::OutCubic.apply(t));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Ease {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Stays at the start, then jumps to the end.
+    Step,
+    /// Starts slow, speeding up.
+    InQuad,
+    /// Starts fast, slowing down.
+    OutQuad,
+    /// Starts and ends slow.
+    InOutQuad,
+    /// Like [`InQuad`][Self::InQuad], but sharper.
+    InCubic,
+    /// Like [`OutQuad`][Self::OutQuad], but sharper.
+    OutCubic,
+    /// Like [`InOutQuad`][Self::InOutQuad], but sharper.
+    InOutCubic,
+    /// Starts and ends slow, following a sine wave.
+    InOutSine,
+    /// Pulls back a little before starting.
+    InBack,
+    /// Overshoots the end a little, then settles.
+    OutBack,
+    /// Bounces on the end, like a dropped ball.
+    OutBounce,
+    /// Overshoots and wobbles around the end, like a spring.
+    OutElastic,
+}
+
+impl Ease {
+    /// Applies this curve to `t`, which goes from 0 to 1. The result starts at 0 and ends at 1,
+    /// but may go a bit outside of that in between.
+    #[must_use]
+    pub fn apply(self, t: f32) -> f32 {
+        const BACK: f32 = 1.701_58;
+
+        let t = t.clamp(0., 1.);
+        match self {
+            Self::Linear => t,
+            Self::Step => f32::from(t >= 1.),
+            Self::InQuad => t * t,
+            Self::OutQuad => 1. - (1. - t) * (1. - t),
+            Self::InOutQuad if t < 0.5 => 2. * t * t,
+            Self::InOutQuad => 1. - (-2. * t + 2.).powi(2) / 2.,
+            Self::InCubic => t * t * t,
+            Self::OutCubic => 1. - (1. - t).powi(3),
+            Self::InOutCubic if t < 0.5 => 4. * t * t * t,
+            Self::InOutCubic => 1. - (-2. * t + 2.).powi(3) / 2.,
+            Self::InOutSine => -((PI * t).cos() - 1.) / 2.,
+            Self::InBack => (BACK + 1.) * t * t * t - BACK * t * t,
+            Self::OutBack => {
+                let t = t - 1.;
+                1. + (BACK + 1.) * t * t * t + BACK * t * t
+            }
+            Self::OutBounce => bounce(t),
+            Self::OutElastic if t <= 0. || t >= 1. => t,
+            Self::OutElastic => {
+                2f32.powf(-10. * t) * ((t * 10. - 0.75) * (2. * PI / 3.)).sin() + 1.
+            }
+        }
+    }
+}
+
+fn bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1. / D {
+        N * t * t
+    } else if t < 2. / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984_375
+    }
+}
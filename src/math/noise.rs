@@ -0,0 +1,156 @@
+//! Perlin noise: smooth random values, for things like terrain, screen shake and wobbling
+//! animations.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let (mut camera, trauma) = (Camera::default(), 0.5);
+//! use baba::math::noise::Noise;
+//!
+//! let noise = Noise::new(1234);
+//!
+//! // A height map, with detail from several octaves
+//! let height = noise.fbm2(vec2(12., 7.) * 0.05, 4);
+//!
+//! // Screen shake which moves smoothly, unlike picking random offsets every frame
+//! let t = baba::time::elapsed() * 20.;
+//! let shake = vec2(noise.get1(t), noise.get1(t + 100.)) * 8. * trauma;
+//! camera.position += shake;
+//! ```
+//!
+//! Noise changes over a distance of about 1, so scale positions down for smoother noise.
+
+use glam::{FloatExt, Vec2};
+
+use super::rng::Rng;
+
+/// A seeded Perlin noise generator. The same seed always gives the same noise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Noise {
+    perm: [u8; 512],
+    lacunarity: f32,
+    gain: f32,
+}
+
+impl Noise {
+    /// Creates a noise generator from a seed.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        let mut perm = [0; 512];
+        let mut values: Vec<u8> = (0..=255).collect();
+        Rng::new(seed).shuffle(&mut values);
+        for (i, p) in perm.iter_mut().enumerate() {
+            *p = values[i & 255];
+        }
+        Self {
+            perm,
+            lacunarity: 2.,
+            gain: 0.5,
+        }
+    }
+
+    /// Sets how much the frequency grows with each octave of [`fbm1`][Self::fbm1] and
+    /// [`fbm2`][Self::fbm2]. Defaults to 2.
+    #[must_use]
+    pub const fn with_lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// Sets how much the strength shrinks with each octave of [`fbm1`][Self::fbm1] and
+    /// [`fbm2`][Self::fbm2]. Defaults to 0.5.
+    #[must_use]
+    pub const fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn hash(&self, x: i32) -> usize {
+        usize::from(self.perm[(x & 255) as usize])
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn hash2(&self, x: i32, y: i32) -> usize {
+        usize::from(self.perm[self.hash(x) + (y & 255) as usize])
+    }
+
+    /// Noise along a line, from -1 to 1.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn get1(&self, x: f32) -> f32 {
+        let cell = x.floor();
+        let (left, frac) = (cell as i32, x - cell);
+        let grad = |corner: i32, distance: f32| (self.hash(corner) as f32 / 127.5 - 1.) * distance;
+        let start = grad(left, frac);
+        let end = grad(left.wrapping_add(1), frac - 1.);
+        // Gradients are at most 1, so this is within -0.5..0.5
+        2. * start.lerp(end, fade(frac))
+    }
+
+    /// Noise over a plane, from -1 to 1.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn get2(&self, point: Vec2) -> f32 {
+        let cell = point.floor();
+        let (left, top) = (cell.x as i32, cell.y as i32);
+        let (right, bottom) = (left.wrapping_add(1), top.wrapping_add(1));
+        let frac = point - cell;
+        let grad = |x: i32, y: i32, corner: Vec2| {
+            let offset = frac - corner;
+            match self.hash2(x, y) & 7 {
+                0 => offset.x,
+                1 => -offset.x,
+                2 => offset.y,
+                3 => -offset.y,
+                4 => offset.x + offset.y,
+                5 => offset.x - offset.y,
+                6 => -offset.x + offset.y,
+                _ => -offset.x - offset.y,
+            }
+        };
+        let (fade_x, fade_y) = (fade(frac.x), fade(frac.y));
+        let upper = grad(left, top, Vec2::ZERO).lerp(grad(right, top, Vec2::X), fade_x);
+        let lower = grad(left, bottom, Vec2::Y).lerp(grad(right, bottom, Vec2::ONE), fade_x);
+        upper.lerp(lower, fade_y).clamp(-1., 1.)
+    }
+
+    /// Fractal noise along a line, adding up `octaves` layers of noise with more and more detail.
+    /// The result is from -1 to 1.
+    #[must_use]
+    pub fn fbm1(&self, x: f32, octaves: u32) -> f32 {
+        self.fbm(octaves, |noise, frequency, i| {
+            // Offset each octave, so they don't all line up at the origin
+            noise.get1(x * frequency + i * 17.3)
+        })
+    }
+
+    /// Fractal noise over a plane, adding up `octaves` layers of noise with more and more
+    /// detail. The result is from -1 to 1.
+    #[must_use]
+    pub fn fbm2(&self, point: Vec2, octaves: u32) -> f32 {
+        self.fbm(octaves, |noise, frequency, i| {
+            noise.get2(point * frequency + Vec2::splat(i * 17.3))
+        })
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn fbm(&self, octaves: u32, sample: impl Fn(&Self, f32, f32) -> f32) -> f32 {
+        let (mut sum, mut total, mut amplitude, mut frequency) = (0., 0., 1., 1.);
+        for i in 0..octaves {
+            sum += sample(self, frequency, i as f32) * amplitude;
+            total += amplitude;
+            amplitude *= self.gain;
+            frequency *= self.lacunarity;
+        }
+        if total > 0. {
+            sum / total
+        } else {
+            0.
+        }
+    }
+}
+
+/// Perlin's smoother step, so noise has no creases at cell edges.
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
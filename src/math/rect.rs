@@ -0,0 +1,132 @@
+use glam::{vec2, Vec2};
+
+use super::Rect;
+
+/// A rectangle with floating point coordinates, like the bounds of something in the world.
+///
+/// ```
+/// # use baba::prelude::*;
+/// let player = FRect::new(10., 10., 16., 24.);
+/// let wall = FRect::new(20., 0., 32., 64.);
+///
+/// assert!(player.intersects(&wall));
+/// assert_eq!(player.intersection(&wall), Some(FRect::new(20., 10., 6., 24.)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FRect {
+    /// Horizontal position of the left edge
+    pub x: f32,
+    /// Vertical position of the top edge
+    pub y: f32,
+    /// Horizontal size
+    pub w: f32,
+    /// Vertical size
+    pub h: f32,
+}
+
+impl FRect {
+    /// Create a new rectangle with position and size.
+    #[must_use]
+    pub const fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    /// Create a rectangle from its top left and bottom right corners.
+    #[must_use]
+    pub fn from_min_max(min: Vec2, max: Vec2) -> Self {
+        Self::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    /// Create a rectangle of some size, centered on a point.
+    #[must_use]
+    pub fn from_center(center: Vec2, size: Vec2) -> Self {
+        Self::from_min_max(center - size / 2., center + size / 2.)
+    }
+
+    /// The top left corner.
+    #[must_use]
+    pub const fn min(&self) -> Vec2 {
+        vec2(self.x, self.y)
+    }
+
+    /// The bottom right corner.
+    #[must_use]
+    pub fn max(&self) -> Vec2 {
+        vec2(self.x + self.w, self.y + self.h)
+    }
+
+    /// The width and height.
+    #[must_use]
+    pub const fn size(&self) -> Vec2 {
+        vec2(self.w, self.h)
+    }
+
+    /// The point in the middle.
+    #[must_use]
+    pub fn center(&self) -> Vec2 {
+        self.min() + self.size() / 2.
+    }
+
+    /// Is a point inside this rectangle? Points on the left and top edges are, and points on the
+    /// right and bottom edges aren't, so rectangles side by side never both contain a point.
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        let (min, max) = (self.min(), self.max());
+        point.x >= min.x && point.y >= min.y && point.x < max.x && point.y < max.y
+    }
+
+    /// Do two rectangles overlap? Rectangles which only touch don't.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (min, max) = (self.min().max(other.min()), self.max().min(other.max()));
+        min.x < max.x && min.y < max.y
+    }
+
+    /// The area where two rectangles overlap, or `None` if they don't.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let (min, max) = (self.min().max(other.min()), self.max().min(other.max()));
+        (min.x < max.x && min.y < max.y).then(|| Self::from_min_max(min, max))
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_min_max(self.min().min(other.min()), self.max().max(other.max()))
+    }
+
+    /// This rectangle, moved by an offset.
+    #[must_use]
+    pub fn offset(&self, offset: Vec2) -> Self {
+        Self::new(self.x + offset.x, self.y + offset.y, self.w, self.h)
+    }
+
+    /// This rectangle, grown by an amount on every side. Negative amounts shrink it.
+    #[must_use]
+    pub fn inflate(&self, amount: f32) -> Self {
+        Self::from_min_max(self.min() - amount, self.max() + amount)
+    }
+
+    /// Converts to an integer rectangle, like for [`Texture::slice`][crate::gfx::Texture::slice],
+    /// rounding the corners to the nearest pixel. Parts to the left of or above 0 are cut off.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_rect(&self) -> Rect {
+        let min = self.min().round().max(Vec2::ZERO);
+        let max = self.max().round().max(min);
+        Rect::new(
+            min.x as u32,
+            min.y as u32,
+            (max.x - min.x) as u32,
+            (max.y - min.y) as u32,
+        )
+    }
+}
+
+impl From<Rect> for FRect {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(rect: Rect) -> Self {
+        Self::new(rect.x as f32, rect.y as f32, rect.w as f32, rect.h as f32)
+    }
+}
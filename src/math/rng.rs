@@ -0,0 +1,309 @@
+//! Random numbers.
+//!
+//! The functions here use a generator shared by the whole game, which is seeded differently every
+//! time the game runs:
+//!
+//! ```no_run
+//! use baba::math::rng;
+//!
+//! let roll = rng::random_range(1..=6);
+//! let spread = rng::random_range(-0.1..0.1);
+//! let offset = rng::random_vec2_in_circle(16.);
+//! ```
+//!
+//! To get the same numbers every time, like for replays, seed it with [`seed`], or keep an [`Rng`]
+//! of your own.
+//!
+//! Daily challenges give every player the same run on a given day, by seeding generators with
+//! [`daily_seed`]:
+//!
+//! ```no_run
+//! use baba::math::rng;
+//! use baba::procgen::dungeon::{self, BspOptions};
+//!
+//! let seed = rng::daily_seed("my-game");
+//! let map = dungeon::bsp(64, 48, &BspOptions::default(), seed);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::ops::{Range, RangeInclusive};
+
+use chrono::{Datelike, NaiveDate, Utc};
+use glam::Vec2;
+
+use crate::math::TAU;
+
+/// A small, seeded random number generator. This is `SplitMix64`, which is fast and good enough
+/// for games, but not for anything secure.
+///
+/// The same seed always gives the same numbers, on every platform.
+///
+/// ```
+/// use baba::math::rng::Rng;
+///
+/// let mut rng = Rng::new(1234);
+/// let damage = rng.range(8..=12);
+/// assert!((8..=12).contains(&damage));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+impl Rng {
+    /// Creates a generator from a seed.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Creates a generator with a seed from the operating system, which is different every time.
+    #[must_use]
+    pub fn from_entropy() -> Self {
+        Self::new(RandomState::new().build_hasher().finish())
+    }
+
+    /// A random number, with every bit random.
+    pub const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        mix(self.0)
+    }
+
+    /// A number in `0..n`. `n` must not be 0.
+    pub(crate) const fn below(&mut self, n: u32) -> u32 {
+        (((self.next_u64() >> 32) * n as u64) >> 32) as u32
+    }
+
+    /// A number in `min..=max`.
+    pub(crate) const fn between(&mut self, min: u32, max: u32) -> u32 {
+        min + self.below(max - min + 1)
+    }
+
+    /// A number in `0..n`, or any number if `n` is 0.
+    #[allow(clippy::cast_possible_truncation)]
+    fn below_u64(&mut self, n: u64) -> u64 {
+        match u32::try_from(n) {
+            Ok(0) => self.next_u64(),
+            Ok(n) => u64::from(self.below(n)),
+            Err(_) => ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64,
+        }
+    }
+
+    /// A number in `0.0..1.0`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn float(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1 << 24) as f32
+    }
+
+    /// A number in a range, like `1..=6` or `0.5..2.0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn range<R: SampleRange>(&mut self, range: R) -> R::Item {
+        range.sample(self)
+    }
+
+    /// `true` with a probability from 0 to 1.
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.float() < probability
+    }
+
+    /// A point inside a circle around the origin. Points are spread evenly over its area.
+    pub fn vec2_in_circle(&mut self, radius: f32) -> Vec2 {
+        self.direction() * radius * self.float().sqrt()
+    }
+
+    /// A vector of length 1, pointing in any direction.
+    pub fn direction(&mut self) -> Vec2 {
+        Vec2::from_angle(self.float() * TAU)
+    }
+
+    /// A random item from a slice, or `None` if it's empty.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.below_u64(items.len() as u64) as usize)
+    }
+
+    /// Puts the items of a slice in a random order.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below_u64(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Ranges which an [`Rng`] can pick numbers from.
+pub trait SampleRange {
+    /// The type of number in the range.
+    type Item;
+
+    /// Picks a number in this range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    fn sample(self, rng: &mut Rng) -> Self::Item;
+}
+
+macro_rules! sample_int {
+    ($($t:ty),*) => {$(
+        impl SampleRange for Range<$t> {
+            type Item = $t;
+
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss
+            )]
+            fn sample(self, rng: &mut Rng) -> $t {
+                assert!(self.start < self.end, "cannot sample an empty range");
+                let width = (self.end as i128 - self.start as i128) as u64;
+                (self.start as i128 + i128::from(rng.below_u64(width))) as $t
+            }
+        }
+
+        impl SampleRange for RangeInclusive<$t> {
+            type Item = $t;
+
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss
+            )]
+            fn sample(self, rng: &mut Rng) -> $t {
+                let (start, end) = self.into_inner();
+                assert!(start <= end, "cannot sample an empty range");
+                // Wraps to 0 for the full range of 64-bit numbers, which picks any number
+                let width = (end as i128 - start as i128 + 1) as u64;
+                (start as i128 + i128::from(rng.below_u64(width))) as $t
+            }
+        }
+    )*};
+}
+
+sample_int!(i32, u32, i64, u64, usize);
+
+impl SampleRange for Range<f32> {
+    type Item = f32;
+
+    fn sample(self, rng: &mut Rng) -> f32 {
+        assert!(self.start < self.end, "cannot sample an empty range");
+        let value = self.start + rng.float() * (self.end - self.start);
+        // Rounding can land on the end, which isn't part of the range
+        if value < self.end {
+            value
+        } else {
+            self.start
+        }
+    }
+}
+
+impl SampleRange for RangeInclusive<f32> {
+    type Item = f32;
+
+    fn sample(self, rng: &mut Rng) -> f32 {
+        let (start, end) = self.into_inner();
+        assert!(start <= end, "cannot sample an empty range");
+        (start + rng.float() * (end - start)).min(end)
+    }
+}
+
+thread_local! {
+    static RNG: RefCell<Rng> = RefCell::new(Rng::from_entropy());
+}
+
+fn with_rng<T>(f: impl FnOnce(&mut Rng) -> T) -> T {
+    RNG.with_borrow_mut(f)
+}
+
+/// Seeds the shared generator, so the functions here give the same numbers every time.
+pub fn seed(seed: u64) {
+    with_rng(|rng| *rng = Rng::new(seed));
+}
+
+/// A number in `0.0..1.0`, from the shared generator.
+#[must_use]
+pub fn random() -> f32 {
+    with_rng(Rng::float)
+}
+
+/// A number in a range, like `1..=6` or `0.5..2.0`, from the shared generator.
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+#[must_use]
+pub fn random_range<R: SampleRange>(range: R) -> R::Item {
+    with_rng(|rng| rng.range(range))
+}
+
+/// `true` with a probability from 0 to 1, from the shared generator.
+#[must_use]
+pub fn random_chance(probability: f32) -> bool {
+    with_rng(|rng| rng.chance(probability))
+}
+
+/// A point inside a circle around the origin, from the shared generator.
+#[must_use]
+pub fn random_vec2_in_circle(radius: f32) -> Vec2 {
+    with_rng(|rng| rng.vec2_in_circle(radius))
+}
+
+/// A vector of length 1, pointing in any direction, from the shared generator.
+#[must_use]
+pub fn random_direction() -> Vec2 {
+    with_rng(Rng::direction)
+}
+
+/// A random item from a slice, or `None` if it's empty, from the shared generator.
+#[must_use]
+pub fn random_choice<T>(items: &[T]) -> Option<&T> {
+    with_rng(|rng| rng.choose(items))
+}
+
+/// Puts the items of a slice in a random order, with the shared generator.
+pub fn shuffle<T>(items: &mut [T]) {
+    with_rng(|rng| rng.shuffle(items));
+}
+
+/// A seed for today's daily challenge. It changes at midnight UTC, so it's the same for every
+/// player around the world at the same moment.
+///
+/// `salt` should be unique to the game, or to a mode within it, so that different games don't
+/// share the same runs.
+#[must_use]
+pub fn daily_seed(salt: &str) -> u64 {
+    seed_for_date(Utc::now().date_naive(), salt)
+}
+
+/// The [`daily_seed`] for any date, like for replaying past challenges.
+#[must_use]
+pub fn seed_for_date(date: NaiveDate, salt: &str) -> u64 {
+    // FNV-1a, which is stable across platforms and Rust versions, unlike `DefaultHasher`
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for byte in salt.bytes() {
+        hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+    }
+    #[allow(clippy::cast_sign_loss)]
+    let day = date.num_days_from_ce() as u64;
+    mix(hash ^ mix(day))
+}
+
+/// The `SplitMix64` finalizer, which spreads small changes across all bits.
+const fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,185 @@
+use glam::Vec2;
+
+use super::{Circle, FRect};
+
+/// A straight line between two points, for things like line of sight and bullets.
+///
+/// ```
+/// # use baba::prelude::*;
+/// let sight = Segment::new(vec2(0., 8.), vec2(64., 8.));
+/// let wall = FRect::new(32., 0., 8., 16.);
+///
+/// let hit = sight.intersect_rect(&wall).unwrap();
+/// assert_eq!(hit.point, vec2(32., 8.));
+/// assert_eq!(hit.normal, vec2(-1., 0.));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    /// Where the segment starts
+    pub start: Vec2,
+    /// Where the segment ends
+    pub end: Vec2,
+}
+
+/// Where a [`Segment`] first hits something.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    /// The point that was hit.
+    pub point: Vec2,
+    /// The direction the surface faces at that point, with a length of 1. This is zero if the
+    /// segment started inside what it hit.
+    pub normal: Vec2,
+    /// How far along the segment the hit is, from 0 at the start to 1 at the end.
+    pub fraction: f32,
+}
+
+impl Segment {
+    /// Create a new segment between two points.
+    #[must_use]
+    pub const fn new(start: Vec2, end: Vec2) -> Self {
+        Self { start, end }
+    }
+
+    /// The vector from the start to the end.
+    #[must_use]
+    pub fn delta(&self) -> Vec2 {
+        self.end - self.start
+    }
+
+    /// The distance from the start to the end.
+    #[must_use]
+    pub fn length(&self) -> f32 {
+        self.delta().length()
+    }
+
+    /// The point at some fraction along the segment, from 0 at the start to 1 at the end.
+    #[must_use]
+    pub fn at(&self, fraction: f32) -> Vec2 {
+        self.start + self.delta() * fraction
+    }
+
+    fn hit(&self, fraction: f32, normal: Vec2) -> Hit {
+        Hit {
+            point: self.at(fraction),
+            normal,
+            fraction,
+        }
+    }
+
+    /// Where this segment crosses another, or `None` if it doesn't. Parallel segments never
+    /// cross.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Hit> {
+        let (delta, other_delta) = (self.delta(), other.delta());
+        let denominator = delta.perp_dot(other_delta);
+        if denominator == 0. {
+            return None;
+        }
+        let offset = other.start - self.start;
+        let fraction = offset.perp_dot(other_delta) / denominator;
+        let other_fraction = offset.perp_dot(delta) / denominator;
+        if !(0. ..=1.).contains(&fraction) || !(0. ..=1.).contains(&other_fraction) {
+            return None;
+        }
+        // Of the two sides of the other segment, the normal faces the one this one comes from
+        let mut normal = other_delta.perp().normalize_or_zero();
+        if normal.dot(delta) > 0. {
+            normal = -normal;
+        }
+        Some(self.hit(fraction, normal))
+    }
+
+    /// Where this segment first enters a rectangle, or `None` if it doesn't.
+    #[must_use]
+    pub fn intersect_rect(&self, rect: &FRect) -> Option<Hit> {
+        if rect.contains(self.start) {
+            return Some(self.hit(0., Vec2::ZERO));
+        }
+        let delta = self.delta();
+        let (mut enter, mut exit, mut normal) = (0_f32, 1_f32, Vec2::ZERO);
+        for (start, delta, min, max, axis) in [
+            (self.start.x, delta.x, rect.x, rect.x + rect.w, Vec2::X),
+            (self.start.y, delta.y, rect.y, rect.y + rect.h, Vec2::Y),
+        ] {
+            if delta == 0. {
+                if start < min || start > max {
+                    return None;
+                }
+                continue;
+            }
+            let (mut near, mut far) = ((min - start) / delta, (max - start) / delta);
+            let mut side = -axis;
+            if near > far {
+                (near, far) = (far, near);
+                side = axis;
+            }
+            if near > enter {
+                enter = near;
+                normal = side;
+            }
+            exit = exit.min(far);
+            if enter > exit {
+                return None;
+            }
+        }
+        Some(self.hit(enter, normal))
+    }
+
+    /// Where this segment first enters a circle, or `None` if it doesn't.
+    #[must_use]
+    pub fn intersect_circle(&self, circle: &Circle) -> Option<Hit> {
+        let offset = self.start - circle.center;
+        if offset.length_squared() <= circle.radius * circle.radius {
+            return Some(self.hit(0., Vec2::ZERO));
+        }
+        // Solve |offset + delta * t| = radius for the smallest t
+        let delta = self.delta();
+        let a = delta.length_squared();
+        let b = offset.dot(delta);
+        let c = offset.length_squared() - circle.radius * circle.radius;
+        let discriminant = b * b - a * c;
+        if a == 0. || discriminant < 0. {
+            return None;
+        }
+        let fraction = (-b - discriminant.sqrt()) / a;
+        if !(0. ..=1.).contains(&fraction) {
+            return None;
+        }
+        let point = self.at(fraction);
+        Some(Hit {
+            point,
+            normal: (point - circle.center).normalize_or_zero(),
+            fraction,
+        })
+    }
+}
+
+/// Casts a ray from `origin` in a direction, up to `max_distance` away, returning the index of
+/// the first rectangle it hits and where.
+///
+/// ```
+/// # use baba::prelude::*;
+/// let walls = [FRect::new(100., -50., 10., 100.), FRect::new(40., -50., 10., 100.)];
+///
+/// let (index, hit) = raycast(Vec2::ZERO, Vec2::X, 500., &walls).unwrap();
+/// assert_eq!(index, 1);
+/// assert_eq!(hit.point, vec2(40., 0.));
+/// ```
+#[must_use]
+pub fn raycast(
+    origin: Vec2,
+    direction: Vec2,
+    max_distance: f32,
+    rects: &[FRect],
+) -> Option<(usize, Hit)> {
+    let ray = Segment::new(
+        origin,
+        origin + direction.normalize_or_zero() * max_distance,
+    );
+    rects
+        .iter()
+        .enumerate()
+        .filter_map(|(i, rect)| Some((i, ray.intersect_rect(rect)?)))
+        .min_by(|(_, a), (_, b)| a.fraction.total_cmp(&b.fraction))
+}
@@ -0,0 +1,109 @@
+//! Steering behaviours, for moving agents around in a natural way.
+//!
+//! Every function returns a steering force: the change in velocity the agent wants to make. Add
+//! up the forces you want (possibly weighted), limit them, and apply them to the velocity:
+//!
+//! ```
+//! # use baba::prelude::*;
+//! use baba::math::steering;
+//!
+//! let (mut position, mut velocity) = (Vec2::ZERO, Vec2::X);
+//! let target = vec2(100., 50.);
+//! let max_speed = 4.;
+//!
+//! let force = steering::arrive(position, velocity, target, max_speed, 32.);
+//! velocity = (velocity + force.clamp_length_max(0.5)).clamp_length_max(max_speed);
+//! position += velocity;
+//! ```
+//!
+//! The flocking behaviours, [`separation`], [`alignment`] and [`cohesion`], take the agent's
+//! neighbours. Combined, they make boids.
+
+use glam::Vec2;
+
+/// Steers towards a target at full speed.
+#[must_use]
+pub fn seek(position: Vec2, velocity: Vec2, target: Vec2, max_speed: f32) -> Vec2 {
+    (target - position).normalize_or_zero() * max_speed - velocity
+}
+
+/// Steers away from a threat at full speed.
+#[must_use]
+pub fn flee(position: Vec2, velocity: Vec2, threat: Vec2, max_speed: f32) -> Vec2 {
+    (position - threat).normalize_or_zero() * max_speed - velocity
+}
+
+/// Steers towards a target, slowing down to stop on it once within `slowing_radius`.
+#[must_use]
+pub fn arrive(
+    position: Vec2,
+    velocity: Vec2,
+    target: Vec2,
+    max_speed: f32,
+    slowing_radius: f32,
+) -> Vec2 {
+    let offset = target - position;
+    let distance = offset.length();
+    if distance <= f32::EPSILON {
+        return -velocity;
+    }
+
+    let speed = if distance < slowing_radius {
+        max_speed * distance / slowing_radius
+    } else {
+        max_speed
+    };
+    offset / distance * speed - velocity
+}
+
+/// Steers in a random-looking, but smooth, direction.
+///
+/// A circle with `radius` is placed `distance` ahead of the agent, and it steers towards the point
+/// in that circle at `angle`. To wander around, nudge the angle by a small random amount every
+/// frame.
+#[must_use]
+pub fn wander(velocity: Vec2, distance: f32, radius: f32, angle: f32) -> Vec2 {
+    let ahead = velocity.normalize_or(Vec2::X) * distance;
+    let offset = Vec2::from_angle(velocity.to_angle() + angle) * radius;
+    ahead + offset
+}
+
+/// Steers away from neighbours which are closer than `radius`. Closer neighbours push harder.
+#[must_use]
+pub fn separation(position: Vec2, neighbours: impl IntoIterator<Item = Vec2>, radius: f32) -> Vec2 {
+    neighbours
+        .into_iter()
+        .filter_map(|other| {
+            let offset = position - other;
+            let distance = offset.length();
+            (distance > f32::EPSILON && distance < radius)
+                .then(|| offset / distance * (1. - distance / radius))
+        })
+        .sum()
+}
+
+/// Steers to match the average heading of neighbours, given their velocities.
+#[must_use]
+pub fn alignment(velocity: Vec2, neighbours: impl IntoIterator<Item = Vec2>) -> Vec2 {
+    average(neighbours).map_or(Vec2::ZERO, |average| average - velocity)
+}
+
+/// Steers towards the center of neighbours, given their positions.
+#[must_use]
+pub fn cohesion(
+    position: Vec2,
+    velocity: Vec2,
+    neighbours: impl IntoIterator<Item = Vec2>,
+    max_speed: f32,
+) -> Vec2 {
+    average(neighbours).map_or(Vec2::ZERO, |center| {
+        seek(position, velocity, center, max_speed)
+    })
+}
+
+fn average(points: impl IntoIterator<Item = Vec2>) -> Option<Vec2> {
+    let (sum, count) = points
+        .into_iter()
+        .fold((Vec2::ZERO, 0), |(sum, count), p| (sum + p, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
@@ -0,0 +1,102 @@
+//! Math for worlds which wrap around at the edges, like in Asteroids, where leaving one side
+//! brings you back on the other.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let (ship, rock) = (Texture::empty(), Texture::empty());
+//! # let (mut position, velocity, rock_position) = (Vec2::ZERO, Vec2::X, Vec2::ZERO);
+//! use baba::math::wrap::Wrap;
+//!
+//! let world = Wrap::new(vec2(640., 360.));
+//!
+//! // Every frame
+//! position = world.wrap(position + velocity);
+//! let to_rock = world.delta(position, rock_position);
+//!
+//! // Near an edge, the ship is also drawn on the other side
+//! world.draw(&ship, position, 16.);
+//! ```
+
+use glam::Vec2;
+
+use crate::gfx::{self, Drawable, Transform};
+
+/// A rectangular world from the origin to `size`, whose opposite edges are joined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Wrap {
+    /// Size of the world. Positions go from 0 up to this.
+    pub size: Vec2,
+}
+
+impl Wrap {
+    /// Creates a world of some size.
+    #[must_use]
+    pub const fn new(size: Vec2) -> Self {
+        Self { size }
+    }
+
+    /// Brings a position back inside the world.
+    #[must_use]
+    pub fn wrap(&self, position: Vec2) -> Vec2 {
+        position.rem_euclid(self.size)
+    }
+
+    /// The shortest vector from one position to another, which may cross an edge.
+    #[must_use]
+    pub fn delta(&self, from: Vec2, to: Vec2) -> Vec2 {
+        let half = self.size / 2.;
+        (to - from + half).rem_euclid(self.size) - half
+    }
+
+    /// The shortest distance between two positions, which may cross an edge.
+    #[must_use]
+    pub fn distance(&self, from: Vec2, to: Vec2) -> f32 {
+        self.delta(from, to).length()
+    }
+
+    /// The direction to go from one position to reach another the fastest, as a vector of
+    /// length 1. This is zero if they're in the same place.
+    #[must_use]
+    pub fn direction(&self, from: Vec2, to: Vec2) -> Vec2 {
+        self.delta(from, to).normalize_or_zero()
+    }
+
+    /// Every place an object needs to be drawn to appear on both sides of the edges it overlaps.
+    /// `radius` is how far the object reaches from its position. The position itself comes
+    /// first.
+    pub fn copies(&self, position: Vec2, radius: f32) -> impl Iterator<Item = Vec2> {
+        let side = |position: f32, size: f32| {
+            if position < radius {
+                size
+            } else if position > size - radius {
+                -size
+            } else {
+                0.
+            }
+        };
+        let (x, y) = (side(position.x, self.size.x), side(position.y, self.size.y));
+        [
+            Some(Vec2::ZERO),
+            (x != 0.).then(|| Vec2::new(x, 0.)),
+            (y != 0.).then(|| Vec2::new(0., y)),
+            (x != 0. && y != 0.).then(|| Vec2::new(x, y)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(move |offset| position + offset)
+    }
+
+    /// Draws an object, and copies of it on the other side of any edges it overlaps. `radius`
+    /// is how far the object reaches from its position.
+    pub fn draw<T: Drawable>(&self, object: &T, transform: impl Into<Transform>, radius: f32) {
+        let transform = transform.into();
+        let position = transform.to_affine().translation;
+        for copy in self.copies(position, radius) {
+            gfx::draw(
+                object,
+                Transform::from_translation(copy - position) * transform,
+            );
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::ffi::CString;
+
+use sdl2_sys::{SDL_MessageBoxFlags, SDL_ShowSimpleMessageBox};
+
+use crate::gfx::CANVAS;
+use crate::SdlError;
+
+/// The kind of a [`message_box`], which decides its icon.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    /// Something to let the player know.
+    #[default]
+    Info,
+    /// Something that might be a problem.
+    Warning,
+    /// Something went wrong.
+    Error,
+}
+
+/// Shows a message in a box from the operating system, and waits until it's closed.
+///
+/// This works even before the game is running, so it's a good way to tell players about errors
+/// they wouldn't see in a terminal.
+///
+/// ```no_run
+/// use baba::MessageKind;
+///
+/// # #[derive(Default)]
+/// # struct MyGame;
+/// # impl MyGame { fn update(&mut self) {} }
+/// if let Err(e) = baba::run("My game", MyGame::update) {
+///     let message = format!("The game crashed!\n\n{}", e.source_chain());
+///     let _ = baba::message_box("My game", &message, MessageKind::Error);
+/// }
+/// ```
+pub fn message_box(title: &str, message: &str, kind: MessageKind) -> Result<(), SdlError> {
+    let flags = match kind {
+        MessageKind::Info => SDL_MessageBoxFlags::SDL_MESSAGEBOX_INFORMATION,
+        MessageKind::Warning => SDL_MessageBoxFlags::SDL_MESSAGEBOX_WARNING,
+        MessageKind::Error => SDL_MessageBoxFlags::SDL_MESSAGEBOX_ERROR,
+    };
+    // Strings can't have null bytes in them, so anything after one is left out
+    let c_string = |s: &str| CString::new(s.split('\0').next().unwrap_or_default()).unwrap();
+    let (title, message) = (c_string(title), c_string(message));
+
+    // Shown on top of the window, if there is one
+    let window = CANVAS.with(|canvas| Some(canvas.try_borrow().ok()?.as_ref()?.window()));
+    let result = unsafe {
+        SDL_ShowSimpleMessageBox(
+            flags as u32,
+            title.as_ptr(),
+            message.as_ptr(),
+            window.unwrap_or(std::ptr::null_mut()),
+        )
+    };
+    if result < 0 {
+        return Err(SdlError::from_sdl());
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Rich presence, showing what the player is doing on platforms like Steam and Discord.
+//!
+//! ```no_run
+//! # let level = 3;
+//! baba::presence::set(&format!("In level {level}"), Some("2 lives left"));
+//!
+//! // Back in the menus
+//! baba::presence::set("Main menu", None);
+//! ```
+//!
+//! baba doesn't talk to any platform by itself, so this does nothing until a [`Backend`] is set.
+//! Games can call it everywhere, and only wire up a backend in the builds released on a platform.
+//!
+//! ```no_run
+//! use baba::presence::{self, Backend};
+//!
+//! struct Steam;
+//!
+//! impl Backend for Steam {
+//!     fn set(&mut self, state: &str, details: Option<&str>) {
+//!         // Call into the platform's SDK here
+//!     }
+//!
+//!     fn clear(&mut self) {}
+//! }
+//!
+//! presence::set_backend(Steam);
+//! ```
+
+use std::cell::RefCell;
+
+/// Sends presence updates to a platform.
+pub trait Backend {
+    /// Shows what the player is doing, with optional details.
+    fn set(&mut self, state: &str, details: Option<&str>);
+
+    /// Stops showing anything.
+    fn clear(&mut self);
+}
+
+#[derive(Default)]
+struct Presence {
+    backend: Option<Box<dyn Backend>>,
+    current: Option<(String, Option<String>)>,
+}
+
+thread_local! {
+    static PRESENCE: RefCell<Presence> = RefCell::new(Presence::default());
+}
+
+/// Sets where presence updates are sent. The current presence, if any, is sent to it right away.
+pub fn set_backend(backend: impl Backend + 'static) {
+    PRESENCE.with_borrow_mut(|presence| {
+        let mut backend = Box::new(backend);
+        if let Some((state, details)) = &presence.current {
+            backend.set(state, details.as_deref());
+        }
+        presence.backend = Some(backend);
+    });
+}
+
+/// Shows what the player is doing, like the level they're in. Setting the same presence again
+/// doesn't send another update, so this can be called every frame.
+pub fn set(state: &str, details: Option<&str>) {
+    PRESENCE.with_borrow_mut(|presence| {
+        if let Some((current_state, current_details)) = &presence.current {
+            if current_state == state && current_details.as_deref() == details {
+                return;
+            }
+        }
+        presence.current = Some((state.to_owned(), details.map(str::to_owned)));
+        if let Some(backend) = &mut presence.backend {
+            backend.set(state, details);
+        }
+    });
+}
+
+/// Stops showing what the player is doing.
+pub fn clear() {
+    PRESENCE.with_borrow_mut(|presence| {
+        if presence.current.take().is_some() {
+            if let Some(backend) = &mut presence.backend {
+                backend.clear();
+            }
+        }
+    });
+}
@@ -0,0 +1,118 @@
+//! Procedural generation of tile maps.
+//!
+//! Generators fill a [`Grid`] of tiles, which you can then draw, for example with a
+//! [`SpriteBatch`][crate::gfx::SpriteBatch]:
+//!
+//! - [`dungeon::bsp`] places rooms and joins them with corridors.
+//! - [`dungeon::drunkard_walk`] digs organic caves.
+//! - [`wfc::Rules`] solves a wave function collapse: it fills a grid with your own tiles, only
+//!   putting them next to tiles they're allowed to touch.
+//!
+//! Every generator takes a seed, and the same seed always generates the same map.
+
+use std::ops::{Index, IndexMut};
+
+pub mod dungeon;
+pub mod wfc;
+
+/// A 2D grid of tiles, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: u32,
+    height: u32,
+    tiles: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid filled with a tile.
+    #[must_use]
+    pub fn new(width: u32, height: u32, fill: T) -> Self {
+        let tiles = vec![fill; (width * height) as usize];
+        Self {
+            width,
+            height,
+            tiles,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// The width of this grid, in tiles.
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of this grid, in tiles.
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Is this position inside of the grid?
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub const fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height
+    }
+
+    /// The tile at a position, if it's inside of the grid.
+    #[must_use]
+    pub fn get(&self, x: i32, y: i32) -> Option<&T> {
+        #[allow(clippy::cast_sign_loss)]
+        self.contains(x, y)
+            .then(|| &self.tiles[(y as u32 * self.width + x as u32) as usize])
+    }
+
+    /// The tile at a position, mutably, if it's inside of the grid.
+    #[must_use]
+    pub fn get_mut(&mut self, x: i32, y: i32) -> Option<&mut T> {
+        #[allow(clippy::cast_sign_loss)]
+        self.contains(x, y)
+            .then(|| &mut self.tiles[(y as u32 * self.width + x as u32) as usize])
+    }
+
+    /// Every tile, row by row.
+    #[must_use]
+    pub fn tiles(&self) -> &[T] {
+        &self.tiles
+    }
+
+    /// Every tile with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32, &T)> {
+        let width = self.width;
+        let tiles = self.tiles.iter().enumerate();
+        tiles.map(move |(i, tile)| (i as u32 % width, i as u32 / width, tile))
+    }
+
+    /// Creates a grid with every tile converted.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            tiles: self.tiles.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<(u32, u32)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (u32, u32)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside of the grid"
+        );
+        &self.tiles[(y * self.width + x) as usize]
+    }
+}
+
+impl<T> IndexMut<(u32, u32)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (u32, u32)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside of the grid"
+        );
+        &mut self.tiles[(y * self.width + x) as usize]
+    }
+}
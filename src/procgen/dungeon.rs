@@ -0,0 +1,197 @@
+//! Dungeon generators, which dig floors out of a grid of walls.
+//!
+//! ```
+//! use baba::procgen::dungeon::{self, BspOptions, Tile};
+//!
+//! let dungeon = dungeon::bsp(64, 48, &BspOptions::default(), 1234);
+//! for (x, y, tile) in dungeon.tiles.iter() {
+//!     if *tile == Tile::Floor {
+//!         // Draw a floor tile at x, y
+//!     }
+//! }
+//!
+//! // Start the player in the first room
+//! let start = &dungeon.rooms[0];
+//! # assert!(dungeon.tiles[(start.x, start.y)] == Tile::Floor);
+//! ```
+
+use crate::math::rng::Rng;
+use crate::math::Rect;
+
+use super::Grid;
+
+/// A tile in a generated dungeon.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    /// Solid rock.
+    #[default]
+    Wall,
+    /// Open space, which can be walked on.
+    Floor,
+}
+
+/// A map of rooms joined by corridors.
+#[derive(Debug, Clone)]
+pub struct Dungeon {
+    /// Every tile of the map.
+    pub tiles: Grid<Tile>,
+    /// Every room, in the order they were placed.
+    pub rooms: Vec<Rect>,
+}
+
+/// Options for [`bsp`].
+#[derive(Debug, Clone)]
+pub struct BspOptions {
+    /// Smallest width and height of a room. Defaults to 4.
+    pub min_room: u32,
+    /// Largest width and height of a room. Defaults to 10.
+    pub max_room: u32,
+}
+
+impl Default for BspOptions {
+    fn default() -> Self {
+        Self {
+            min_room: 4,
+            max_room: 10,
+        }
+    }
+}
+
+/// Generates rooms joined by corridors, with binary space partitioning.
+///
+/// The map is split in two again and again, until every part fits a room. Each part gets a
+/// room, and the two halves of every split are joined by a corridor, so every room can be
+/// reached.
+///
+/// # Panics
+///
+/// If the map is too small for a room, with a wall around it, or `min_room` is larger than
+/// `max_room`.
+#[must_use]
+pub fn bsp(width: u32, height: u32, options: &BspOptions, seed: u64) -> Dungeon {
+    assert!(
+        options.min_room > 0 && options.min_room <= options.max_room,
+        "invalid room sizes"
+    );
+    assert!(
+        width >= options.min_room + 2 && height >= options.min_room + 2,
+        "{width}x{height} is too small for rooms"
+    );
+
+    let mut dungeon = Dungeon {
+        tiles: Grid::new(width, height, Tile::Wall),
+        rooms: Vec::new(),
+    };
+    let mut rng = Rng::new(seed);
+    let area = Rect::new(0, 0, width, height);
+    split(&area, options, &mut rng, &mut dungeon);
+    dungeon
+}
+
+/// Splits an area into rooms. Returns the index of one of them, to join to other areas.
+fn split(area: &Rect, options: &BspOptions, rng: &mut Rng, dungeon: &mut Dungeon) -> usize {
+    // Each side needs to fit a room with a wall around it
+    let min = options.min_room + 2;
+    let can_split_x = area.w >= min * 2;
+    let can_split_y = area.h >= min * 2;
+    let too_big = area.w > options.max_room + 2 || area.h > options.max_room + 2;
+
+    if too_big && (can_split_x || can_split_y) {
+        let split_x = match (can_split_x, can_split_y) {
+            (true, true) if area.w == area.h => rng.below(2) == 0,
+            (true, true) => area.w > area.h,
+            (split_x, _) => split_x,
+        };
+
+        let (a, b) = if split_x {
+            let at = rng.between(min, area.w - min);
+            let a = Rect::new(area.x, area.y, at, area.h);
+            (a, Rect::new(area.x + at, area.y, area.w - at, area.h))
+        } else {
+            let at = rng.between(min, area.h - min);
+            let a = Rect::new(area.x, area.y, area.w, at);
+            (a, Rect::new(area.x, area.y + at, area.w, area.h - at))
+        };
+
+        let a = split(&a, options, rng, dungeon);
+        let b = split(&b, options, rng, dungeon);
+        let (from, to) = (center(&dungeon.rooms[a]), center(&dungeon.rooms[b]));
+        dig_corridor(&mut dungeon.tiles, from, to, rng);
+        return if rng.below(2) == 0 { a } else { b };
+    }
+
+    let w = rng.between(options.min_room, options.max_room.min(area.w - 2));
+    let h = rng.between(options.min_room, options.max_room.min(area.h - 2));
+    let x = area.x + rng.between(1, area.w - w - 1);
+    let y = area.y + rng.between(1, area.h - h - 1);
+    for y in y..y + h {
+        for x in x..x + w {
+            dungeon.tiles[(x, y)] = Tile::Floor;
+        }
+    }
+
+    dungeon.rooms.push(Rect::new(x, y, w, h));
+    dungeon.rooms.len() - 1
+}
+
+const fn center(room: &Rect) -> (u32, u32) {
+    (room.x + room.w / 2, room.y + room.h / 2)
+}
+
+/// Digs an L-shaped corridor between two points.
+fn dig_corridor(tiles: &mut Grid<Tile>, from: (u32, u32), to: (u32, u32), rng: &mut Rng) {
+    let corner = if rng.below(2) == 0 {
+        (to.0, from.1)
+    } else {
+        (from.0, to.1)
+    };
+    for (a, b) in [(from, corner), (corner, to)] {
+        for y in a.1.min(b.1)..=a.1.max(b.1) {
+            for x in a.0.min(b.0)..=a.0.max(b.0) {
+                tiles[(x, y)] = Tile::Floor;
+            }
+        }
+    }
+}
+
+/// Digs a cave by walking around randomly, starting from the center.
+///
+/// Walking stops once `coverage` of the map, from 0 to 1, is floor. The edges of the map are
+/// always walls.
+#[must_use]
+pub fn drunkard_walk(width: u32, height: u32, coverage: f32, seed: u64) -> Grid<Tile> {
+    let mut tiles = Grid::new(width, height, Tile::Wall);
+    if width < 3 || height < 3 {
+        return tiles;
+    }
+
+    let inside = (width - 2) * (height - 2);
+    #[allow(clippy::cast_sign_loss)]
+    let target = ((coverage.clamp(0., 1.) * inside as f32) as u32).max(1);
+    // In case the walker gets unlucky, don't walk forever
+    let max_steps = inside.saturating_mul(100);
+
+    let mut rng = Rng::new(seed);
+    let (mut x, mut y) = (width / 2, height / 2);
+    let mut dug = 0;
+    for _ in 0..max_steps {
+        let tile = &mut tiles[(x, y)];
+        if *tile == Tile::Wall {
+            *tile = Tile::Floor;
+            dug += 1;
+            if dug >= target {
+                break;
+            }
+        }
+
+        match rng.below(4) {
+            0 if x > 1 => x -= 1,
+            1 if x < width - 2 => x += 1,
+            2 if y > 1 => y -= 1,
+            3 if y < height - 2 => y += 1,
+            _ => {}
+        }
+    }
+
+    tiles
+}
@@ -0,0 +1,196 @@
+//! Wave function collapse, for filling a grid with tiles which fit together.
+//!
+//! Describe which tiles may be next to each other, and the solver fills a grid so that every
+//! pair of neighbours is allowed. Each tile is an index, up to 64 of them.
+//!
+//! ```
+//! use baba::procgen::wfc::Rules;
+//!
+//! const WATER: usize = 0;
+//! const SAND: usize = 1;
+//! const GRASS: usize = 2;
+//!
+//! let mut rules = Rules::new(3);
+//! // Sand is always between water and grass
+//! rules.allow_all(WATER, WATER);
+//! rules.allow_all(WATER, SAND);
+//! rules.allow_all(SAND, SAND);
+//! rules.allow_all(SAND, GRASS);
+//! rules.allow_all(GRASS, GRASS);
+//! rules.set_weight(GRASS, 4.);
+//!
+//! let map = rules.solve(32, 32, 1234).expect("no solution");
+//! # for (x, y, &tile) in map.iter() {
+//! #     if let Some(&right) = map.get(x as i32 + 1, y as i32) {
+//! #         assert!(tile.abs_diff(right) <= 1);
+//! #     }
+//! # }
+//! ```
+
+use super::Grid;
+use crate::math::rng::Rng;
+
+/// Largest number of tiles [`Rules`] can have.
+pub const MAX_TILES: usize = 64;
+
+/// Which tiles can be next to each other.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    weights: Vec<f32>,
+    // Tiles allowed to the right of, and below, each tile
+    right: Vec<u64>,
+    below: Vec<u64>,
+    attempts: u32,
+}
+
+impl Rules {
+    /// Creates rules for some number of tiles, which can't be next to anything yet.
+    ///
+    /// # Panics
+    ///
+    /// If there are more than [`MAX_TILES`] tiles.
+    #[must_use]
+    pub fn new(tiles: usize) -> Self {
+        assert!(
+            tiles <= MAX_TILES,
+            "at most {MAX_TILES} tiles are supported"
+        );
+        Self {
+            weights: vec![1.; tiles],
+            right: vec![0; tiles],
+            below: vec![0; tiles],
+            attempts: 10,
+        }
+    }
+
+    /// Allows `right` to be placed to the right of `left`.
+    pub fn allow_horizontal(&mut self, left: usize, right: usize) {
+        self.right[left] |= 1 << right;
+    }
+
+    /// Allows `below` to be placed under `above`.
+    pub fn allow_vertical(&mut self, above: usize, below: usize) {
+        self.below[above] |= 1 << below;
+    }
+
+    /// Allows two tiles to be next to each other, in any direction.
+    pub fn allow_all(&mut self, a: usize, b: usize) {
+        self.allow_horizontal(a, b);
+        self.allow_horizontal(b, a);
+        self.allow_vertical(a, b);
+        self.allow_vertical(b, a);
+    }
+
+    /// Sets how often a tile is picked, compared to others. Defaults to 1.
+    pub fn set_weight(&mut self, tile: usize, weight: f32) {
+        self.weights[tile] = weight;
+    }
+
+    /// Sets how many times solving starts over when it gets stuck. Defaults to 10.
+    pub const fn set_attempts(&mut self, attempts: u32) {
+        self.attempts = attempts;
+    }
+
+    /// Fills a grid with tiles. Returns `None` if no solution was found, which may happen if
+    /// the rules are too strict.
+    #[must_use]
+    pub fn solve(&self, width: u32, height: u32, seed: u64) -> Option<Grid<usize>> {
+        let mut rng = Rng::new(seed);
+        (0..self.attempts.max(1)).find_map(|_| self.try_solve(width, height, &mut rng))
+    }
+
+    fn try_solve(&self, width: u32, height: u32, rng: &mut Rng) -> Option<Grid<usize>> {
+        let all = if self.weights.len() == MAX_TILES {
+            u64::MAX
+        } else {
+            (1 << self.weights.len()) - 1
+        };
+        let mut wave = Grid::new(width, height, all);
+        let mut stack = Vec::new();
+
+        loop {
+            // Collapse the most constrained cell next, picking randomly between ties
+            let mut best = None;
+            let mut best_count = u32::MAX;
+            let mut ties = 0;
+            for (x, y, &options) in wave.iter() {
+                let count = options.count_ones();
+                if count == 0 {
+                    return None;
+                }
+                if count == 1 || count > best_count {
+                    continue;
+                }
+                if count < best_count {
+                    (best_count, ties) = (count, 0);
+                }
+                ties += 1;
+                if rng.below(ties) == 0 {
+                    best = Some((x, y));
+                }
+            }
+            let Some((x, y)) = best else {
+                return Some(wave.map(|options| options.trailing_zeros() as usize));
+            };
+
+            let tile = self.pick(wave[(x, y)], rng)?;
+            wave[(x, y)] = 1 << tile;
+            stack.push((x, y));
+            self.propagate(&mut wave, &mut stack)?;
+        }
+    }
+
+    /// Picks one of the options at random, by weight.
+    fn pick(&self, options: u64, rng: &mut Rng) -> Option<usize> {
+        let tiles = (0..self.weights.len()).filter(|i| options & 1 << i != 0);
+        let total: f32 = tiles.clone().map(|i| self.weights[i]).sum();
+        let mut roll = rng.float() * total;
+        let mut last = None;
+        for tile in tiles {
+            last = Some(tile);
+            roll -= self.weights[tile];
+            if roll < 0. {
+                break;
+            }
+        }
+        last
+    }
+
+    /// Removes options which can't fit next to their neighbours, starting from changed cells.
+    fn propagate(&self, wave: &mut Grid<u64>, stack: &mut Vec<(u32, u32)>) -> Option<()> {
+        while let Some((x, y)) = stack.pop() {
+            let options = wave[(x, y)];
+            let (mut right, mut left, mut below, mut above) = (0, 0, 0, 0);
+            for tile in (0..self.weights.len()).filter(|i| options & 1 << i != 0) {
+                right |= self.right[tile];
+                below |= self.below[tile];
+            }
+            for tile in 0..self.weights.len() {
+                if self.right[tile] & options != 0 {
+                    left |= 1 << tile;
+                }
+                if self.below[tile] & options != 0 {
+                    above |= 1 << tile;
+                }
+            }
+
+            let (x, y) = (x as i32, y as i32);
+            for (dx, dy, allowed) in [(1, 0, right), (-1, 0, left), (0, 1, below), (0, -1, above)] {
+                let (nx, ny) = (x + dx, y + dy);
+                let Some(neighbour) = wave.get_mut(nx, ny) else {
+                    continue;
+                };
+                let narrowed = *neighbour & allowed;
+                if narrowed == 0 {
+                    return None;
+                }
+                if narrowed != *neighbour {
+                    *neighbour = narrowed;
+                    #[allow(clippy::cast_sign_loss)]
+                    stack.push((nx as u32, ny as u32));
+                }
+            }
+        }
+        Some(())
+    }
+}
@@ -0,0 +1,222 @@
+//! Ropes and chains which swing and sag, simulated with verlet integration.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let (anchor, player) = (vec2(160., 20.), vec2(200., 120.));
+//! use baba::rope::Rope;
+//!
+//! let mut rope = Rope::new(anchor, player, 16).with_thickness(2.);
+//!
+//! // Every frame, keep the ends attached to whatever they're tied to
+//! rope.pin(0, anchor);
+//! rope.pin(rope.len() - 1, player);
+//! rope.update();
+//! gfx::draw(&rope, Vec2::ZERO);
+//! ```
+//!
+//! To draw a chain instead, draw a sprite along each of the [`segments`][Rope::segments].
+
+use glam::Vec2;
+
+use crate::alloc::with_frame_arena;
+use crate::gfx::{Canvas, Color, Drawable, Transform, Vertex};
+use crate::time;
+
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    position: Vec2,
+    previous: Vec2,
+    pinned: bool,
+}
+
+/// A string of points held together at a fixed distance from each other.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Rope {
+    points: Vec<Point>,
+    length: f32,
+    gravity: Vec2,
+    damping: f32,
+    iterations: u32,
+    thickness: f32,
+    color: Color,
+}
+
+impl Rope {
+    /// Creates a rope in a straight line between two points, made of some number of segments.
+    /// It's as long as the distance between them, and neither end is pinned.
+    pub fn new(start: Vec2, end: Vec2, segments: usize) -> Self {
+        let segments = segments.max(1);
+        let points = (0..=segments).map(|i| {
+            let position = start.lerp(end, i as f32 / segments as f32);
+            Point {
+                position,
+                previous: position,
+                pinned: false,
+            }
+        });
+        Self {
+            points: points.collect(),
+            length: start.distance(end) / segments as f32,
+            gravity: Vec2::new(0., 400.),
+            damping: 0.99,
+            iterations: 10,
+            thickness: 1.,
+            color: Color::WHITE,
+        }
+    }
+
+    /// Sets the length of each segment. By default, the rope starts out tight.
+    pub const fn with_segment_length(mut self, length: f32) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Sets the gravity, in pixels per second squared. Defaults to 400 downwards.
+    pub const fn with_gravity(mut self, gravity: Vec2) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Sets how much speed is kept every frame, from 0 to 1. Defaults to 0.99.
+    pub const fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets how many times the segments are pulled back to their length every update. More
+    /// iterations make the rope less stretchy. Defaults to 10.
+    pub const fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets how thick the rope is drawn. Defaults to 1.
+    pub const fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets the color the rope is drawn with. Defaults to white.
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Number of points in the rope, one more than the number of segments.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Does this rope have no points? This is never the case.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Holds a point in place, attaching it to something. Call this every frame to drag it
+    /// along.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn pin(&mut self, index: usize, position: Vec2) {
+        let point = &mut self.points[index];
+        point.position = position;
+        point.pinned = true;
+    }
+
+    /// Lets go of a point, so it moves freely again.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn unpin(&mut self, index: usize) {
+        self.points[index].pinned = false;
+    }
+
+    /// The position of a point.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    #[must_use]
+    pub fn point(&self, index: usize) -> Vec2 {
+        self.points[index].position
+    }
+
+    /// Every point, from start to end.
+    pub fn points(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.points.iter().map(|p| p.position)
+    }
+
+    /// Every segment, as pairs of points from start to end.
+    pub fn segments(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        self.points
+            .windows(2)
+            .map(|w| (w[0].position, w[1].position))
+    }
+
+    /// Moves the rope along. This should be called every frame.
+    pub fn update(&mut self) {
+        let delta = time::delta();
+        let gravity = self.gravity * delta * delta;
+        for point in self.points.iter_mut().filter(|p| !p.pinned) {
+            let velocity = (point.position - point.previous) * self.damping;
+            point.previous = point.position;
+            point.position += velocity + gravity;
+        }
+        for point in self.points.iter_mut().filter(|p| p.pinned) {
+            point.previous = point.position;
+        }
+
+        for _ in 0..self.iterations {
+            for i in 0..self.points.len() - 1 {
+                let (a, b) = (self.points[i], self.points[i + 1]);
+                let offset = b.position - a.position;
+                let distance = offset.length();
+                if distance <= f32::EPSILON {
+                    continue;
+                }
+                let correction = offset * (distance - self.length) / distance;
+                // Pinned points don't move, so the other one moves all the way
+                let (move_a, move_b) = match (a.pinned, b.pinned) {
+                    (true, true) => continue,
+                    (true, false) => (0., 1.),
+                    (false, true) => (1., 0.),
+                    (false, false) => (0.5, 0.5),
+                };
+                self.points[i].position += correction * move_a;
+                self.points[i + 1].position -= correction * move_b;
+            }
+        }
+    }
+}
+
+impl Drawable for Rope {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        let count = self.points.len();
+        with_frame_arena(|arena| {
+            // Both sides of the rope are interleaved
+            let vertex = Vertex::from_xy_color(Vec2::ZERO, self.color);
+            let verts = arena.alloc_slice_fill(count * 2, vertex);
+            let indices = arena.alloc_slice_fill((count - 1) * 6, 0);
+
+            for i in 0..count {
+                let before = self.points[i.saturating_sub(1)].position;
+                let after = self.points[(i + 1).min(count - 1)].position;
+                let normal = (after - before).perp().normalize_or_zero() * self.thickness / 2.;
+                let position = self.points[i].position;
+                verts[i * 2].coord = transform.transform_point(position + normal);
+                verts[i * 2 + 1].coord = transform.transform_point(position - normal);
+            }
+            for i in 0..count - 1 {
+                let (a, b) = (i as i32 * 2, i as i32 * 2 + 2);
+                indices[i * 6..i * 6 + 6].copy_from_slice(&[a, b, a + 1, a + 1, b, b + 1]);
+            }
+
+            canvas.draw_colored_geometry(verts, Some(indices));
+        });
+    }
+}
@@ -0,0 +1,170 @@
+//! Scenes, like menus, levels and pause screens, kept in a stack.
+//!
+//! The scene on top of the stack is updated every frame, after the game's own `update`. Pushing
+//! a scene covers the one below it until it's popped, which is how a pause screen goes over a
+//! level, and replacing a scene moves on, like from a menu to the game.
+//!
+//! ```no_run
+//! use baba::prelude::*;
+//! use baba::scene::{self, Scene};
+//!
+//! struct Menu;
+//! struct Level {
+//!     time: f32,
+//! }
+//! struct Paused;
+//!
+//! impl Scene for Menu {
+//!     fn update(&mut self) {
+//!         if is_key_pressed(KeyCode::Return) {
+//!             scene::replace_scene(Level { time: 0. });
+//!         }
+//!     }
+//! }
+//!
+//! impl Scene for Level {
+//!     fn update(&mut self) {
+//!         self.time += baba::time::delta();
+//!         if is_key_pressed(KeyCode::Escape) {
+//!             scene::push_scene(Paused);
+//!         }
+//!     }
+//!
+//!     fn draw(&self) {
+//!         // Draw the level
+//!     }
+//! }
+//!
+//! impl Scene for Paused {
+//!     fn update(&mut self) {
+//!         if is_key_pressed(KeyCode::Escape) {
+//!             scene::pop_scene();
+//!         }
+//!     }
+//!
+//!     fn draw(&self) {
+//!         gfx::draw_rect(gfx::screen_size(), Color::from_black_alpha(128), Vec2::ZERO);
+//!     }
+//!
+//!     fn is_overlay(&self) -> bool {
+//!         true
+//!     }
+//! }
+//!
+//! # fn main() -> baba::Result {
+//! baba::game("My game", |()| {}).run_with(|| scene::push_scene(Menu))
+//! # }
+//! ```
+//!
+//! Changes to the stack are applied once the current scene is done updating, so scenes can push,
+//! pop and replace themselves.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// A scene in the stack. All hooks have empty defaults, so you only need to implement the ones
+/// you use.
+pub trait Scene {
+    /// Called when the scene is added to the stack.
+    fn enter(&mut self) {}
+
+    /// Called when the scene is removed from the stack.
+    fn exit(&mut self) {}
+
+    /// Called every frame while this scene is on top of the stack.
+    fn update(&mut self) {}
+
+    /// Called every frame after updating, while this scene is visible. Scenes are drawn from the
+    /// bottom up.
+    fn draw(&self) {}
+
+    /// Does the scene below show through this one? This is usually the case for pause screens
+    /// and dialogs. Defaults to `false`, so only this scene is drawn.
+    fn is_overlay(&self) -> bool {
+        false
+    }
+}
+
+enum Change {
+    Push(Box<dyn Scene>),
+    Pop,
+    Replace(Box<dyn Scene>),
+    Clear,
+}
+
+thread_local! {
+    static STACK: RefCell<Vec<Box<dyn Scene>>> = const { RefCell::new(Vec::new()) };
+    static CHANGES: RefCell<VecDeque<Change>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// Adds a scene on top of the stack, covering the current one.
+pub fn push_scene(scene: impl Scene + 'static) {
+    CHANGES.with_borrow_mut(|changes| changes.push_back(Change::Push(Box::new(scene))));
+}
+
+/// Removes the scene on top of the stack, going back to the one below it.
+pub fn pop_scene() {
+    CHANGES.with_borrow_mut(|changes| changes.push_back(Change::Pop));
+}
+
+/// Replaces the scene on top of the stack with another.
+pub fn replace_scene(scene: impl Scene + 'static) {
+    CHANGES.with_borrow_mut(|changes| changes.push_back(Change::Replace(Box::new(scene))));
+}
+
+/// Removes every scene from the stack.
+pub fn clear_scenes() {
+    CHANGES.with_borrow_mut(|changes| changes.push_back(Change::Clear));
+}
+
+/// Updates the scene on top, and draws every visible scene. Called by the game loop.
+pub(crate) fn update() {
+    apply_changes();
+    with_stack(|stack| {
+        if let Some(top) = stack.last_mut() {
+            top.update();
+        }
+    });
+    apply_changes();
+    with_stack(|stack| {
+        let bottom = stack.iter().rposition(|s| !s.is_overlay()).unwrap_or(0);
+        for scene in &stack[bottom..] {
+            scene.draw();
+        }
+    });
+}
+
+/// Runs `f` with the stack moved out, so scenes can change it while their hooks run.
+fn with_stack(f: impl FnOnce(&mut Vec<Box<dyn Scene>>)) {
+    let mut stack = STACK.take();
+    f(&mut stack);
+    STACK.set(stack);
+}
+
+fn apply_changes() {
+    while let Some(change) = CHANGES.with_borrow_mut(VecDeque::pop_front) {
+        with_stack(|stack| match change {
+            Change::Push(mut scene) => {
+                scene.enter();
+                stack.push(scene);
+            }
+            Change::Pop => {
+                if let Some(mut scene) = stack.pop() {
+                    scene.exit();
+                }
+            }
+            Change::Replace(mut scene) => {
+                if let Some(mut old) = stack.pop() {
+                    old.exit();
+                }
+                scene.enter();
+                stack.push(scene);
+            }
+            Change::Clear => {
+                while let Some(mut scene) = stack.pop() {
+                    scene.exit();
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,171 @@
+//! Loading and unloading parts of a large world as the player moves around.
+//!
+//! The world is split into a grid of chunks. A [`Streamer`] keeps the chunks near a point loaded,
+//! loading new ones in the background with [`jobs`][crate::jobs], and drops the ones which are
+//! far away.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let camera = Camera::default();
+//! use baba::streaming::Streamer;
+//!
+//! struct Chunk {
+//!     tiles: Vec<u8>,
+//! }
+//!
+//! let mut world = Streamer::new(vec2(256., 256.), |coord: IVec2| {
+//!     let path = format!("resources/world/{}_{}.bin", coord.x, coord.y);
+//!     Chunk { tiles: std::fs::read(path).unwrap_or_default() }
+//! });
+//!
+//! // Every frame
+//! let _unloaded = world.update(camera.screen_to_world(vec2(160., 90.)));
+//! for (coord, chunk) in world.iter() {
+//!     // Draw the chunk
+//! }
+//! ```
+//!
+//! Chunks are loaded on worker threads, so they can't hold textures. Keep pixel data in them,
+//! and create textures with [`Texture::from_rgba8`][crate::gfx::Texture::from_rgba8] when
+//! they're first drawn.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use glam::{IVec2, Vec2};
+
+use crate::jobs::{self, Job, JobPanicked, Priority};
+
+/// Keeps the chunks around a point loaded.
+pub struct Streamer<C> {
+    chunk_size: Vec2,
+    load_radius: u32,
+    unload_radius: u32,
+    load: Arc<dyn Fn(IVec2) -> C + Send + Sync>,
+    loaded: HashMap<IVec2, C>,
+    pending: HashMap<IVec2, Job<C>>,
+}
+
+impl<C: Send + 'static> Streamer<C> {
+    /// Creates a streamer for chunks of a size in world units, loaded with a function.
+    ///
+    /// By default, the chunk around the center and the ones next to it are kept loaded.
+    pub fn new(chunk_size: Vec2, load: impl Fn(IVec2) -> C + Send + Sync + 'static) -> Self {
+        Self {
+            chunk_size,
+            load_radius: 1,
+            unload_radius: 2,
+            load: Arc::new(load),
+            loaded: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Sets how many chunks away from the center are loaded, and how far they can go before
+    /// they're unloaded.
+    ///
+    /// The unload radius should be larger, so that walking back and forth over the border of a
+    /// chunk doesn't keep loading and unloading it.
+    #[must_use]
+    pub fn with_radius(mut self, load: u32, unload: u32) -> Self {
+        self.load_radius = load;
+        self.unload_radius = unload.max(load);
+        self
+    }
+
+    /// The chunk a point in the world is in.
+    #[must_use]
+    pub fn chunk_at(&self, point: Vec2) -> IVec2 {
+        (point / self.chunk_size).floor().as_ivec2()
+    }
+
+    /// The position of the top left corner of a chunk, in the world.
+    #[must_use]
+    pub fn chunk_position(&self, coord: IVec2) -> Vec2 {
+        coord.as_vec2() * self.chunk_size
+    }
+
+    /// Loads chunks near a point in the world, and unloads far away ones, which are returned.
+    ///
+    /// Chunks which finished loading since the previous update become available, and ones whose
+    /// loader panicked are logged and loaded again. This should be called every frame.
+    pub fn update(&mut self, center: Vec2) -> Vec<(IVec2, C)> {
+        let center = self.chunk_at(center);
+        let distance = |coord: IVec2| (coord - center).abs().max_element().unsigned_abs();
+
+        self.pending.retain(|coord, job| {
+            if distance(*coord) > self.unload_radius {
+                return false;
+            }
+            match job.try_take() {
+                Ok(Some(chunk)) => {
+                    self.loaded.insert(*coord, chunk);
+                    false
+                }
+                Ok(None) => true,
+                Err(JobPanicked) => {
+                    log::error!("Loading chunk {coord} panicked, trying again");
+                    false
+                }
+            }
+        });
+
+        let radius = self.load_radius as i32;
+        for y in -radius..=radius {
+            for x in -radius..=radius {
+                let coord = center + IVec2::new(x, y);
+                if self.loaded.contains_key(&coord) || self.pending.contains_key(&coord) {
+                    continue;
+                }
+                // The chunk the center is in is needed the soonest
+                let priority = if coord == center {
+                    Priority::High
+                } else {
+                    Priority::Normal
+                };
+                let load = self.load.clone();
+                let job = jobs::spawn_with(priority, move || load(coord));
+                self.pending.insert(coord, job);
+            }
+        }
+
+        let far = self
+            .loaded
+            .keys()
+            .copied()
+            .filter(|&c| distance(c) > self.unload_radius);
+        let far: Vec<_> = far.collect();
+        far.into_iter()
+            .filter_map(|coord| Some((coord, self.loaded.remove(&coord)?)))
+            .collect()
+    }
+
+    /// A loaded chunk.
+    #[must_use]
+    pub fn get(&self, coord: IVec2) -> Option<&C> {
+        self.loaded.get(&coord)
+    }
+
+    /// A loaded chunk, mutably.
+    #[must_use]
+    pub fn get_mut(&mut self, coord: IVec2) -> Option<&mut C> {
+        self.loaded.get_mut(&coord)
+    }
+
+    /// Is this chunk still being loaded?
+    #[must_use]
+    pub fn is_loading(&self, coord: IVec2) -> bool {
+        self.pending.contains_key(&coord)
+    }
+
+    /// All loaded chunks, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &C)> {
+        self.loaded.iter().map(|(coord, chunk)| (*coord, chunk))
+    }
+
+    /// Unloads every chunk, and forgets the ones being loaded.
+    pub fn clear(&mut self) -> Vec<(IVec2, C)> {
+        self.pending.clear();
+        self.loaded.drain().collect()
+    }
+}
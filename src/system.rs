@@ -0,0 +1,209 @@
+//! Information about the system the game is running on, like its language and clock.
+//!
+//! ```no_run
+//! // Pick a language on first launch
+//! let language = baba::system::preferred_language(&["en", "fr", "pt-BR"]).unwrap_or("en");
+//! ```
+//!
+//! The real-world clock is useful for daily challenges and seasonal content:
+//!
+//! ```no_run
+//! use baba::system::{self, ClockEvent, Daily};
+//!
+//! let winter = system::is_date_between((12, 1), (2, 28));
+//! let mut reset = Daily::new("daily_reset", 0, 0);
+//!
+//! // Every frame
+//! reset.update();
+//! for event in baba::events::drain::<ClockEvent>() {
+//!     if event.name == "daily_reset" {
+//!         // Roll new daily quests
+//!     }
+//! }
+//! ```
+
+use std::ffi::CStr;
+use std::fmt;
+use std::time::Duration;
+
+pub use chrono::{DateTime, Datelike, Local, NaiveTime, Timelike, Utc, Weekday};
+use sdl2_sys::{SDL_GetPreferredLocales, SDL_free};
+
+use crate::{events, SdlError};
+
+/// A language, and optionally the country it's used in, like `en-US`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Locale {
+    /// Language code, like `"en"` for English.
+    pub language: String,
+    /// Country code, like `"US"` for the United States, if the system gave one.
+    pub country: Option<String>,
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.country {
+            Some(country) => write!(f, "{}-{country}", self.language),
+            None => f.write_str(&self.language),
+        }
+    }
+}
+
+/// The player's preferred locales, from most to least preferred. This is empty if the system
+/// doesn't say.
+///
+/// This can change while the game is running, if the player changes their system settings.
+#[must_use]
+pub fn preferred_locales() -> Vec<Locale> {
+    let list = unsafe { SDL_GetPreferredLocales() };
+    if list.is_null() {
+        log::warn!("Failed to get locales: {}", SdlError::from_sdl());
+        return Vec::new();
+    }
+
+    let string = |ptr| {
+        unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned()
+    };
+    let mut locales = Vec::new();
+    for i in 0.. {
+        let locale = unsafe { &*list.add(i) };
+        if locale.language.is_null() {
+            break;
+        }
+        locales.push(Locale {
+            language: string(locale.language),
+            country: (!locale.country.is_null()).then(|| string(locale.country)),
+        });
+    }
+    unsafe { SDL_free(list.cast()) };
+    locales
+}
+
+/// Picks the language the player would like most, out of the ones the game has, or `None` if
+/// none of them match.
+///
+/// Languages are given as codes like `"en"` or `"pt-BR"`. A locale matches a language with the
+/// same country first, and then one without a country.
+#[must_use]
+pub fn preferred_language<'a>(available: &[&'a str]) -> Option<&'a str> {
+    preferred_locales().iter().find_map(|locale| {
+        let full = locale.to_string();
+        let find = |code: &str| {
+            available
+                .iter()
+                .find(|a| a.replace('_', "-").eq_ignore_ascii_case(code))
+                .copied()
+        };
+        find(&full).or_else(|| find(&locale.language))
+    })
+}
+
+/// The current date and time, in the player's time zone.
+#[must_use]
+pub fn local_time() -> DateTime<Local> {
+    Local::now()
+}
+
+/// The current date and time in UTC, which is the same for every player.
+#[must_use]
+pub fn utc_time() -> DateTime<Utc> {
+    Utc::now()
+}
+
+/// Is today between two dates, given as month and day, including both? The range can wrap
+/// around the new year, like `(12, 20)` to `(1, 6)`. Good for turning seasonal content on and
+/// off.
+#[must_use]
+pub fn is_date_between(from: (u32, u32), to: (u32, u32)) -> bool {
+    let today = local_time();
+    let today = (today.month(), today.day());
+    if from <= to {
+        from <= today && today <= to
+    } else {
+        today >= from || today <= to
+    }
+}
+
+/// Sent as an [event][crate::events] when a [`Daily`] time is reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockEvent {
+    /// Name given to the [`Daily`].
+    pub name: String,
+    /// When it was due.
+    pub time: DateTime<Local>,
+}
+
+/// Something that happens every day at a set time of day, in the player's time zone, like a
+/// daily reset at midnight.
+#[derive(Debug, Clone)]
+pub struct Daily {
+    name: String,
+    time: NaiveTime,
+    next: DateTime<Local>,
+}
+
+impl Daily {
+    /// Creates a daily event at an hour and minute of the day. It first happens the next time
+    /// the clock reaches that time.
+    ///
+    /// # Panics
+    ///
+    /// If the hour or minute are out of range.
+    #[must_use]
+    pub fn new(name: impl Into<String>, hour: u32, minute: u32) -> Self {
+        let time = NaiveTime::from_hms_opt(hour, minute, 0).expect("invalid time of day");
+        let next = next_after(local_time(), time);
+        let name = name.into();
+        Self { name, time, next }
+    }
+
+    /// When this happens next.
+    #[must_use]
+    pub const fn next(&self) -> DateTime<Local> {
+        self.next
+    }
+
+    /// Time left until this happens next, like for a countdown.
+    #[must_use]
+    pub fn time_left(&self) -> Duration {
+        (self.next - local_time()).to_std().unwrap_or_default()
+    }
+
+    /// Sends a [`ClockEvent`] if the time was reached, returning whether it was. This should be
+    /// called every frame. If the game wasn't running for a few days, it's only sent once.
+    pub fn update(&mut self) -> bool {
+        let now = local_time();
+        if now < self.next {
+            return false;
+        }
+        events::emit(ClockEvent {
+            name: self.name.clone(),
+            time: self.next,
+        });
+        self.next = next_after(now, self.time);
+        true
+    }
+}
+
+/// The first time after `now` that the clock reads `time`.
+fn next_after(now: DateTime<Local>, time: NaiveTime) -> DateTime<Local> {
+    let mut date = now.date_naive();
+    loop {
+        // Times skipped by daylight saving happen at the end of the skipped hour instead
+        let at = date.and_time(time);
+        let at = at.and_local_timezone(Local).earliest().or_else(|| {
+            (at + chrono::Duration::hours(1))
+                .and_local_timezone(Local)
+                .earliest()
+        });
+        match at {
+            Some(at) if at > now => return at,
+            _ => match date.succ_opt() {
+                Some(next) => date = next,
+                None => return now,
+            },
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Frame timing.
+//!
+//! Use [`delta`] to make movement independent of the framerate:
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let mut position = Vec2::ZERO;
+//! let speed = 120.; // pixels per second
+//! position.x += speed * baba::time::delta();
+//! ```
+//!
+//! The game also keeps statistics about recent frames, for FPS counters and spotting hitches:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! let fps = baba::time::fps();
+//! let worst = baba::time::frame_time_percentile(0.99);
+//! if worst > Duration::from_millis(50) {
+//!     log::warn!("Hitching at {fps:.0} FPS, slowest frames took {worst:?}");
+//! }
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Longest delta reported by [`delta`]. Longer frames, such as when the window is being dragged,
+/// are clamped to this so that things don't jump across the screen.
+pub(crate) const MAX_DELTA: Duration = Duration::from_millis(250);
+
+/// Number of frames kept for [`fps`] and the other frame statistics.
+pub const FRAME_HISTORY: usize = 120;
+
+#[derive(Clone, Copy)]
+struct Clock {
+    delta: Duration,
+    elapsed: Duration,
+    frame: u64,
+    cpu: Duration,
+    present: Duration,
+    input: Duration,
+}
+
+thread_local! {
+    static CLOCK: Cell<Clock> = const {
+        Cell::new(Clock {
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            frame: 0,
+            cpu: Duration::ZERO,
+            present: Duration::ZERO,
+            input: Duration::ZERO,
+        })
+    };
+    static FRAME_TIMES: RefCell<VecDeque<Duration>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// Time since the previous update, in seconds.
+///
+/// This is capped at a quarter of a second.
+#[must_use]
+pub fn delta() -> f32 {
+    delta_duration().as_secs_f32()
+}
+
+/// Time since the previous update, as a [`Duration`].
+#[must_use]
+pub fn delta_duration() -> Duration {
+    CLOCK.get().delta
+}
+
+/// Time since the game started, in seconds. This is the sum of all deltas.
+#[must_use]
+pub fn elapsed() -> f32 {
+    CLOCK.get().elapsed.as_secs_f32()
+}
+
+/// Number of updates since the game started. The first update is frame 0.
+#[must_use]
+pub fn frame() -> u64 {
+    CLOCK.get().frame
+}
+
+/// Time the CPU spent on the previous frame, updating and submitting draws.
+#[must_use]
+pub fn cpu_time() -> Duration {
+    CLOCK.get().cpu
+}
+
+/// Time spent waiting for the previous frame to be presented.
+///
+/// SDL2 has no GPU timing queries, so this is an estimate of GPU time: presenting blocks while
+/// the GPU catches up, so a high value compared to [`cpu_time`] points to fill-rate problems,
+/// like too much overdraw, rather than slow game logic. With vsync, this also includes waiting
+/// for the display.
+#[must_use]
+pub fn present_time() -> Duration {
+    CLOCK.get().present
+}
+
+/// How long input waited before an update could handle it, measured from when the system
+/// received the oldest input to the start of the update. This is from the latest update which
+/// had any input, and only has millisecond precision.
+///
+/// Events are handled right before each update, so this is mostly the time spent waiting for
+/// the previous frame to finish. Display and driver latency come on top of it.
+#[must_use]
+pub fn input_latency() -> Duration {
+    CLOCK.get().input
+}
+
+/// How long the previous frame took, from one frame being shown to the next. Unlike [`delta`],
+/// this isn't capped, and with a fixed update rate it can span several updates.
+#[must_use]
+pub fn frame_time() -> Duration {
+    FRAME_TIMES.with_borrow(|times| times.back().copied().unwrap_or_default())
+}
+
+/// Frames shown per second, averaged over the last [`FRAME_HISTORY`] frames.
+#[must_use]
+pub fn fps() -> f32 {
+    let average = average_frame_time();
+    if average.is_zero() {
+        0.
+    } else {
+        average.as_secs_f32().recip()
+    }
+}
+
+/// Average time between frames being shown, over the last [`FRAME_HISTORY`] frames.
+#[must_use]
+pub fn average_frame_time() -> Duration {
+    FRAME_TIMES.with_borrow(|times| {
+        let count = u32::try_from(times.len()).unwrap_or(u32::MAX).max(1);
+        times.iter().sum::<Duration>() / count
+    })
+}
+
+/// A frame time percentile over the last [`FRAME_HISTORY`] frames, with `percentile` from 0 to 1.
+/// For example, `0.99` is a time that only 1% of frames took longer than, which shows hitches
+/// that the average hides.
+#[must_use]
+pub fn frame_time_percentile(percentile: f32) -> Duration {
+    FRAME_TIMES.with_borrow(|times| {
+        if times.is_empty() {
+            return Duration::ZERO;
+        }
+        let mut sorted: Vec<_> = times.iter().copied().collect();
+        sorted.sort_unstable();
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let index = (percentile.clamp(0., 1.) * (sorted.len() - 1) as f32).round() as usize;
+        sorted[index]
+    })
+}
+
+/// Starts a new frame, which took `delta` since the previous one.
+pub(crate) fn advance(delta: Duration) {
+    let mut clock = CLOCK.get();
+    clock.delta = delta.min(MAX_DELTA);
+    clock.elapsed += clock.delta;
+    CLOCK.set(clock);
+}
+
+/// Counts a finished frame.
+pub(crate) fn end_frame() {
+    let mut clock = CLOCK.get();
+    clock.frame += 1;
+    CLOCK.set(clock);
+}
+
+/// Records how long input waited before being handled by an update.
+pub(crate) fn record_input_latency(latency: Duration) {
+    let mut clock = CLOCK.get();
+    clock.input = latency;
+    CLOCK.set(clock);
+}
+
+/// Records how long the previous frame took to draw and present, and the time since the frame
+/// before it was shown.
+pub(crate) fn record_frame(frame: Duration, cpu: Duration, present: Duration) {
+    FRAME_TIMES.with_borrow_mut(|times| {
+        if times.len() == FRAME_HISTORY {
+            times.pop_front();
+        }
+        times.push_back(frame);
+    });
+
+    let mut clock = CLOCK.get();
+    clock.cpu = cpu;
+    clock.present = present;
+    CLOCK.set(clock);
+}
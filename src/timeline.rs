@@ -0,0 +1,331 @@
+//! Timelines for cutscenes, where tracks of keyframes animate values over time and cues trigger
+//! things like sounds and dialog.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let mut camera = Camera::default();
+//! use baba::math::Ease;
+//! use baba::timeline::{Cue, Timeline, Track};
+//!
+//! let mut intro = Timeline::new()
+//!     .with_vec2(
+//!         "camera",
+//!         Track::new()
+//!             .key(0., vec2(0., 0.))
+//!             .key_eased(3., vec2(400., 120.), Ease::InOutSine),
+//!     )
+//!     .with_float("fade", Track::new().key(0., 1.).key(1., 0.))
+//!     .with_cue(1.5, "thunder")
+//!     .with_cue(3., "villain_speaks");
+//! intro.play();
+//!
+//! // Every frame
+//! intro.update();
+//! camera.position = intro.vec2("camera").unwrap_or(camera.position);
+//! for cue in baba::events::drain::<Cue>() {
+//!     match cue.name.as_str() {
+//!         "thunder" => { /* play a sound */ }
+//!         "villain_speaks" => { /* open a dialog box */ }
+//!         _ => {}
+//!     }
+//! }
+//! ```
+//!
+//! Cues are sent as [events][crate::events] when the timeline passes them. With the `serde`
+//! feature, timelines can be serialized, so cutscenes can be written as data files.
+
+use std::collections::BTreeMap;
+
+use glam::{FloatExt, Vec2};
+
+use crate::events;
+use crate::gfx::Color;
+use crate::math::Ease;
+use crate::time;
+
+/// Values which can be animated by a [`Track`].
+pub trait Animate: Copy {
+    /// Interpolates between two values, with `t` from 0 to 1.
+    #[must_use]
+    fn animate(self, to: Self, t: f32) -> Self;
+}
+
+impl Animate for f32 {
+    fn animate(self, to: Self, t: f32) -> Self {
+        self.lerp(to, t)
+    }
+}
+
+impl Animate for Vec2 {
+    fn animate(self, to: Self, t: f32) -> Self {
+        self.lerp(to, t)
+    }
+}
+
+impl Animate for Color {
+    fn animate(self, to: Self, t: f32) -> Self {
+        self.lerp_to_gamma(to, t)
+    }
+}
+
+/// A value at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframe<T> {
+    /// Time of this keyframe, in seconds from the start of the timeline.
+    pub time: f32,
+    /// The value at this time.
+    pub value: T,
+    /// How the value moves from the previous keyframe to this one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ease: Ease,
+}
+
+/// Keyframes for a single value.
+#[must_use]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "T: serde::Deserialize<'de>"))
+)]
+pub struct Track<T> {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "sorted_by_time"))]
+    keys: Vec<Keyframe<T>>,
+}
+
+impl<T> Default for Track<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Track<T> {
+    /// Creates a track with no keyframes.
+    pub const fn new() -> Self {
+        Self { keys: Vec::new() }
+    }
+
+    /// Adds a keyframe, moving to it at a constant speed.
+    pub fn key(self, time: f32, value: T) -> Self {
+        self.key_eased(time, value, Ease::Linear)
+    }
+
+    /// Adds a keyframe, moving to it from the previous one along an easing curve.
+    pub fn key_eased(mut self, time: f32, value: T, ease: Ease) -> Self {
+        self.insert(Keyframe { time, value, ease });
+        self
+    }
+
+    /// Adds a keyframe, keeping them sorted by time.
+    pub fn insert(&mut self, key: Keyframe<T>) {
+        let index = self.keys.partition_point(|k| k.time <= key.time);
+        self.keys.insert(index, key);
+    }
+
+    /// Every keyframe, in order.
+    #[must_use]
+    pub fn keys(&self) -> &[Keyframe<T>] {
+        &self.keys
+    }
+
+    /// Time of the last keyframe.
+    #[must_use]
+    pub fn duration(&self) -> f32 {
+        self.keys.last().map_or(0., |k| k.time)
+    }
+}
+
+impl<T: Animate> Track<T> {
+    /// The value at a point in time, or `None` if there are no keyframes. Before the first
+    /// keyframe and after the last one, the value stays where they are.
+    #[must_use]
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let next = self.keys.partition_point(|k| k.time <= time);
+        let (Some(from), Some(to)) = (self.keys.get(next.wrapping_sub(1)), self.keys.get(next))
+        else {
+            return self
+                .keys
+                .get(next.min(self.keys.len().saturating_sub(1)))
+                .map(|k| k.value);
+        };
+        let t = (time - from.time) / (to.time - from.time);
+        Some(from.value.animate(to.value, to.ease.apply(t)))
+    }
+}
+
+/// Something that happens at a point in a [`Timeline`]. When it's reached, it's sent as an
+/// [event][crate::events].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cue {
+    /// Time of this cue, in seconds from the start of the timeline.
+    pub time: f32,
+    /// What happens, like the name of a sound or a line of dialog.
+    pub name: String,
+}
+
+/// Named tracks and cues, played back over time.
+#[must_use]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timeline {
+    #[cfg_attr(feature = "serde", serde(default))]
+    floats: BTreeMap<String, Track<f32>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    vectors: BTreeMap<String, Track<Vec2>>,
+    #[cfg_attr(feature = "serde", serde(default, deserialize_with = "sorted_by_time"))]
+    cues: Vec<Cue>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    time: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    next_cue: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    playing: bool,
+}
+
+impl Timeline {
+    /// Creates an empty timeline. It doesn't start playing until [`play`][Self::play] is called.
+    pub const fn new() -> Self {
+        Self {
+            floats: BTreeMap::new(),
+            vectors: BTreeMap::new(),
+            cues: Vec::new(),
+            time: 0.,
+            next_cue: 0,
+            playing: false,
+        }
+    }
+
+    /// Adds a track for a number, like a fade or the camera's zoom.
+    pub fn with_float(mut self, name: impl Into<String>, track: Track<f32>) -> Self {
+        self.floats.insert(name.into(), track);
+        self
+    }
+
+    /// Adds a track for a vector, like a position.
+    pub fn with_vec2(mut self, name: impl Into<String>, track: Track<Vec2>) -> Self {
+        self.vectors.insert(name.into(), track);
+        self
+    }
+
+    /// Adds a cue.
+    pub fn with_cue(mut self, time: f32, name: impl Into<String>) -> Self {
+        let name = name.into();
+        let index = self.cues.partition_point(|c| c.time <= time);
+        self.cues.insert(index, Cue { time, name });
+        self.next_cue = self.cues.partition_point(|c| c.time < self.time);
+        self
+    }
+
+    /// The current value of a number track, or `None` if there's no such track.
+    #[must_use]
+    pub fn float(&self, name: &str) -> Option<f32> {
+        self.floats.get(name)?.sample(self.time)
+    }
+
+    /// The current value of a vector track, or `None` if there's no such track.
+    #[must_use]
+    pub fn vec2(&self, name: &str) -> Option<Vec2> {
+        self.vectors.get(name)?.sample(self.time)
+    }
+
+    /// Starts or resumes playing.
+    pub const fn play(&mut self) {
+        self.playing = true;
+    }
+
+    /// Pauses, keeping the current time.
+    pub const fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Is the timeline playing?
+    #[must_use]
+    pub const fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Has the timeline reached its end?
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.time >= self.duration()
+    }
+
+    /// Current time, in seconds from the start.
+    #[must_use]
+    pub const fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Time of the last keyframe or cue.
+    #[must_use]
+    pub fn duration(&self) -> f32 {
+        let floats = self.floats.values().map(Track::duration);
+        let vectors = self.vectors.values().map(Track::duration);
+        let cues = self.cues.iter().map(|c| c.time);
+        floats.chain(vectors).chain(cues).fold(0., f32::max)
+    }
+
+    /// Jumps to a point in time, like to skip a cutscene. Cues before it aren't sent, and cues
+    /// after it will be.
+    pub fn seek(&mut self, time: f32) {
+        self.time = time.clamp(0., self.duration());
+        self.next_cue = self.cues.partition_point(|c| c.time < self.time);
+    }
+
+    /// Moves time forward while playing, sending any cues that are passed. This should be
+    /// called every frame. The timeline stops once it reaches the end.
+    pub fn update(&mut self) {
+        if !self.playing {
+            return;
+        }
+        let duration = self.duration();
+        self.time = (self.time + time::delta()).min(duration);
+        while let Some(cue) = self.cues.get(self.next_cue) {
+            if cue.time > self.time {
+                break;
+            }
+            events::emit(cue.clone());
+            self.next_cue += 1;
+        }
+        if self.time >= duration {
+            self.playing = false;
+        }
+    }
+}
+
+/// Something placed at a point in a timeline.
+#[cfg(feature = "serde")]
+trait Timed {
+    fn time(&self) -> f32;
+}
+
+#[cfg(feature = "serde")]
+impl<T> Timed for Keyframe<T> {
+    fn time(&self) -> f32 {
+        self.time
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Timed for Cue {
+    fn time(&self) -> f32 {
+        self.time
+    }
+}
+
+/// Deserializes keyframes or cues, sorting them by time, as hand-written files may be out of
+/// order.
+#[cfg(feature = "serde")]
+fn sorted_by_time<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de> + Timed,
+{
+    let mut items = <Vec<T> as serde::Deserialize>::deserialize(deserializer)?;
+    // A stable sort, so things at the same time keep their order, like with `insert`
+    items.sort_by(|a, b| a.time().total_cmp(&b.time()));
+    Ok(items)
+}
@@ -0,0 +1,130 @@
+//! Callbacks which run after a delay, or repeatedly.
+//!
+//! Timers follow [game time][crate::time], so they stop while the game is paused, and run before
+//! the game's `update` on the frame they're due.
+//!
+//! ```no_run
+//! use baba::timer;
+//!
+//! struct SpawnEnemy;
+//!
+//! let spawner = timer::every(2., || baba::events::emit(SpawnEnemy));
+//! timer::after(30., move || spawner.cancel());
+//! ```
+
+use std::cell::{Cell, RefCell};
+
+use crate::time;
+
+struct Timer {
+    id: u64,
+    remaining: f32,
+    interval: Option<f32>,
+    // Taken out while it runs
+    callback: Option<Box<dyn FnMut()>>,
+}
+
+thread_local! {
+    static TIMERS: RefCell<Vec<Timer>> = const { RefCell::new(Vec::new()) };
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A timer which was started with [`after`] or [`every`], for cancelling it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerHandle(u64);
+
+impl TimerHandle {
+    /// Stops the timer, so its callback doesn't run again. This does nothing if it already
+    /// finished.
+    pub fn cancel(self) {
+        TIMERS.with_borrow_mut(|timers| timers.retain(|t| t.id != self.0));
+    }
+
+    /// Is the timer still waiting to run?
+    #[must_use]
+    pub fn is_active(self) -> bool {
+        // A timer which runs once stops being active as soon as its callback starts
+        let active = |t: &Timer| t.callback.is_some() || t.interval.is_some();
+        TIMERS.with_borrow(|timers| timers.iter().any(|t| t.id == self.0 && active(t)))
+    }
+
+    /// Seconds until the timer runs next, or `None` if it's not active.
+    #[must_use]
+    pub fn remaining(self) -> Option<f32> {
+        TIMERS.with_borrow(|timers| timers.iter().find(|t| t.id == self.0).map(|t| t.remaining))
+    }
+}
+
+/// Runs a function once, after some seconds.
+pub fn after(seconds: f32, f: impl FnOnce() + 'static) -> TimerHandle {
+    let mut f = Some(f);
+    start(seconds, None, move || {
+        if let Some(f) = f.take() {
+            f();
+        }
+    })
+}
+
+/// Runs a function every some seconds, starting after the first interval, until it's cancelled.
+pub fn every(seconds: f32, f: impl FnMut() + 'static) -> TimerHandle {
+    start(seconds, Some(seconds), f)
+}
+
+/// Cancels every timer.
+pub fn cancel_all() {
+    TIMERS.with_borrow_mut(Vec::clear);
+}
+
+fn start(seconds: f32, interval: Option<f32>, f: impl FnMut() + 'static) -> TimerHandle {
+    let id = NEXT_ID.get();
+    NEXT_ID.set(id + 1);
+    TIMERS.with_borrow_mut(|timers| {
+        timers.push(Timer {
+            id,
+            remaining: seconds,
+            interval,
+            callback: Some(Box::new(f)),
+        });
+    });
+    TimerHandle(id)
+}
+
+/// Counts down every timer, and runs the ones which are due. Called by the game loop.
+pub(crate) fn update() {
+    let delta = time::delta();
+    let due: Vec<u64> = TIMERS.with_borrow_mut(|timers| {
+        for timer in timers.iter_mut() {
+            timer.remaining -= delta;
+        }
+        let due = timers.iter().filter(|t| t.remaining <= 0.);
+        due.map(|t| t.id).collect()
+    });
+
+    for id in due {
+        let find = |timers: &mut Vec<Timer>| timers.iter().position(|t| t.id == id);
+        // An earlier callback may have cancelled this one
+        let Some(mut callback) = TIMERS.with_borrow_mut(|timers| {
+            let index = find(timers)?;
+            timers[index].callback.take()
+        }) else {
+            continue;
+        };
+
+        // Callbacks run outside of the borrow, so they can start and cancel timers
+        callback();
+        TIMERS.with_borrow_mut(|timers| {
+            // It may have been cancelled while running
+            let Some(index) = find(timers) else {
+                return;
+            };
+            let timer = &mut timers[index];
+            if let Some(interval) = timer.interval {
+                // If several intervals passed, the rest run on the next frames
+                timer.remaining = (timer.remaining + interval).max(0.);
+                timer.callback = Some(callback);
+            } else {
+                timers.remove(index);
+            }
+        });
+    }
+}
@@ -0,0 +1,225 @@
+//! Turn order for turn-based games.
+//!
+//! A [`Turns`] scheduler keeps actors sorted by initiative, and gives each of them some action
+//! points when their turn starts. Between actions, it can wait for things to finish, like
+//! animations or player input, before the game moves on.
+//!
+//! ```
+//! use baba::turns::Turns;
+//!
+//! let mut turns = Turns::new();
+//! turns.add("goblin", 5, 2);
+//! turns.add("hero", 10, 3);
+//!
+//! // every frame
+//! if turns.update() {
+//!     assert_eq!(turns.current(), Some(&"hero"));
+//!     turns.spend(1);
+//!     // play a 30 frame attack animation before the next action
+//!     turns.wait_frames(30);
+//! }
+//! ```
+//!
+//! There's no coroutine support in Rust yet, so waits are conditions polled by
+//! [`update`][Turns::update] rather than suspended code.
+
+use std::fmt;
+
+struct Actor<A> {
+    id: A,
+    initiative: i32,
+    max_points: u32,
+}
+
+/// Something [`Turns`] is waiting on.
+enum Gate {
+    Frames(u32),
+    Until(Box<dyn FnMut() -> bool>),
+}
+
+impl Gate {
+    /// Returns whether this gate is still closed.
+    fn poll(&mut self) -> bool {
+        match self {
+            Self::Frames(0) => false,
+            Self::Frames(frames) => {
+                *frames -= 1;
+                true
+            }
+            Self::Until(f) => !f(),
+        }
+    }
+}
+
+/// A scheduler which cycles through actors, in order of initiative.
+///
+/// Actors are identified by any value you like, such as an entity index or a name.
+pub struct Turns<A> {
+    actors: Vec<Actor<A>>,
+    current: usize,
+    points: u32,
+    round: u32,
+    started: bool,
+    gates: Vec<Gate>,
+}
+
+impl<A> Default for Turns<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: fmt::Debug> fmt::Debug for Turns<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Turns")
+            .field("current", &self.current())
+            .field("points", &self.points)
+            .field("round", &self.round)
+            .field("waiting", &!self.gates.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A> Turns<A> {
+    /// Creates a scheduler with no actors.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            actors: Vec::new(),
+            current: 0,
+            points: 0,
+            round: 0,
+            started: false,
+            gates: Vec::new(),
+        }
+    }
+
+    /// Adds an actor. Actors with higher initiative go first; ties go in the order they were
+    /// added.
+    ///
+    /// The actor is given `action_points` every time its turn starts. The first turn starts on
+    /// the first [`update`][Self::update], so actors added before that are all in order.
+    pub fn add(&mut self, actor: A, initiative: i32, action_points: u32) {
+        let index = self.actors.partition_point(|a| a.initiative >= initiative);
+        if self.started && index <= self.current {
+            self.current += 1;
+        }
+
+        self.actors.insert(
+            index,
+            Actor {
+                id: actor,
+                initiative,
+                max_points: action_points,
+            },
+        );
+    }
+
+    /// Removes an actor, such as when it dies. If it's currently its turn, the turn ends.
+    pub fn remove(&mut self, actor: &A) -> Option<A>
+    where
+        A: PartialEq,
+    {
+        let index = self.actors.iter().position(|a| &a.id == actor)?;
+        let removed = self.actors.remove(index);
+
+        if index < self.current {
+            self.current -= 1;
+        } else if index == self.current {
+            if index < self.actors.len() {
+                self.start_turn(index);
+            } else {
+                self.start_turn(0);
+                self.round += 1;
+            }
+        }
+        Some(removed.id)
+    }
+
+    /// The actor whose turn it is.
+    #[must_use]
+    pub fn current(&self) -> Option<&A> {
+        self.actors.get(self.current).map(|a| &a.id)
+    }
+
+    /// All actors, in turn order.
+    pub fn order(&self) -> impl Iterator<Item = &A> {
+        self.actors.iter().map(|a| &a.id)
+    }
+
+    /// How many full rounds have passed.
+    #[must_use]
+    pub const fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// Action points the current actor has left.
+    #[must_use]
+    pub const fn action_points(&self) -> u32 {
+        self.points
+    }
+
+    /// Spends some of the current actor's action points. Returns `false`, spending nothing, if
+    /// there aren't enough.
+    ///
+    /// The turn doesn't end on its own when points run out, see [`end_turn`][Self::end_turn].
+    pub const fn spend(&mut self, points: u32) -> bool {
+        let Some(left) = self.points.checked_sub(points) else {
+            return false;
+        };
+        self.points = left;
+        true
+    }
+
+    /// Ends the current actor's turn, moving on to the next one.
+    pub fn end_turn(&mut self) {
+        if self.actors.is_empty() {
+            return;
+        }
+
+        let next = (self.current + 1) % self.actors.len();
+        if next == 0 {
+            self.round += 1;
+        }
+        self.start_turn(next);
+    }
+
+    fn start_turn(&mut self, index: usize) {
+        self.current = index;
+        self.points = self.actors.get(index).map_or(0, |a| a.max_points);
+    }
+
+    /// Waits a number of frames before the next action.
+    pub fn wait_frames(&mut self, frames: u32) {
+        self.gates.push(Gate::Frames(frames));
+    }
+
+    /// Waits until a condition is met before the next action. It's checked once every
+    /// [`update`][Self::update].
+    pub fn wait_until(&mut self, condition: impl FnMut() -> bool + 'static) {
+        self.gates.push(Gate::Until(Box::new(condition)));
+    }
+
+    /// Is this waiting on anything?
+    #[must_use]
+    pub const fn is_waiting(&self) -> bool {
+        !self.gates.is_empty()
+    }
+
+    /// Stops waiting on everything.
+    pub fn skip_waits(&mut self) {
+        self.gates.clear();
+    }
+
+    /// Checks on everything this is waiting for. Call this once per frame.
+    ///
+    /// Returns `true` if the current actor may act.
+    pub fn update(&mut self) -> bool {
+        if !self.started && !self.actors.is_empty() {
+            self.started = true;
+            self.start_turn(0);
+        }
+        self.gates.retain_mut(Gate::poll);
+        self.gates.is_empty() && !self.actors.is_empty()
+    }
+}
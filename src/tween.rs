@@ -0,0 +1,258 @@
+//! Tweens, which move a value to a target over time along an [easing curve][Ease].
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! use baba::tween::{Ease, Tween};
+//!
+//! let mut slide = Tween::new(vec2(-200., 100.), vec2(100., 100.), 0.5)
+//!     .with_ease(Ease::OutBack)
+//!     .then(vec2(100., 300.), 1.)
+//!     .on_finished(|| info!("Done sliding"));
+//!
+//! // Every frame
+//! let position = slide.update();
+//! ```
+//!
+//! Tweens can also be [started](start) and left to the game loop, which updates them before the
+//! game's `update` and passes each new value to a function.
+//!
+//! ```no_run
+//! # use std::{cell::Cell, rc::Rc};
+//! use baba::tween::{self, Tween};
+//!
+//! let fade = Rc::new(Cell::new(1.));
+//! let target = fade.clone();
+//! tween::start(Tween::new(1., 0., 2.), move |value| target.set(value));
+//! ```
+//!
+//! Anything that implements [`Animate`], like `f32`, [`Vec2`][glam::Vec2] and
+//! [`Color`][crate::gfx::Color], can be tweened.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+pub use crate::math::Ease;
+use crate::time;
+pub use crate::timeline::Animate;
+
+#[derive(Debug, Clone, Copy)]
+struct Segment<T> {
+    to: T,
+    duration: f32,
+    ease: Ease,
+}
+
+/// Called when a [`Tween`] finishes.
+type TweenCallback = Box<dyn FnMut()>;
+
+/// A value which moves from a start to one or more targets, one after the other.
+#[must_use]
+pub struct Tween<T> {
+    from: T,
+    segments: Vec<Segment<T>>,
+    current: usize,
+    elapsed: f32,
+    value: T,
+    on_finished: Option<TweenCallback>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Tween<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tween")
+            .field("value", &self.value)
+            .field("current", &self.current)
+            .field("elapsed", &self.elapsed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Animate> Tween<T> {
+    /// Creates a tween from `from` to `to`, taking `duration` seconds at a constant speed.
+    pub fn new(from: T, to: T, duration: f32) -> Self {
+        Self {
+            from,
+            segments: vec![Segment {
+                to,
+                duration,
+                ease: Ease::Linear,
+            }],
+            current: 0,
+            elapsed: 0.,
+            value: from,
+            on_finished: None,
+        }
+    }
+
+    /// Sets the easing curve of the last target added.
+    pub fn with_ease(mut self, ease: Ease) -> Self {
+        if let Some(segment) = self.segments.last_mut() {
+            segment.ease = ease;
+        }
+        self
+    }
+
+    /// Adds another target, which is moved to from the previous one once it's reached.
+    pub fn then(mut self, to: T, duration: f32) -> Self {
+        self.segments.push(Segment {
+            to,
+            duration,
+            ease: Ease::Linear,
+        });
+        self
+    }
+
+    /// Stays at the previous target for some seconds before moving on.
+    pub fn then_wait(self, seconds: f32) -> Self {
+        let to = self.segments.last().map_or(self.from, |s| s.to);
+        self.then(to, seconds)
+    }
+
+    /// Sets a function to call when the tween reaches its last target.
+    pub fn on_finished(mut self, f: impl FnMut() + 'static) -> Self {
+        self.on_finished = Some(Box::new(f));
+        self
+    }
+
+    /// Moves the value forward in time, returning it. This should be called every frame.
+    pub fn update(&mut self) -> T {
+        if self.is_finished() {
+            return self.value;
+        }
+        self.elapsed += time::delta();
+        while let Some(segment) = self.segments.get(self.current) {
+            if self.elapsed < segment.duration {
+                break;
+            }
+            self.elapsed -= segment.duration;
+            self.current += 1;
+        }
+        self.value = self.sample();
+        if self.is_finished() {
+            if let Some(f) = &mut self.on_finished {
+                f();
+            }
+        }
+        self.value
+    }
+
+    fn sample(&self) -> T {
+        let Some(segment) = self.segments.get(self.current) else {
+            return self.segments.last().map_or(self.from, |s| s.to);
+        };
+        let start = match self.current.checked_sub(1) {
+            Some(previous) => self.segments[previous].to,
+            None => self.from,
+        };
+        let t = if segment.duration > 0. {
+            self.elapsed / segment.duration
+        } else {
+            1.
+        };
+        start.animate(segment.to, segment.ease.apply(t))
+    }
+
+    /// The current value.
+    #[must_use]
+    pub const fn value(&self) -> T {
+        self.value
+    }
+
+    /// Has the tween reached its last target?
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.current >= self.segments.len()
+    }
+
+    /// Total time to reach the last target, in seconds.
+    #[must_use]
+    pub fn duration(&self) -> f32 {
+        self.segments.iter().map(|s| s.duration).sum()
+    }
+
+    /// Goes back to the start.
+    pub const fn restart(&mut self) {
+        self.current = 0;
+        self.elapsed = 0.;
+        self.value = self.from;
+    }
+}
+
+struct Running {
+    id: u64,
+    step: Option<Box<dyn FnMut() -> bool>>,
+}
+
+thread_local! {
+    static TWEENS: RefCell<Vec<Running>> = const { RefCell::new(Vec::new()) };
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A tween which was [started](start), for stopping it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TweenHandle(u64);
+
+impl TweenHandle {
+    /// Stops the tween where it is. Its `on_finished` function isn't called.
+    pub fn cancel(self) {
+        TWEENS.with_borrow_mut(|tweens| tweens.retain(|t| t.id != self.0));
+    }
+
+    /// Is the tween still running?
+    #[must_use]
+    pub fn is_active(self) -> bool {
+        TWEENS.with_borrow(|tweens| tweens.iter().any(|t| t.id == self.0))
+    }
+}
+
+/// Hands a tween to the game loop, which updates it every frame and calls `apply` with its
+/// value, until it's finished.
+pub fn start<T: Animate + 'static>(
+    mut tween: Tween<T>,
+    mut apply: impl FnMut(T) + 'static,
+) -> TweenHandle {
+    let id = NEXT_ID.get();
+    NEXT_ID.set(id + 1);
+    let step = move || {
+        apply(tween.update());
+        !tween.is_finished()
+    };
+    TWEENS.with_borrow_mut(|tweens| {
+        tweens.push(Running {
+            id,
+            step: Some(Box::new(step)),
+        });
+    });
+    TweenHandle(id)
+}
+
+/// Stops every tween which was [started](start).
+pub fn cancel_all() {
+    TWEENS.with_borrow_mut(Vec::clear);
+}
+
+/// Updates every tween which was started. Called by the game loop.
+pub(crate) fn update() {
+    let ids: Vec<u64> = TWEENS.with_borrow(|tweens| tweens.iter().map(|t| t.id).collect());
+    for id in ids {
+        let find = |tweens: &mut Vec<Running>| tweens.iter().position(|t| t.id == id);
+        let Some(mut step) = TWEENS.with_borrow_mut(|tweens| {
+            let index = find(tweens)?;
+            tweens[index].step.take()
+        }) else {
+            continue;
+        };
+
+        // The tween runs outside of the borrow, so its functions can start and cancel tweens
+        let running = step();
+        TWEENS.with_borrow_mut(|tweens| {
+            // It may have been cancelled while running
+            if let Some(index) = find(tweens) {
+                if running {
+                    tweens[index].step = Some(step);
+                } else {
+                    tweens.remove(index);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,210 @@
+//! A surface of water, with waves which ripple out from splashes.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let (player, landed) = (Vec2::ZERO, true);
+//! use baba::water::Water;
+//!
+//! let pool = vec2(0., 120.);
+//! let blue = Color::from_rgba_unmultiplied(40, 90, 200, 160);
+//! let mut water = Water::new(320., 60.).with_color(blue);
+//!
+//! // Every frame
+//! if landed {
+//!     water.splash(player.x - pool.x, 200.);
+//! }
+//! water.update();
+//! gfx::draw(&water, pool);
+//! ```
+//!
+//! The surface is a row of springs, which pull back to rest and tug on their neighbours. It's
+//! drawn as a strip from the surface down to the bottom, with its top left corner at the origin.
+
+use glam::{vec2, Vec2};
+
+use crate::alloc::with_frame_arena;
+use crate::gfx::{Canvas, Color, Drawable, Texture, Transform, Vertex};
+use crate::time;
+
+/// Default distance between springs, in pixels.
+const SPACING: f32 = 4.;
+/// Most steps taken in one update, so very fast waves or long frames can't stall the game.
+const MAX_STEPS: f32 = 64.;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Spring {
+    height: f32,
+    velocity: f32,
+}
+
+/// A rectangle of water, whose top edge moves in waves.
+#[must_use]
+#[derive(Clone)]
+pub struct Water {
+    width: f32,
+    depth: f32,
+    springs: Vec<Spring>,
+    stiffness: f32,
+    damping: f32,
+    speed: f32,
+    color: Color,
+    texture: Option<Texture>,
+}
+
+impl Water {
+    /// Creates calm water of a size, in pixels. It's always at least 1 pixel wide.
+    #[allow(clippy::cast_sign_loss)]
+    pub fn new(width: f32, depth: f32) -> Self {
+        Self::with_resolution(width, depth, (width / SPACING).ceil() as usize + 1)
+    }
+
+    /// Creates calm water with a number of springs along its surface. More springs make
+    /// smoother waves, but take longer to update. There's always at least 2, and the water is
+    /// always at least 1 pixel wide.
+    pub fn with_resolution(width: f32, depth: f32, springs: usize) -> Self {
+        Self {
+            width: width.max(1.),
+            depth,
+            springs: vec![Spring::default(); springs.max(2)],
+            stiffness: 30.,
+            damping: 2.,
+            speed: 150.,
+            color: Color::WHITE,
+            texture: None,
+        }
+    }
+
+    /// Sets the color of the water, which multiplies the texture if there is one. Defaults to
+    /// white.
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Stretches a texture over the water, which bends along with the surface.
+    pub fn with_texture(mut self, texture: &Texture) -> Self {
+        self.texture = Some(texture.clone());
+        self
+    }
+
+    /// Sets how strongly the surface is pulled back to rest. Defaults to 30.
+    pub const fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    /// Sets how quickly waves calm down. Defaults to 2.
+    pub const fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets how fast waves travel along the surface, in pixels per second. Defaults to 150.
+    pub const fn with_wave_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// The width of the water, in pixels.
+    #[must_use]
+    pub const fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// The depth of the water at rest, in pixels.
+    #[must_use]
+    pub const fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    fn spacing(&self) -> f32 {
+        self.width / (self.springs.len() - 1) as f32
+    }
+
+    /// Pushes the surface down at a position from the left edge, with some speed in pixels per
+    /// second. Negative speeds pull it up instead.
+    #[allow(clippy::cast_sign_loss)]
+    pub fn splash(&mut self, x: f32, speed: f32) {
+        let i = (x / self.spacing()).round().max(0.) as usize;
+        if let Some(spring) = self.springs.get_mut(i) {
+            spring.velocity += speed;
+        }
+    }
+
+    /// How far the surface is below its resting height at a position from the left edge. This
+    /// is negative where a wave rises above it.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn height_at(&self, x: f32) -> f32 {
+        let last = self.springs.len() - 1;
+        let at = (x / self.spacing()).clamp(0., last as f32);
+        let i = (at as usize).min(last - 1);
+        let (a, b) = (self.springs[i].height, self.springs[i + 1].height);
+        a + (b - a) * (at - i as f32)
+    }
+
+    /// Moves the waves along. This should be called every frame.
+    #[allow(clippy::cast_sign_loss)]
+    pub fn update(&mut self) {
+        let delta = time::delta();
+        let spacing = self.spacing();
+        // Waves can't travel more than half a spring per step, or they blow up
+        let steps = (self.speed * delta / spacing * 2.)
+            .ceil()
+            .clamp(1., MAX_STEPS);
+        let dt = delta / steps;
+        let spread = (self.speed / spacing).powi(2);
+
+        for _ in 0..steps as u32 {
+            for i in 0..self.springs.len() {
+                let height = self.springs[i].height;
+                let left = self
+                    .springs
+                    .get(i.wrapping_sub(1))
+                    .map_or(height, |s| s.height);
+                let right = self.springs.get(i + 1).map_or(height, |s| s.height);
+
+                let spring = &mut self.springs[i];
+                let accel = spread * (left + right - 2. * height)
+                    - self.stiffness * height
+                    - self.damping * spring.velocity;
+                spring.velocity += accel * dt;
+            }
+            for spring in &mut self.springs {
+                spring.height += spring.velocity * dt;
+            }
+        }
+    }
+}
+
+impl Drawable for Water {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        let spacing = self.spacing();
+        let count = self.springs.len();
+        with_frame_arena(|arena| {
+            // Surface and bottom vertices are interleaved
+            let vertex = Vertex::new(Vec2::ZERO, self.color, Vec2::ZERO);
+            let verts = arena.alloc_slice_fill(count * 2, vertex);
+            let indices = arena.alloc_slice_fill((count - 1) * 6, 0);
+
+            for (i, spring) in self.springs.iter().enumerate() {
+                let x = i as f32 * spacing;
+                let u = x / self.width;
+                let surface = vec2(x, spring.height);
+                verts[i * 2].coord = transform.transform_point(surface);
+                verts[i * 2].uv = vec2(u, 0.);
+                verts[i * 2 + 1].coord = transform.transform_point(vec2(x, self.depth));
+                verts[i * 2 + 1].uv = vec2(u, 1.);
+            }
+            for i in 0..count - 1 {
+                let (a, b) = (i as i32 * 2, i as i32 * 2 + 2);
+                indices[i * 6..i * 6 + 6].copy_from_slice(&[a, b, a + 1, a + 1, b, b + 1]);
+            }
+
+            match &self.texture {
+                Some(texture) => canvas.draw_geometry(texture, verts, Some(indices)),
+                None => canvas.draw_colored_geometry(verts, Some(indices)),
+            }
+        });
+    }
+}
@@ -0,0 +1,244 @@
+//! Rain, snow and fog, drawn over the screen.
+//!
+//! ```no_run
+//! # use baba::prelude::*;
+//! # let camera = Camera::default();
+//! use baba::weather;
+//!
+//! let mut rain = weather::rain(0.5).with_wind(-40.);
+//!
+//! // Every frame, after drawing the world
+//! rain.update(Some(&camera));
+//! gfx::draw(&rain, Vec2::ZERO);
+//! ```
+//!
+//! Weather is drawn in screen coordinates, so draw it outside of
+//! [`with_camera`][crate::gfx::with_camera]. It fills the whole screen, whatever its size, and
+//! moves along with the camera passed to [`Weather::update`], so it looks like part of the world.
+
+use glam::{vec2, Vec2};
+
+use crate::alloc::with_frame_arena;
+use crate::gfx::{self, Camera, Canvas, Color, Drawable, Shape, Transform, Vertex};
+use crate::math::rng::Rng;
+use crate::math::TAU;
+use crate::time;
+
+/// Number of particles on a 100 by 100 area of the screen, at full intensity.
+const RAIN_DENSITY: f32 = 12.;
+const SNOW_DENSITY: f32 = 8.;
+const FOG_DENSITY: f32 = 0.15;
+
+/// Extra room around the screen, so particles don't pop in at the edges.
+const MARGIN: f32 = 64.;
+
+const QUAD_IDX: [i32; 6] = [0, 1, 2, 2, 1, 3];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Rain,
+    Snow,
+    Fog,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Particle {
+    position: Vec2,
+    speed: f32,
+    size: f32,
+    phase: f32,
+}
+
+/// A layer of rain, snow or fog. Create one with [`rain`], [`snow`] or [`fog`].
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Weather {
+    kind: Kind,
+    intensity: f32,
+    wind: f32,
+    color: Color,
+    parallax: f32,
+    area: Vec2,
+    zoom: f32,
+    last_camera: Option<Camera>,
+    particles: Vec<Particle>,
+    rng: Rng,
+}
+
+/// Falling rain. `intensity` goes from 0, for none, to 1 for a downpour.
+pub fn rain(intensity: f32) -> Weather {
+    Weather::new(
+        Kind::Rain,
+        intensity,
+        Color::from_rgba_unmultiplied(170, 190, 230, 160),
+    )
+}
+
+/// Falling snow. `intensity` goes from 0, for none, to 1 for a blizzard.
+pub fn snow(intensity: f32) -> Weather {
+    Weather::new(
+        Kind::Snow,
+        intensity,
+        Color::from_rgba_unmultiplied(255, 255, 255, 220),
+    )
+}
+
+/// Slowly drifting fog. `density` goes from 0, for clear skies, to 1 for thick fog.
+pub fn fog(density: f32) -> Weather {
+    Weather::new(
+        Kind::Fog,
+        density,
+        Color::from_rgba_unmultiplied(200, 205, 215, 40),
+    )
+}
+
+impl Weather {
+    const fn new(kind: Kind, intensity: f32, color: Color) -> Self {
+        Self {
+            kind,
+            intensity: intensity.clamp(0., 1.),
+            wind: 0.,
+            color,
+            parallax: 1.,
+            area: Vec2::ZERO,
+            zoom: 1.,
+            last_camera: None,
+            particles: Vec::new(),
+            rng: Rng::new(0x5eed),
+        }
+    }
+
+    /// Sets the wind, in pixels per second. Positive values blow to the right.
+    pub const fn with_wind(mut self, wind: f32) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    /// Sets the color of this weather.
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets how much this layer moves with the camera. 1 moves along with the world, less than
+    /// that looks further away, and 0 stays still on the screen. Defaults to 1.
+    pub const fn with_parallax(mut self, parallax: f32) -> Self {
+        self.parallax = parallax;
+        self
+    }
+
+    /// Changes the intensity, from 0 to 1. Particles come and go over the next few frames.
+    pub const fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity.clamp(0., 1.);
+    }
+
+    /// The intensity, from 0 to 1.
+    #[must_use]
+    pub const fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    /// Moves the particles along. This should be called every frame, with the camera the world
+    /// is drawn with, if any.
+    pub fn update(&mut self, camera: Option<&Camera>) {
+        let delta = time::delta();
+        let screen = gfx::screen_size();
+        self.area = screen + MARGIN * 2.;
+        self.zoom = camera.map_or(1., |c| c.zoom);
+
+        // Keep particles in place in the world while the camera moves
+        let scroll = match (camera, &self.last_camera) {
+            (Some(camera), Some(last)) => {
+                camera.world_to_screen(last.position) - camera.world_to_screen(camera.position)
+            }
+            _ => Vec2::ZERO,
+        };
+        self.last_camera = camera.copied();
+
+        let density = match self.kind {
+            Kind::Rain => RAIN_DENSITY,
+            Kind::Snow => SNOW_DENSITY,
+            Kind::Fog => FOG_DENSITY,
+        };
+        #[allow(clippy::cast_sign_loss)]
+        let count = (screen.x * screen.y / 10000. * density * self.intensity) as usize;
+        self.particles.truncate(count);
+        while self.particles.len() < count {
+            let particle = self.spawn();
+            self.particles.push(particle);
+        }
+
+        let (wind, area) = (self.wind, self.area);
+        let elapsed = time::elapsed();
+        for p in &mut self.particles {
+            let velocity = match self.kind {
+                Kind::Rain => vec2(wind, p.speed),
+                Kind::Snow => vec2(wind + (elapsed + p.phase).sin() * 20., p.speed),
+                Kind::Fog => vec2(wind + p.speed, 0.),
+            };
+            p.position += velocity * delta * self.zoom + scroll * self.parallax;
+            p.position = (p.position + MARGIN).rem_euclid(area) - MARGIN;
+        }
+    }
+
+    fn spawn(&mut self) -> Particle {
+        let position = vec2(self.rng.float(), self.rng.float()) * self.area - MARGIN;
+        let t = self.rng.float();
+        let phase = self.rng.float() * TAU;
+        let (speed, size) = match self.kind {
+            Kind::Rain => (500. + t * 300., 8. + t * 8.),
+            Kind::Snow => (30. + t * 40., 1. + t * 2.),
+            Kind::Fog => ((t - 0.5) * 16., 48. + t * 64.),
+        };
+        Particle {
+            position,
+            speed,
+            size,
+            phase,
+        }
+    }
+
+    fn draw_particles(&self, canvas: &mut Canvas, transform: Transform) {
+        with_frame_arena(|arena| {
+            let count = self.particles.len();
+            let vertex = Vertex::from_xy_color(Vec2::ZERO, self.color);
+            let verts = arena.alloc_slice_fill(count * 4, vertex);
+            let indices = arena.alloc_slice_fill(count * 6, 0);
+
+            for (i, p) in self.particles.iter().enumerate() {
+                let size = p.size * self.zoom;
+                let (x, y) = if self.kind == Kind::Rain {
+                    // A thin streak, along the direction it's falling
+                    let along = vec2(self.wind, p.speed).normalize_or_zero();
+                    (along.perp(), along * size)
+                } else {
+                    (vec2(size, 0.), vec2(0., size))
+                };
+                let corners = [Vec2::ZERO, x, y, x + y].map(|c| p.position + c);
+                for (j, corner) in corners.into_iter().enumerate() {
+                    verts[i * 4 + j].coord = transform.transform_point(corner);
+                }
+                let first = i as i32 * 4;
+                indices[i * 6..i * 6 + 6].copy_from_slice(&QUAD_IDX.map(|j| first + j));
+            }
+
+            canvas.draw_colored_geometry(verts, Some(indices));
+        });
+    }
+}
+
+impl Drawable for Weather {
+    fn draw(&self, canvas: &mut Canvas, transform: Transform) {
+        if self.particles.is_empty() {
+            return;
+        }
+        if self.kind == Kind::Fog {
+            for p in &self.particles {
+                let shape = Shape::circle(p.size * self.zoom).color(self.color);
+                shape.draw(canvas, transform.translate(p.position));
+            }
+        } else {
+            self.draw_particles(canvas, transform);
+        }
+    }
+}